@@ -298,6 +298,7 @@ pub fn build_impl(cx: &DocContext, did: DefId, ret: &mut Vec<clean::Item>) {
         if !cx.access_levels.borrow().is_doc_reachable(traitref.def_id) {
             return
         }
+        clean::record_marker_trait(cx, traitref.def_id);
     }
 
     // If this is an auto impl, then bail out early here
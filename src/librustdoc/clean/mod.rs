@@ -133,6 +133,8 @@ impl<'a, 'tcx> Clean<Crate> for visit_ast::RustdocVisitor<'a, 'tcx> {
             r.deref_trait_did = cx.tcx.lang_items().deref_trait();
             r.deref_mut_trait_did = cx.tcx.lang_items().deref_mut_trait();
             r.owned_box_did = cx.tcx.lang_items().owned_box();
+            r.marker_trait_dids.extend(cx.tcx.lang_items().sized_trait());
+            r.marker_trait_dids.extend(cx.tcx.lang_items().sync_trait());
         }
 
         let mut externs = Vec::new();
@@ -369,6 +371,17 @@ impl Item {
         }
     }
 
+    /// Returns whether this item is marked `#[non_exhaustive]`, meaning more
+    /// fields or variants may be added to it later on.
+    pub fn is_non_exhaustive(&self) -> bool {
+        self.attrs.other_attrs.iter().any(|a| a.check_name("non_exhaustive"))
+    }
+
+    /// Returns whether this item is marked `#[doc(hidden)]`.
+    pub fn is_doc_hidden(&self) -> bool {
+        self.attrs.lists("doc").has_word("hidden")
+    }
+
     pub fn stability_class(&self) -> Option<String> {
         self.stability.as_ref().and_then(|ref s| {
             let mut classes = Vec::with_capacity(2);
@@ -1314,6 +1327,7 @@ impl<'a> Clean<Method> for (&'a hir::MethodSig, &'a hir::Generics, hir::BodyId)
 #[derive(Clone, RustcEncodable, RustcDecodable, Debug)]
 pub struct TyMethod {
     pub unsafety: hir::Unsafety,
+    pub constness: hir::Constness,
     pub decl: FnDecl,
     pub generics: Generics,
     pub abi: Abi,
@@ -1556,6 +1570,7 @@ impl Clean<Item> for hir::TraitItem {
             hir::TraitItemKind::Method(ref sig, hir::TraitMethod::Required(ref names)) => {
                 TyMethodItem(TyMethod {
                     unsafety: sig.unsafety.clone(),
+                    constness: sig.constness,
                     decl: (&*sig.decl, &names[..]).clean(cx),
                     generics: self.generics.clean(cx),
                     abi: sig.abi
@@ -1654,12 +1669,12 @@ impl<'tcx> Clean<Item> for ty::AssociatedItem {
                     ty::ImplContainer(_) => true,
                     ty::TraitContainer(_) => self.defaultness.has_value()
                 };
+                let constness = if cx.tcx.is_const_fn(self.def_id) {
+                    hir::Constness::Const
+                } else {
+                    hir::Constness::NotConst
+                };
                 if provided {
-                    let constness = if cx.tcx.is_const_fn(self.def_id) {
-                        hir::Constness::Const
-                    } else {
-                        hir::Constness::NotConst
-                    };
                     MethodItem(Method {
                         unsafety: sig.unsafety(),
                         generics,
@@ -1670,6 +1685,7 @@ impl<'tcx> Clean<Item> for ty::AssociatedItem {
                 } else {
                     TyMethodItem(TyMethod {
                         unsafety: sig.unsafety(),
+                        constness,
                         generics,
                         decl,
                         abi: sig.abi(),
@@ -1897,6 +1913,23 @@ impl Type {
             _ => None,
         }
     }
+
+    /// Returns the associated type bindings of the last segment of a path,
+    /// such as `Item = u8` in `Iterator<Item = u8>`.
+    pub fn bindings(&self) -> Option<&[TypeBinding]> {
+        match *self {
+            ResolvedPath { ref path, .. } => {
+                path.segments.last().and_then(|seg| {
+                    if let PathParameters::AngleBracketed { ref bindings, .. } = seg.params {
+                        Some(&**bindings)
+                    } else {
+                        None
+                    }
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl GetDefId for Type {
@@ -2605,6 +2638,7 @@ impl Path {
 
 impl Clean<Path> for hir::Path {
     fn clean(&self, cx: &DocContext) -> Path {
+        record_source_link(cx, self);
         Path {
             global: self.is_global(),
             def: self.def,
@@ -2613,6 +2647,41 @@ impl Clean<Path> for hir::Path {
     }
 }
 
+/// Remembers where `path` appears in its source file along with the item it
+/// resolves to, so that the source pages can link it to the documentation of
+/// that item.
+fn record_source_link(cx: &DocContext, path: &hir::Path) {
+    let did = match path.def {
+        Def::Mod(did) | Def::Struct(did) | Def::Union(did) | Def::Enum(did) |
+        Def::Trait(did) | Def::TyAlias(did) | Def::TyForeign(did) | Def::Fn(did) |
+        Def::Const(did) | Def::Static(did, _) | Def::Macro(did, _) => did,
+        _ => return,
+    };
+    let name = match path.segments.last() {
+        Some(segment) => segment.name.as_str(),
+        None => return,
+    };
+    let codemap = cx.sess().codemap();
+    let snippet = match codemap.span_to_snippet(path.span) {
+        Ok(snippet) => snippet,
+        Err(_) => return,
+    };
+    // The span also covers the type parameters of the path, only its last
+    // identifier gets linked.
+    let end = snippet.find('<').unwrap_or(snippet.len());
+    let start = match snippet[..end].rfind(&*name) {
+        Some(start) => start,
+        None => return,
+    };
+    let loc = codemap.lookup_byte_offset(path.span.lo());
+    if let FileName::Real(ref file) = loc.fm.name {
+        cx.renderinfo.borrow_mut().source_links
+            .entry(file.clone())
+            .or_insert_with(FxHashMap)
+            .insert(loc.pos.to_usize() + start, did);
+    }
+}
+
 #[derive(Clone, RustcEncodable, RustcDecodable, PartialEq, Debug)]
 pub enum PathParameters {
     AngleBracketed {
@@ -2841,6 +2910,10 @@ impl Clean<Vec<Item>> for doctree::Impl {
             build_deref_target_impls(cx, &items, &mut ret);
         }
 
+        if let Some(did) = trait_.def_id() {
+            record_marker_trait(cx, did);
+        }
+
         let provided = trait_.def_id().map(|did| {
             cx.tcx.provided_trait_methods(did)
                   .into_iter()
@@ -2870,6 +2943,14 @@ impl Clean<Vec<Item>> for doctree::Impl {
     }
 }
 
+/// Remembers `did` as a marker trait if it is an auto trait, so that impls of
+/// it can be rendered apart from the other trait implementations.
+pub fn record_marker_trait(cx: &DocContext, did: DefId) {
+    if cx.tcx.trait_is_auto(did) {
+        cx.renderinfo.borrow_mut().marker_trait_dids.insert(did);
+    }
+}
+
 fn build_deref_target_impls(cx: &DocContext,
                             items: &[Item],
                             ret: &mut Vec<Item>) {
@@ -3195,12 +3276,15 @@ fn resolve_use_source(cx: &DocContext, path: Path) -> ImportSource {
 #[derive(Clone, RustcEncodable, RustcDecodable, Debug)]
 pub struct Macro {
     pub source: String,
+    /// The matcher of each arm of the macro, in order.
+    pub matchers: Vec<String>,
     pub imported_from: Option<String>,
 }
 
 impl Clean<Item> for doctree::Macro {
     fn clean(&self, cx: &DocContext) -> Item {
         let name = self.name.clean(cx);
+        let matchers = self.matchers.iter().map(|span| span.to_src(cx)).collect::<Vec<_>>();
         Item {
             name: Some(name.clone()),
             attrs: self.attrs.clean(cx),
@@ -3212,9 +3296,10 @@ impl Clean<Item> for doctree::Macro {
             inner: MacroItem(Macro {
                 source: format!("macro_rules! {} {{\n{}}}",
                                 name,
-                                self.matchers.iter().map(|span| {
-                                    format!("    {} => {{ ... }};\n", span.to_src(cx))
+                                matchers.iter().map(|matcher| {
+                                    format!("    {} => {{ ... }};\n", matcher)
                                 }).collect::<String>()),
+                matchers,
                 imported_from: self.imported_from.clean(cx),
             }),
         }
@@ -24,6 +24,7 @@ use rustc::hir;
 
 use clean::{self, PrimitiveType};
 use core::DocAccessLevels;
+use html::escape::Escape;
 use html::item_type::ItemType;
 use html::render;
 use html::render::{cache, CURRENT_LOCATION_KEY};
@@ -70,6 +71,9 @@ pub struct WhereClause<'a>{
     pub indent: usize,
     /// Whether the where clause needs to add a comma and newline after the last bound.
     pub end_newline: bool,
+    /// Whether the bounds of the predicates constraining the same type should be merged into a
+    /// single predicate.
+    pub group_bounds: bool,
 }
 
 pub struct HRef<'a> {
@@ -172,8 +176,15 @@ impl fmt::Display for clean::Generics {
 
 impl<'a> fmt::Display for WhereClause<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let &WhereClause { gens, indent, end_newline } = self;
-        if gens.where_predicates.is_empty() {
+        let &WhereClause { gens, indent, end_newline, group_bounds } = self;
+        let grouped;
+        let predicates = if group_bounds {
+            grouped = group_where_predicates(&gens.where_predicates);
+            &grouped[..]
+        } else {
+            &gens.where_predicates[..]
+        };
+        if predicates.is_empty() {
             return Ok(());
         }
         let mut clause = String::new();
@@ -186,7 +197,7 @@ impl<'a> fmt::Display for WhereClause<'a> {
                 clause.push_str(" <span class=\"where\">where");
             }
         }
-        for (i, pred) in gens.where_predicates.iter().enumerate() {
+        for (i, pred) in predicates.iter().enumerate() {
             if f.alternate() {
                 clause.push(' ');
             } else {
@@ -222,7 +233,7 @@ impl<'a> fmt::Display for WhereClause<'a> {
                 }
             }
 
-            if i < gens.where_predicates.len() - 1 || end_newline {
+            if i < predicates.len() - 1 || end_newline {
                 clause.push(',');
             }
         }
@@ -250,6 +261,29 @@ impl<'a> fmt::Display for WhereClause<'a> {
     }
 }
 
+/// Merges the bounds of the where predicates constraining the same type into the
+/// first of them, so that `T: A, T: B` reads `T: A + B`.
+fn group_where_predicates(predicates: &[clean::WherePredicate]) -> Vec<clean::WherePredicate> {
+    let mut grouped: Vec<clean::WherePredicate> = Vec::with_capacity(predicates.len());
+    for pred in predicates {
+        if let clean::WherePredicate::BoundPredicate { ref ty, ref bounds } = *pred {
+            let pos = grouped.iter().position(|p| match *p {
+                clean::WherePredicate::BoundPredicate { ty: ref other, .. } => other == ty,
+                _ => false,
+            });
+            if let Some(pos) = pos {
+                if let clean::WherePredicate::BoundPredicate { bounds: ref mut existing, .. } =
+                        grouped[pos] {
+                    existing.extend(bounds.iter().cloned());
+                }
+                continue;
+            }
+        }
+        grouped.push(pred.clone());
+    }
+    grouped
+}
+
 impl fmt::Display for clean::Lifetime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(self.get_ref())?;
@@ -707,6 +741,14 @@ fn fmt_type(t: &clean::Type, f: &mut fmt::Formatter, use_absolute: bool) -> fmt:
             }
         }
         clean::ImplTrait(ref bounds) => {
+            if !f.alternate() {
+                // Hovering the type lists what is known of it, one bound per line.
+                let title = bounds.iter().map(|bound| {
+                    format!("&#10;- {}", Escape(&format!("{:#}", bound)))
+                }).collect::<String>();
+                write!(f, "<span class=\"impl-trait\" title=\"An opaque type implementing:{}\">",
+                       title)?;
+            }
             write!(f, "impl ")?;
             for (i, bound) in bounds.iter().enumerate() {
                 if i != 0 {
@@ -718,6 +760,9 @@ fn fmt_type(t: &clean::Type, f: &mut fmt::Formatter, use_absolute: bool) -> fmt:
                     write!(f, "{}", *bound)?;
                 }
             }
+            if !f.alternate() {
+                write!(f, "</span>")?;
+            }
             Ok(())
         }
         clean::QPath { ref name, ref self_type, ref trait_ } => {
@@ -818,7 +863,12 @@ fn fmt_impl(i: &clean::Impl,
 
     fmt_type(&i.for_, f, use_absolute)?;
 
-    fmt::Display::fmt(&WhereClause { gens: &i.generics, indent: 0, end_newline: true }, f)?;
+    fmt::Display::fmt(&WhereClause {
+        gens: &i.generics,
+        indent: 0,
+        end_newline: true,
+        group_bounds: false,
+    }, f)?;
     Ok(())
 }
 
@@ -21,6 +21,7 @@
 //! `Classifier`.
 
 use html::escape::Escape;
+use rustc::util::nodemap::FxHashMap;
 
 use std::fmt::Display;
 use std::io;
@@ -30,7 +31,7 @@ use syntax::codemap::{CodeMap, FilePathMapping};
 use syntax::parse::lexer::{self, TokenAndSpan};
 use syntax::parse::token;
 use syntax::parse;
-use syntax_pos::{Span, FileName};
+use syntax_pos::{Span, FileName, Pos};
 
 /// Highlights `src`, returning the HTML output.
 pub fn render_with_highlighting(src: &str, class: Option<&str>, id: Option<&str>,
@@ -60,18 +61,73 @@ pub fn render_with_highlighting(src: &str, class: Option<&str>, id: Option<&str>
     String::from_utf8_lossy(&out[..]).into_owned()
 }
 
+/// Highlights the concatenation of `chunks` like `render_with_highlighting`,
+/// except that every chunk flagged as hidden is wrapped into a
+/// `<span class="hidden-line">` so that it can be revealed on demand.
+pub fn render_with_hidden_lines(chunks: &[(String, bool)], class: Option<&str>,
+                                id: Option<&str>, extension: Option<&str>,
+                                tooltip: Option<(&str, &str)>) -> String {
+    let mut out = Vec::new();
+    if let Some((tooltip, class)) = tooltip {
+        write!(out, "<div class='information'><div class='tooltip {}'>ⓘ<span \
+                     class='tooltiptext'>{}</span></div></div>",
+               class, tooltip).unwrap();
+    }
+    write_header(class, id, &mut out).unwrap();
+
+    for &(ref chunk, hidden) in chunks {
+        if hidden {
+            write!(out, "<span class=\"hidden-line\">").unwrap();
+        }
+        match render_inner_with_highlighting(chunk) {
+            Ok(html) => write!(out, "{}", html).unwrap(),
+            Err(_) => write!(out, "{}", Escape(chunk)).unwrap(),
+        }
+        if hidden {
+            write!(out, "</span>").unwrap();
+        }
+    }
+
+    if let Some(extension) = extension {
+        write!(out, "{}", extension).unwrap();
+    }
+    write_footer(&mut out).unwrap();
+    String::from_utf8_lossy(&out[..]).into_owned()
+}
+
 /// Highlights `src`, returning the HTML output. Returns only the inner html to
 /// be inserted into an element. C.f., `render_with_highlighting` which includes
 /// an enclosing `<pre>` block.
 pub fn render_inner_with_highlighting(src: &str) -> io::Result<String> {
+    render_inner_with_links(src, &FxHashMap())
+}
+
+/// Highlights `src` like `render_with_highlighting`, additionally turning the
+/// tokens starting at the byte offsets of `links` into links to the associated
+/// urls.
+pub fn render_with_links(src: &str, links: &FxHashMap<usize, String>) -> String {
+    let mut out = Vec::new();
+    write_header(None, None, &mut out).unwrap();
+    match render_inner_with_links(src, links) {
+        Ok(html) => write!(out, "{}", html).unwrap(),
+        Err(_) => return format!("<pre>{}</pre>", src),
+    }
+    write_footer(&mut out).unwrap();
+    String::from_utf8_lossy(&out[..]).into_owned()
+}
+
+/// Like `render_inner_with_highlighting`, turning the tokens starting at the
+/// byte offsets of `links` into links to the associated urls.
+pub fn render_inner_with_links(src: &str, links: &FxHashMap<usize, String>)
+                               -> io::Result<String> {
     let sess = parse::ParseSess::new(FilePathMapping::empty());
     let fm = sess.codemap().new_filemap(FileName::Custom("stdin".to_string()), src.to_string());
 
-    let mut out = Vec::new();
+    let mut out = LinkWriter { out: Vec::new(), links };
     let mut classifier = Classifier::new(lexer::StringReader::new(&sess, fm), sess.codemap());
     classifier.write_source(&mut out)?;
 
-    Ok(String::from_utf8_lossy(&out).into_owned())
+    Ok(String::from_utf8_lossy(&out.out).into_owned())
 }
 
 /// Processes a program (nested in the internal `lexer`), classifying strings of
@@ -167,6 +223,41 @@ impl<U: Write> Writer for U {
     }
 }
 
+/// A `Writer` turning the tokens starting at the byte offsets of `links` into
+/// links to the associated urls, and writing everything else like the default
+/// implementation.
+struct LinkWriter<'a, W: Write> {
+    out: W,
+    links: &'a FxHashMap<usize, String>,
+}
+
+impl<'a, W: Write> Writer for LinkWriter<'a, W> {
+    fn string<T: Display>(&mut self,
+                          text: T,
+                          klass: Class,
+                          tas: Option<&TokenAndSpan>)
+                          -> io::Result<()> {
+        // The highlighted source is the only file of its codemap, so its
+        // positions are offsets in `src`.
+        match tas.and_then(|tas| self.links.get(&tas.sp.lo().to_usize())) {
+            Some(url) => {
+                write!(self.out, "<a href=\"{}\">", url)?;
+                self.out.string(text, klass, tas)?;
+                write!(self.out, "</a>")
+            }
+            None => self.out.string(text, klass, tas),
+        }
+    }
+
+    fn enter_span(&mut self, klass: Class) -> io::Result<()> {
+        self.out.enter_span(klass)
+    }
+
+    fn exit_span(&mut self) -> io::Result<()> {
+        self.out.exit_span()
+    }
+}
+
 impl<'a> Classifier<'a> {
     pub fn new(lexer: lexer::StringReader<'a>, codemap: &'a CodeMap) -> Classifier<'a> {
         Classifier {
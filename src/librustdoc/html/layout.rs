@@ -8,10 +8,12 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::io;
 
 use externalfiles::ExternalHtml;
+use serialize::json::as_json;
 
 #[derive(Clone)]
 pub struct Layout {
@@ -19,6 +21,24 @@ pub struct Layout {
     pub favicon: String,
     pub external_html: ExternalHtml,
     pub krate: String,
+    /// Crate-supplied content of the help overlay, already rendered to HTML.
+    pub help: String,
+    /// The names of the themes selectable in place of the default one.
+    pub themes: Vec<String>,
+    /// Whether the pages have a search box, backed by `search-index.js`.
+    pub search: bool,
+    /// HTML inserted as is in the footer of every page.
+    pub footer: String,
+    /// The names the static files are written under, when they differ from the
+    /// usual ones, such as `main.js`.
+    pub assets: HashMap<String, String>,
+}
+
+impl Layout {
+    /// Returns the name the static file usually called `name` is written under.
+    pub fn asset<'a>(&'a self, name: &'a str) -> &'a str {
+        self.assets.get(name).map_or(name, |s| &**s)
+    }
 }
 
 pub struct Page<'a> {
@@ -27,6 +47,10 @@ pub struct Page<'a> {
     pub root_path: &'a str,
     pub description: &'a str,
     pub keywords: &'a str,
+    /// Whether the page should be laid out right-to-left.
+    pub rtl: bool,
+    /// The url of the page search engines should index in place of this one, if any.
+    pub canonical: Option<&'a str>,
 }
 
 pub fn render<T: fmt::Display, S: fmt::Display>(
@@ -36,7 +60,7 @@ pub fn render<T: fmt::Display, S: fmt::Display>(
 {
     write!(dst,
 r##"<!DOCTYPE html>
-<html lang="en">
+<html lang="en"{dir}>
 <head>
     <meta charset="utf-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
@@ -45,11 +69,13 @@ r##"<!DOCTYPE html>
     <meta name="keywords" content="{keywords}">
 
     <title>{title}</title>
+    {canonical}
 
-    <link rel="stylesheet" type="text/css" href="{root_path}normalize.css">
-    <link rel="stylesheet" type="text/css" href="{root_path}rustdoc.css">
-    <link rel="stylesheet" type="text/css" href="{root_path}main.css">
+    <link rel="stylesheet" type="text/css" href="{root_path}{normalize_css}">
+    <link rel="stylesheet" type="text/css" href="{root_path}{rustdoc_css}">
+    <link rel="stylesheet" type="text/css" href="{root_path}{main_css}">
     {css_extension}
+    {theme_links}
 
     {favicon}
     {in_header}
@@ -62,32 +88,28 @@ r##"<!DOCTYPE html>
     </div>
     <![endif]-->
 
+    <a class="skip-to-content" href="#main">Skip to main content</a>
+
     {before_content}
 
-    <nav class="sidebar">
+    <nav class="sidebar" role="navigation">
         <div class="sidebar-menu">&#9776;</div>
         {logo}
         {sidebar}
     </nav>
 
-    <nav class="sub">
-        <form class="search-form js-only">
-            <div class="search-container">
-                <input class="search-input" name="search"
-                       autocomplete="off"
-                       placeholder="Click or press ‘S’ to search, ‘?’ for more options…"
-                       type="search">
-            </div>
-        </form>
+    <nav class="sub" role="navigation">
+        {theme_picker}
+        {search_form}
     </nav>
 
-    <section id='main' class="content">{content}</section>
-    <section id='search' class="content hidden"></section>
+    <section id='main' class="content" role="main">{content}</section>
+    <section id='search' class="content hidden" role="region" aria-label="Search results"></section>
 
-    <section class="footer"></section>
+    <section class="footer" role="contentinfo">{footer}</section>
 
     <aside id="help" class="hidden">
-        <div>
+        <div{help_class}>
             <h1 class="hidden">Help</h1>
 
             <div class="shortcuts">
@@ -131,6 +153,7 @@ r##"<!DOCTYPE html>
                     <code>vec -> usize</code> or <code>* -> vec</code>)
                 </p>
             </div>
+            {help}
         </div>
     </aside>
 
@@ -139,20 +162,63 @@ r##"<!DOCTYPE html>
     <script>
         window.rootPath = "{root_path}";
         window.currentCrate = "{krate}";
+        window.themes = {themes};
     </script>
-    <script src="{root_path}main.js"></script>
-    <script defer src="{root_path}search-index.js"></script>
+    <script src="{root_path}{main_js}"></script>
+    {search_script}
 </body>
 </html>"##,
     css_extension = if css_file_extension {
-        format!("<link rel=\"stylesheet\" type=\"text/css\" href=\"{root_path}theme.css\">",
-                root_path = page.root_path)
+        format!("<link rel=\"stylesheet\" type=\"text/css\" href=\"{root_path}{theme_css}\">",
+                root_path = page.root_path,
+                theme_css = layout.asset("theme.css"))
     } else {
         "".to_owned()
     },
+    theme_links = layout.themes.iter().map(|theme| {
+        format!("<link rel=\"alternate stylesheet\" type=\"text/css\" \
+                 href=\"{root_path}{file}\" title=\"{name}\" data-theme=\"{name}\">",
+                root_path = page.root_path,
+                file = layout.asset(&format!("theme-{}.css", theme)),
+                name = theme)
+    }).collect::<String>(),
+    theme_picker = if layout.themes.is_empty() {
+        "".to_string()
+    } else {
+        let mut picker = "<select id=\"theme-picker\" class=\"js-only\">\
+                          <option value=\"\">default</option>".to_string();
+        for theme in &layout.themes {
+            picker.push_str(&format!("<option value=\"{0}\">{0}</option>", theme));
+        }
+        picker.push_str("</select>");
+        picker
+    },
+    search_form = if layout.search {
+        "<form class=\"search-form js-only\" role=\"search\">
+            <div class=\"search-container\">
+                <input class=\"search-input\" name=\"search\"
+                       autocomplete=\"off\"
+                       placeholder=\"Click or press ‘S’ to search, ‘?’ for more options…\"
+                       type=\"search\">
+            </div>
+        </form>".to_string()
+    } else {
+        "".to_string()
+    },
+    search_script = if layout.search {
+        format!("<script defer src=\"{}search-index.js\"></script>", page.root_path)
+    } else {
+        "".to_string()
+    },
+    themes    = as_json(&layout.themes),
+    normalize_css = layout.asset("normalize.css"),
+    rustdoc_css = layout.asset("rustdoc.css"),
+    main_css  = layout.asset("main.css"),
+    main_js   = layout.asset("main.js"),
     content   = *t,
     root_path = page.root_path,
     css_class = page.css_class,
+    dir       = if page.rtl { " dir=\"rtl\"" } else { "" },
     logo      = if layout.logo.is_empty() {
         "".to_string()
     } else {
@@ -162,6 +228,10 @@ r##"<!DOCTYPE html>
                 layout.logo)
     },
     title     = page.title,
+    canonical = match page.canonical {
+        Some(url) => format!("<link rel=\"canonical\" href=\"{}\">", url),
+        None => "".to_string(),
+    },
     description = page.description,
     keywords = page.keywords,
     favicon   = if layout.favicon.is_empty() {
@@ -174,6 +244,13 @@ r##"<!DOCTYPE html>
     after_content = layout.external_html.after_content,
     sidebar   = *sidebar,
     krate     = layout.krate,
+    footer    = layout.footer,
+    help_class = if layout.help.is_empty() { "" } else { " class=\"with-crate-help\"" },
+    help      = if layout.help.is_empty() {
+        "".to_string()
+    } else {
+        format!("<div class=\"crate-help docblock\">{}</div>", layout.help)
+    },
     )
 }
 
@@ -184,6 +261,7 @@ r##"<!DOCTYPE html>
 <html lang="en">
 <head>
     <meta http-equiv="refresh" content="0;URL={url}">
+    <link rel="canonical" href="{url}">
 </head>
 <body>
     <p>Redirecting to <a href="{url}">{url}</a>...</p>
@@ -30,7 +30,7 @@
 use libc;
 use std::slice;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::default::Default;
 use std::fmt::{self, Write};
@@ -121,7 +121,7 @@ fn collapse_whitespace(s: &str) -> String {
 /// Convert chars from a title for an id.
 ///
 /// "Hello, world!" -> "hello-world"
-fn slugify(c: char) -> Option<char> {
+pub fn slugify(c: char) -> Option<char> {
     if c.is_alphanumeric() || c == '-' || c == '_' {
         if c.is_ascii() {
             Some(c.to_ascii_lowercase())
@@ -141,6 +141,50 @@ thread_local!(pub static PLAYGROUND: RefCell<Option<(Option<String>, String)>> =
     RefCell::new(None)
 });
 
+// Whether the hidden lines of rust code blocks should be kept in the rendered
+// markup (to be revealed on demand) rather than stripped.
+thread_local!(pub static SHOW_HIDDEN_LINES: Cell<bool> = Cell::new(false));
+
+/// Splits the text of a rust code block into runs of consecutive lines which
+/// are either all hidden or all shown. Every run but the last one keeps its
+/// trailing newline.
+fn hidden_line_chunks(origtext: &str) -> Vec<(String, bool)> {
+    let mut chunks: Vec<(String, bool)> = vec![];
+    for l in origtext.lines() {
+        let line = map_line(l);
+        let hidden = match line {
+            Line::Hidden(_) => true,
+            Line::Shown(_) => false,
+        };
+        let line = line.for_code();
+        match chunks.last_mut() {
+            Some(&mut (ref mut chunk, chunk_hidden)) if chunk_hidden == hidden => {
+                chunk.push('\n');
+                chunk.push_str(line);
+                continue;
+            }
+            Some(&mut (ref mut chunk, _)) => chunk.push('\n'),
+            None => {}
+        }
+        chunks.push((line.to_owned(), hidden));
+    }
+    chunks
+}
+
+/// Highlights the given rust code block, keeping its hidden lines in the
+/// markup if `SHOW_HIDDEN_LINES` is set.
+fn highlight_example(origtext: &str, class: &str, extension: Option<&str>,
+                     tooltip: Option<(&str, &str)>) -> String {
+    if SHOW_HIDDEN_LINES.with(|show| show.get()) {
+        highlight::render_with_hidden_lines(&hidden_line_chunks(origtext), Some(class), None,
+                                            extension, tooltip)
+    } else {
+        let lines = origtext.lines().filter_map(|l| map_line(l).for_html());
+        let text = lines.collect::<Vec<&str>>().join("\n");
+        highlight::render_with_highlighting(&text, Some(class), None, extension, tooltip)
+    }
+}
+
 /// Adds syntax highlighting and playground Run buttons to rust code blocks.
 struct CodeBlocks<'a, I: Iterator<Item = Event<'a>>> {
     inner: I,
@@ -182,8 +226,6 @@ impl<'a, I: Iterator<Item = Event<'a>>> Iterator for CodeBlocks<'a, I> {
                 _ => {}
             }
         }
-        let lines = origtext.lines().filter_map(|l| map_line(l).for_html());
-        let text = lines.collect::<Vec<&str>>().join("\n");
         PLAYGROUND.with(|play| {
             // insert newline to clearly separate it from the
             // previous block so we can shorten the html output
@@ -233,13 +275,12 @@ impl<'a, I: Iterator<Item = Event<'a>>> Iterator for CodeBlocks<'a, I> {
             } else {
                 None
             };
-            s.push_str(&highlight::render_with_highlighting(
-                        &text,
-                        Some(&format!("rust-example-rendered{}",
-                                      if ignore { " ignore" }
-                                      else if compile_fail { " compile_fail" }
-                                      else { "" })),
-                        None,
+            s.push_str(&highlight_example(
+                        &origtext,
+                        &format!("rust-example-rendered{}",
+                                 if ignore { " ignore" }
+                                 else if compile_fail { " compile_fail" }
+                                 else { "" }),
                         playground_button.as_ref().map(String::as_str),
                         tooltip));
             Some(Event::Html(s.into()))
@@ -592,8 +633,6 @@ pub fn render(w: &mut fmt::Formatter,
                 }
             };
 
-            let lines = origtext.lines().filter_map(|l| map_line(l).for_html());
-            let text = lines.collect::<Vec<&str>>().join("\n");
             if rendered { return }
             PLAYGROUND.with(|play| {
                 // insert newline to clearly separate it from the
@@ -644,13 +683,12 @@ pub fn render(w: &mut fmt::Formatter,
                 } else {
                     None
                 };
-                s.push_str(&highlight::render_with_highlighting(
-                               &text,
-                               Some(&format!("rust-example-rendered{}",
-                                             if ignore { " ignore" }
-                                             else if compile_fail { " compile_fail" }
-                                             else { "" })),
-                               None,
+                s.push_str(&highlight_example(
+                               origtext,
+                               &format!("rust-example-rendered{}",
+                                        if ignore { " ignore" }
+                                        else if compile_fail { " compile_fail" }
+                                        else { "" }),
                                playground_button.as_ref().map(String::as_str),
                                tooltip));
                 hoedown_buffer_put(ob, s.as_ptr(), s.len());
@@ -1140,9 +1178,56 @@ pub fn plain_summary_line(md: &str) -> String {
     s
 }
 
+/// Returns the plain text of every heading found in the given markdown, in
+/// order of appearance.
+pub fn markdown_headings(md: &str) -> Vec<String> {
+    let mut headings = vec![];
+    let mut current = None;
+    for event in Parser::new(md) {
+        match event {
+            Event::Start(Tag::Header(_)) => current = Some(String::new()),
+            Event::End(Tag::Header(_)) => {
+                if let Some(heading) = current.take() {
+                    headings.push(heading);
+                }
+            }
+            Event::Text(ref text) => {
+                if let Some(ref mut heading) = current {
+                    heading.push_str(text);
+                }
+            }
+            _ => {}
+        }
+    }
+    headings
+}
+
+/// Escapes the characters of `text` which markdown would otherwise interpret,
+/// so that it can be inserted as-is into markdown, as in the text of a link.
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_{}[]()#+-.!|<>&".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Returns the destination of every link found in the given markdown, in order
+/// of appearance.
+pub fn markdown_links(md: &str) -> Vec<String> {
+    Parser::new(md).filter_map(|event| match event {
+        Event::Start(Tag::Link(dest, _)) => Some(dest.into_owned()),
+        _ => None,
+    }).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::{LangString, Markdown, MarkdownHtml};
+    use super::{escape_markdown, hidden_line_chunks, markdown_headings, markdown_links};
     use super::plain_summary_line;
     use super::RenderType;
     use html::render::reset_ids;
@@ -1258,6 +1343,63 @@ mod tests {
         t("## header", "header");
     }
 
+    #[test]
+    fn test_hidden_line_chunks() {
+        fn t(input: &str, expect: &[(&str, bool)]) {
+            let output = hidden_line_chunks(input);
+            let expect = expect.iter().map(|&(s, h)| (s.to_owned(), h)).collect::<Vec<_>>();
+            assert_eq!(output, expect, "original: {}", input);
+        }
+
+        t("let x = 1;", &[("let x = 1;", false)]);
+        t("# fn main() {\nlet x = 1;\n# }", &[("fn main() {\n", true),
+                                                ("let x = 1;\n", false),
+                                                ("}", true)]);
+        t("# use std::fmt;\n#\nlet x = 1;\nlet y = 2;",
+          &[("use std::fmt;\n\n", true), ("let x = 1;\nlet y = 2;", false)]);
+        t("## not hidden", &[("# not hidden", false)]);
+    }
+
+    #[test]
+    fn test_markdown_headings() {
+        fn t(input: &str, expect: &[&str]) {
+            let output = markdown_headings(input);
+            assert_eq!(output, expect, "original: {}", input);
+        }
+
+        t("no headers here", &[]);
+        t("# Panics\n\nWhen empty.", &["Panics"]);
+        t("# Examples\n\n```\n# foo\n```\n\n## `Safety` notes", &["Examples", "Safety notes"]);
+    }
+
+    #[test]
+    fn test_markdown_links() {
+        fn t(input: &str, expect: &[&str]) {
+            let output = markdown_links(input);
+            assert_eq!(output, expect, "original: {}", input);
+        }
+
+        t("no links here", &[]);
+        t("See [foo](foo/index.html) and <https://example.com>.",
+          &["foo/index.html", "https://example.com"]);
+        t("Use [`Bar`][bar].\n\n[bar]: struct.Bar.html#method.new",
+          &["struct.Bar.html#method.new"]);
+        t("`[not](a link)`", &[]);
+    }
+
+    #[test]
+    fn test_escape_markdown() {
+        assert_eq!(escape_markdown("Read more"), "Read more");
+        assert_eq!(escape_markdown("*more* [1]"), "\\*more\\* \\[1\\]");
+        assert_eq!(escape_markdown("<more> & so on..."), "\\<more\\> \\& so on\\.\\.\\.");
+
+        // The escaped text comes out unchanged from a link.
+        let text = "[more](here) `*`";
+        let output = format!("{}", Markdown(&format!("[{}](x.html)", escape_markdown(text)),
+                                            RenderType::Pulldown));
+        assert_eq!(output, "<p><a href=\"x.html\">[more](here) `*`</a></p>\n");
+    }
+
     #[test]
     fn test_markdown_html_escape() {
         fn t(input: &str, expect: &str) {
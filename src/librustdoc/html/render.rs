@@ -37,16 +37,17 @@ pub use self::ExternalLocation::*;
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::default::Default;
 use std::error;
 use std::fmt::{self, Display, Formatter, Write as FmtWrite};
 use std::fs::{self, File, OpenOptions};
 use std::io::prelude::*;
-use std::io::{self, BufWriter, BufReader};
+use std::io;
 use std::iter::repeat;
 use std::mem;
 use std::path::{PathBuf, Path, Component};
+use std::rc::Rc;
 use std::str;
 use std::sync::Arc;
 
@@ -55,7 +56,7 @@ use externalfiles::ExternalHtml;
 use serialize::json::{ToJson, Json, as_json};
 use syntax::{abi, ast};
 use syntax::codemap::FileName;
-use rustc::hir::def_id::{CrateNum, CRATE_DEF_INDEX, DefId};
+use rustc::hir::def_id::{CrateNum, CRATE_DEF_INDEX, DefId, LOCAL_CRATE};
 use rustc::middle::privacy::AccessLevels;
 use rustc::middle::stability;
 use rustc::hir;
@@ -102,6 +103,169 @@ pub struct Context {
     pub render_type: RenderType,
 }
 
+/// The formats the pages of the items can be rendered in.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum OutputFormat {
+    Html,
+    /// Plain Markdown pages with the signatures in code blocks and the
+    /// documentation verbatim. Only crates, modules, functions, structs and
+    /// traits get a page so far.
+    Markdown,
+}
+
+impl Default for OutputFormat {
+    fn default() -> OutputFormat {
+        OutputFormat::Html
+    }
+}
+
+/// The settings of a documentation run which don't change while rendering,
+/// mostly coming from the command line.
+#[derive(Clone, Default)]
+pub struct RenderOptions {
+    /// Whether listings of modules (in the side bar and documentation itself) are ordered
+    /// alphabetically rather than in order of appearance in the source code.
+    pub sort_modules_alphabetically: bool,
+    /// Renders the source file and line of each local inherent impl block above it.
+    pub show_impl_source_files: bool,
+    /// Gives functions and methods documenting a `# Panics` section a badge linking to it.
+    pub show_panics_badge: bool,
+    /// Keeps the hidden lines (`# `) of code examples in the rendered documentation, to be
+    /// revealed on demand, rather than stripping them.
+    pub show_hidden_lines: bool,
+    /// Also renders the signature of object-safe trait methods as seen through a trait
+    /// object, with `Self` erased.
+    pub show_dyn_signatures: bool,
+    /// Writes an `anchors.json` manifest listing the anchor ids of every page.
+    pub emit_anchors: bool,
+    /// Renders the inherent associated constants of a type in their own section rather than
+    /// within their impl blocks.
+    pub assoc_consts_section: bool,
+    /// Generates a `type-aliases.html` page listing every type alias of the crate along
+    /// with its target.
+    pub type_aliases_page: bool,
+    /// Marks the methods taking `self` and returning the type they are defined on, which
+    /// enable method chaining.
+    pub show_chainable_methods: bool,
+    /// Expands the `{{include-lines path:start:end}}` directives of the documentation into
+    /// the given lines of a file relative to the source root.
+    pub include_lines: bool,
+    /// Notes on implementations on foreign types whether the trait or the implementing type
+    /// is local to the implementing crate.
+    pub show_coherence_notes: bool,
+    /// Writes the normalized signature of every public item to `api-snapshot.txt`, to be
+    /// diffed across versions.
+    pub api_snapshot: bool,
+    /// Generates a `stabilized.html` page listing the stable items of the crate by the
+    /// version they were stabilized in.
+    pub stabilized_page: bool,
+    /// Merges the where clause bounds constraining the same type (`T: A + B` rather than
+    /// `T: A, T: B`).
+    pub group_where_bounds: bool,
+    /// Shows on type pages how many functions of the crate mention the type in their
+    /// signature.
+    pub show_type_usage: bool,
+    /// Lays the pages out right-to-left. Paths, code blocks and signatures are still
+    /// rendered left-to-right.
+    pub rtl: bool,
+    /// Adds a column with the version each item was stabilized in to module listings.
+    pub module_since_column: bool,
+    /// The names of the crates to link to from the crate root, given with `--related-crate`.
+    pub related_crates: Vec<String>,
+    /// Shows on item pages the `use` statement importing the item.
+    pub show_import_path: bool,
+    /// How summaries in module listings and the sidebar get shortened, if they should be.
+    pub summary_truncation: Option<Truncation>,
+    /// Summarizes the key facts about an item as badges below the heading of its page.
+    pub at_a_glance: bool,
+    /// Lists trait implementations alphabetically by trait path rather than in the order
+    /// they were collected in.
+    pub sort_impls: bool,
+    /// Lets each item defined in a module be collapsed down to its first line in the source
+    /// view.
+    pub collapsible_source: bool,
+    /// Base URLs of the documentation of external crates, keyed by crate name, used when the
+    /// crate's docs are neither generated locally nor given by an `html_root_url` attribute.
+    pub extern_html_root_urls: HashMap<String, String>,
+    /// Writes each crate's search index to its own `search-index-<crate>.js`, with
+    /// `search-index.js` only loading those files on demand.
+    pub split_search_index: bool,
+    /// Derives the ids of the items of trait impls from a hash of the impl instead of the
+    /// order they're rendered in.
+    pub stable_impl_item_ids: bool,
+    /// Renders doc blocks with Pulldown only, skipping the Hoedown rendering and the
+    /// comparison between both.
+    pub pulldown_only: bool,
+    /// The markdown to render as `index.html` at the root of the output directory, followed by
+    /// the list of the crates documented there, if any.
+    pub index_page: Option<String>,
+    /// The other crates documented into the output directory, to list on the index page.
+    pub index_page_crates: Vec<String>,
+    /// The CSS themes which can be picked in place of the default one. Each of them is
+    /// written as `theme-<name>.css`, its name being the stem of the file.
+    pub themes: Vec<PathBuf>,
+    /// Links the paths of the source pages to the documentation of the items they resolve to.
+    pub link_to_definition: bool,
+    /// Explains on the standard output why some items have no source link.
+    pub verbose: bool,
+    /// Embeds a JSON-LD description of the item in its page.
+    pub structured_data: bool,
+    /// Fails the documentation of the crate on differences between the Hoedown and Pulldown
+    /// renderings.
+    pub deny_markdown_warnings: bool,
+    /// The length, in bytes, past which the values of associated constants are shortened,
+    /// the full value being shown on demand. Defaults to `ASSOC_CONST_VALUE_LEN`.
+    pub assoc_const_value_len: Option<usize>,
+    /// The url the documentation is hosted at, to write `sitemap.xml` with, if any.
+    pub sitemap_base_url: Option<String>,
+    /// Leaves the documentation of the items out of their pages, keeping only their
+    /// signatures.
+    pub signatures_only: bool,
+    /// Fails the documentation of the crate on pages of items colliding on case-insensitive
+    /// file systems, rather than only warning about them.
+    pub deny_file_collisions: bool,
+    /// The format the pages of the items are rendered in.
+    pub output_format: OutputFormat,
+    /// Makes the pages link to each other and to the sources through absolute urls, based
+    /// on the `html_root_url` of the crate.
+    pub absolute_links: bool,
+    /// Writes a `cache.json` file describing the items, implementors and external crates
+    /// known to the cache, for other tools to consume.
+    pub emit_cache_json: bool,
+    /// Groups the items of each section of module pages by the `cfg` they are available on,
+    /// the items without any coming first.
+    pub group_by_cfg: bool,
+    /// Shows the message given to `#[rustc_on_unimplemented]` on the page of the trait.
+    pub show_on_unimplemented: bool,
+    /// Leaves the version items were stabilized in out of their pages.
+    pub hide_stable_since: bool,
+    /// Leaves the search bar and the search index out of the documentation.
+    pub no_search: bool,
+    /// Applied to every item and source page once it is fully rendered, right before it is
+    /// written.
+    pub html_transform: Option<HtmlTransform>,
+    /// The favicon to use in place of the `html_favicon_url` of the crate.
+    pub favicon_url: Option<String>,
+    /// The logo to use in place of the `html_logo_url` of the crate.
+    pub logo_url: Option<String>,
+    /// Warns about the relative links of the documentation to pages which aren't generated.
+    pub check_links: bool,
+    /// HTML inserted as is in the footer of every page.
+    pub footer: Option<String>,
+    /// Names the shared stylesheets and scripts after a hash of their contents.
+    pub hashed_assets: bool,
+    /// Renders impl blocks collapsed, for the toggles of the page to expand them.
+    pub collapse_impls: bool,
+    /// The text of the link to the full documentation of an item, after its truncated
+    /// summary. Defaults to `Read more`.
+    pub read_more_text: Option<String>,
+    /// Base URLs of the sources of external crates, keyed by crate name, used for the `[src]`
+    /// links of their inlined items when the crate's docs couldn't be found.
+    pub extern_src_roots: HashMap<String, String>,
+    /// Lists the redirections emitted as HTML pages in a `redirects.json` file as well.
+    pub emit_redirect_map: bool,
+}
+
 pub struct SharedContext {
     /// The path to the crate root source minus the file name.
     /// Used for simplifying paths to the highlighted source code files.
@@ -113,37 +277,222 @@ pub struct SharedContext {
     /// the source files are present in the html rendering, then this will be
     /// `true`.
     pub include_sources: bool,
-    /// The local file sources we've emitted and their respective url-paths.
+    /// The local file sources we've emitted and their respective url-paths,
+    /// relative to the `src` directory.
     pub local_sources: FxHashMap<PathBuf, String>,
+    /// The hashes of the contents of the source files emitted by this run or
+    /// previous ones into the same directory, along with the url-path of the
+    /// page showing them and the path of the file itself. Identical files only
+    /// get emitted once.
+    pub source_hashes: FxHashMap<u64, (String, PathBuf)>,
     /// All the passes that were run on this crate.
     pub passes: FxHashSet<String>,
     /// The base-URL of the issue tracker for when an item has been tagged with
     /// an issue number.
     pub issue_tracker_base_url: Option<String>,
+    /// The text of the links to the issue tracker, where `{}` is replaced by the
+    /// issue number. Defaults to `#{}`.
+    pub issue_tracker_link_format: Option<String>,
     /// The given user css file which allow to customize the generated
     /// documentation theme.
     pub css_file_extension: Option<PathBuf>,
-    /// Warnings for the user if rendering would differ using different markdown
-    /// parsers.
-    pub markdown_warnings: RefCell<Vec<(Span, String, Vec<html_diff::Difference>)>>,
+    /// Warnings for the user about the documentation of the items at the given
+    /// spans, reported once the whole crate is rendered.
+    pub markdown_warnings: RefCell<Vec<(Span, MarkdownWarning)>>,
+    /// Where the generated files are written to.
+    pub sink: Box<OutputSink>,
+    /// The settings this documentation is rendered with.
+    pub options: RenderOptions,
+    /// The anchor ids generated for each page, keyed by the path of the page relative to the
+    /// documentation root. Only filled if `emit_anchors` or `check_links` is set.
+    pub anchors: RefCell<BTreeMap<String, Vec<String>>>,
+    /// The summaries rendered for module listings so far, keyed by item, so that the summary of
+    /// an item appearing in many modules is only rendered once.
+    pub summaries: RefCell<FxHashMap<DefId, String>>,
+    /// Like `summaries`, but for the plain text summaries of the sidebar.
+    pub plain_summaries: RefCell<FxHashMap<DefId, String>>,
+    /// The pages of the items written so far, by their lowercased path.
+    pub item_files: RefCell<FxHashMap<String, PathBuf>>,
+    /// The `html_root_url` of the crate, ending with a slash, if the pages
+    /// should link to each other and to the sources through absolute urls.
+    pub absolute_root_url: Option<String>,
+    /// The relative links found in the documentation, along with the span of the documented
+    /// item and the directory of the page they appear on. Only filled if `check_links` is set.
+    pub doc_links: RefCell<Vec<(Span, PathBuf, String)>>,
+    /// The redirections emitted so far, from the path of the redirecting page to the path of its
+    /// target, both relative to the root of the documentation.
+    pub redirects: RefCell<BTreeMap<String, String>>,
+    /// The items and spans already explained to have no source link, so that each of them is
+    /// only reported once when `verbose` is set.
+    pub missing_src_hrefs: RefCell<FxHashSet<(DefId, String)>>,
+}
+
+impl SharedContext {
+    /// Writes the entire contents of a file to the output sink.
+    fn write(&self, dst: &Path, contents: &[u8]) -> Result<(), Error> {
+        Ok(try_err!(self.sink.write(dst, contents), dst))
+    }
+
+    /// Records that the page of an item is written at `dst`, warning if the
+    /// page of another item was already written there, or at a path only
+    /// differing in case, which case-insensitive file systems confuse.
+    fn record_item_file(&self, dst: &Path) -> Result<(), Error> {
+        let key = dst.to_string_lossy().to_lowercase();
+        let previous = self.item_files.borrow_mut().insert(key, dst.to_path_buf());
+        if let Some(previous) = previous {
+            let msg = if previous == dst {
+                format!("the page `{}` is written by several items", dst.display())
+            } else {
+                format!("the pages `{}` and `{}` are the same file on case-insensitive \
+                         file systems", previous.display(), dst.display())
+            };
+            if self.options.deny_file_collisions {
+                return Err(Error::new(io::Error::new(io::ErrorKind::Other, msg), dst));
+            }
+            println!("warning: {}", msg);
+        }
+        Ok(())
+    }
+
+    /// Records that the page at `from` redirects to the page at `to`, if a
+    /// redirect map was requested.
+    fn record_redirect(&self, from: String, to: String) {
+        if self.options.emit_redirect_map {
+            self.redirects.borrow_mut().insert(from, to);
+        }
+    }
+
+    /// Runs the HTML of a rendered page through the `html_transform`, if any.
+    fn transform_html(&self, page: Vec<u8>) -> Vec<u8> {
+        match self.options.html_transform {
+            Some(ref transform) => transform(&String::from_utf8_lossy(&page)).into_bytes(),
+            None => page,
+        }
+    }
+}
+
+/// A problem found in the documentation of an item.
+pub enum MarkdownWarning {
+    /// The Hoedown and Pulldown renderings of the given markdown differ.
+    Differences(String, Vec<html_diff::Difference>),
+    /// The documentation links to a page or a section which wasn't generated.
+    BrokenLink(String),
+}
+
+/// A transformation of the HTML of the generated pages, such as injecting
+/// analytics snippets or rewriting the paths of assets. It runs once per item
+/// and source page, after all the other rendering, and is shared between the
+/// contexts rendering those pages.
+pub type HtmlTransform = Arc<Fn(&str) -> String + Send + Sync>;
+
+/// Returns the summary of `did` stored in `summaries`, storing the output of
+/// `render` there first if there's none yet.
+fn memoized_summary<F>(summaries: &RefCell<FxHashMap<DefId, String>>, did: DefId,
+                       render: F) -> String
+    where F: FnOnce() -> String
+{
+    if let Some(summary) = summaries.borrow().get(&did) {
+        return summary.clone();
+    }
+    let summary = render();
+    summaries.borrow_mut().insert(did, summary.clone());
+    summary
+}
+
+/// A destination for the files generated by rustdoc.
+pub trait OutputSink {
+    /// Writes `contents` to the file at `dst`, replacing it if it already exists. Missing parent
+    /// directories are created.
+    fn write(&self, dst: &Path, contents: &[u8]) -> io::Result<()>;
+
+    /// Like `write`, but leaves the file at `dst` untouched if it already exists.
+    fn write_new(&self, dst: &Path, contents: &[u8]) -> io::Result<()>;
+
+    /// Reads back the file at `dst` previously written to the sink, if any. Used to update the
+    /// files shared by all the crates documented into the same directory.
+    fn read(&self, dst: &Path) -> io::Result<Option<Vec<u8>>>;
+
+    /// Takes the lock guarding the files shared by all the crates documented into `dst`, if the
+    /// sink can be shared with other rustdoc processes.
+    fn lock(&self, _dst: &Path) -> io::Result<Option<flock::Lock>> {
+        Ok(None)
+    }
+}
+
+/// Writes the generated files to disk. This is the default sink.
+#[derive(Default)]
+pub struct FsSink {
     /// The directories that have already been created in this doc run. Used to reduce the number
     /// of spurious `create_dir_all` calls.
-    pub created_dirs: RefCell<FxHashSet<PathBuf>>,
-    /// This flag indicates whether listings of modules (in the side bar and documentation itself)
-    /// should be ordered alphabetically or in order of appearance (in the source code).
-    pub sort_modules_alphabetically: bool,
+    created_dirs: RefCell<FxHashSet<PathBuf>>,
 }
 
-impl SharedContext {
-    fn ensure_dir(&self, dst: &Path) -> io::Result<()> {
-        let mut dirs = self.created_dirs.borrow_mut();
-        if !dirs.contains(dst) {
-            fs::create_dir_all(dst)?;
-            dirs.insert(dst.to_path_buf());
+impl FsSink {
+    fn ensure_parent_dir(&self, dst: &Path) -> io::Result<()> {
+        if let Some(parent) = dst.parent() {
+            let mut dirs = self.created_dirs.borrow_mut();
+            if !dirs.contains(parent) {
+                fs::create_dir_all(parent)?;
+                dirs.insert(parent.to_path_buf());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl OutputSink for FsSink {
+    fn write(&self, dst: &Path, contents: &[u8]) -> io::Result<()> {
+        self.ensure_parent_dir(dst)?;
+        File::create(dst)?.write_all(contents)
+    }
+
+    fn write_new(&self, dst: &Path, contents: &[u8]) -> io::Result<()> {
+        self.ensure_parent_dir(dst)?;
+        match OpenOptions::new().create_new(true).write(true).open(dst) {
+            Ok(mut file) => file.write_all(contents),
+            Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn read(&self, dst: &Path) -> io::Result<Option<Vec<u8>>> {
+        let mut contents = Vec::new();
+        match File::open(dst) {
+            Ok(mut file) => file.read_to_end(&mut contents).map(|_| Some(contents)),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
         }
+    }
+
+    fn lock(&self, dst: &Path) -> io::Result<Option<flock::Lock>> {
+        // The lock file lives in the output directory, which may not exist yet.
+        fs::create_dir_all(dst)?;
+        Ok(Some(flock::Lock::panicking_new(&dst.join(".lock"), true, true, true)))
+    }
+}
+
+/// Keeps the generated files in memory, for tools embedding rustdoc. Clones share the same
+/// files, so a clone can be kept to inspect them once the documentation has been rendered.
+#[derive(Clone, Default)]
+pub struct MemorySink {
+    pub files: Rc<RefCell<FxHashMap<PathBuf, Vec<u8>>>>,
+}
+
+impl OutputSink for MemorySink {
+    fn write(&self, dst: &Path, contents: &[u8]) -> io::Result<()> {
+        self.files.borrow_mut().insert(dst.to_path_buf(), contents.to_vec());
+        Ok(())
+    }
 
+    fn write_new(&self, dst: &Path, contents: &[u8]) -> io::Result<()> {
+        self.files.borrow_mut().entry(dst.to_path_buf()).or_insert_with(|| contents.to_vec());
         Ok(())
     }
+
+    fn read(&self, dst: &Path) -> io::Result<Option<Vec<u8>>> {
+        Ok(self.files.borrow().get(dst).cloned())
+    }
 }
 
 impl SharedContext {
@@ -178,6 +527,8 @@ pub struct Implementor {
     pub def_id: DefId,
     pub stability: Option<clean::Stability>,
     pub impl_: clean::Impl,
+    /// Whether the impl itself is marked `#[doc(hidden)]`.
+    pub hidden: bool,
 }
 
 /// Metadata about implementations for a type.
@@ -226,6 +577,39 @@ impl Error {
     }
 }
 
+/// The reason why an item has no link to its source.
+#[derive(Debug)]
+pub enum SrcHrefError {
+    /// The item doesn't come from a file, e.g. it was expanded from a macro
+    /// of another crate.
+    NotARealFile(FileName),
+    /// The source file of this local item wasn't rendered.
+    SourceNotRendered(PathBuf),
+    /// The crate of this external item isn't known to have been documented.
+    NoExternLocation(CrateNum),
+    /// The documentation of the crate of this external item couldn't be found.
+    UnknownExternLocation(String),
+}
+
+impl Display for SrcHrefError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            SrcHrefError::NotARealFile(ref name) => {
+                write!(f, "`{}` is not a real file", name)
+            }
+            SrcHrefError::SourceNotRendered(ref path) => {
+                write!(f, "the source of `{}` wasn't rendered", path.display())
+            }
+            SrcHrefError::NoExternLocation(cnum) => {
+                write!(f, "the location of crate #{} isn't known", cnum.as_u32())
+            }
+            SrcHrefError::UnknownExternLocation(ref name) => {
+                write!(f, "the documentation of crate `{}` couldn't be found", name)
+            }
+        }
+    }
+}
+
 macro_rules! try_err {
     ($e:expr, $file:expr) => ({
         match $e {
@@ -295,16 +679,38 @@ pub struct Cache {
     /// The version of the crate being documented, if given fron the `--crate-version` flag.
     pub crate_version: Option<String>,
 
+    /// Maps the id of every type mentioned in the signature of a function of
+    /// the crate to the number of such functions. Only filled if
+    /// `--show-type-usage` is passed.
+    pub type_usage: FxHashMap<DefId, usize>,
+
+    /// Maps the id of every local type alias to its target type and
+    /// documentation. Used to generate the type aliases page.
+    pub typedefs: FxHashMap<DefId, (clean::Type, Option<String>)>,
+
+    /// Maps every version found in a `since` stability attribute to the local
+    /// items stabilized in it. Associated items, which are documented on the
+    /// page of their parent, are recorded along with the parent, their type and
+    /// their name. Used to generate the stabilized items page.
+    pub stabilized: FxHashMap<String, Vec<(DefId, Option<(DefId, ItemType, String)>)>>,
+
     // Private fields only used when initially crawling a crate to build a cache
 
     stack: Vec<String>,
     parent_stack: Vec<DefId>,
     parent_is_trait_impl: bool,
     search_index: Vec<IndexItem>,
+    /// Whether items are collected into `search_index` at all, which is only
+    /// needed for the search index and for the type usage counts.
+    index_items: bool,
+    /// Whether the functions collected into `search_index` are counted into
+    /// `type_usage`.
+    count_type_usage: bool,
     stripped_mod: bool,
     deref_trait_did: Option<DefId>,
     deref_mut_trait_did: Option<DefId>,
     owned_box_did: Option<DefId>,
+    marker_trait_dids: FxHashSet<DefId>,
     masked_crates: FxHashSet<CrateNum>,
 
     // In rare case where a structure is defined in one module but implemented
@@ -325,6 +731,10 @@ pub struct RenderInfo {
     pub deref_trait_did: Option<DefId>,
     pub deref_mut_trait_did: Option<DefId>,
     pub owned_box_did: Option<DefId>,
+    pub marker_trait_dids: FxHashSet<DefId>,
+    /// The items the paths of each source file resolve to, keyed by the byte
+    /// offset of their last identifier.
+    pub source_links: FxHashMap<PathBuf, FxHashMap<usize, DefId>>,
 }
 
 /// Helper struct to render all source code to HTML pages
@@ -333,11 +743,47 @@ struct SourceCollector<'a> {
 
     /// Root destination to place all HTML output into
     dst: PathBuf,
+
+    /// Source files to render, in the order they were first encountered, along
+    /// with the line spans (`loline`, `hiline`) of the module-level items they
+    /// define. The spans are only collected for `--collapsible-source`.
+    files: Vec<(PathBuf, Vec<(usize, usize)>)>,
+
+    /// Whether the item currently being folded is a direct child of a module.
+    parent_is_mod: bool,
+
+    /// The items the paths of each source file resolve to, used with
+    /// `--generate-link-to-definition`.
+    source_links: &'a FxHashMap<PathBuf, FxHashMap<usize, DefId>>,
 }
 
 /// Wrapper struct to render the source code of a file. This will do things like
-/// adding line numbers to the left-hand side.
-struct Source<'a>(&'a str);
+/// adding line numbers to the left-hand side. The second field lists the line
+/// spans which can be collapsed, if any, and the third one the urls the tokens
+/// starting at the given byte offsets link to.
+struct Source<'a>(&'a str, &'a [(usize, usize)], &'a FxHashMap<usize, String>);
+
+/// Wrapper struct to render the list of all the type aliases of the crate
+/// along with their targets.
+struct TypeAliases<'a>(&'a Cache);
+
+/// Wrapper struct to render the stable items of the crate grouped by the
+/// version they were stabilized in.
+struct Stabilized<'a>(&'a Cache);
+
+/// Wrapper struct to render the list of all the public items of the crate,
+/// grouped by their type.
+struct AllItems<'a>(&'a Cache);
+
+/// Wrapper struct to render the page of an item in Markdown.
+struct MarkdownItem<'a> {
+    cx: &'a Context,
+    item: &'a clean::Item,
+}
+
+/// Wrapper struct to render the help overlay content supplied by the crate
+/// through `#![doc(html_help = "...")]`.
+struct CrateHelp<'a>(&'a str, Span, RenderType, &'a SharedContext);
 
 // Helper structs for rendering items/sidebars and carrying along contextual
 // information
@@ -346,6 +792,8 @@ struct Source<'a>(&'a str);
 struct Item<'a> {
     cx: &'a Context,
     item: &'a clean::Item,
+    /// The `application/ld+json` description of the item to embed in its page, if any.
+    structured_data: Option<&'a str>,
 }
 
 struct Sidebar<'a> { cx: &'a Context, item: &'a clean::Item, }
@@ -360,19 +808,32 @@ struct IndexItem {
     parent: Option<DefId>,
     parent_idx: Option<usize>,
     search_type: Option<IndexItemFunctionType>,
+    /// The `#[doc(alias = "...")]` of the item, only collected for associated items.
+    aliases: Vec<String>,
+    /// The version of Rust the item was stabilized in, if any.
+    since: Option<String>,
 }
 
 impl ToJson for IndexItem {
     fn to_json(&self) -> Json {
         assert_eq!(self.parent.is_some(), self.parent_idx.is_some());
 
-        let mut data = Vec::with_capacity(6);
+        let mut data = Vec::with_capacity(7);
         data.push((self.ty as usize).to_json());
         data.push(self.name.to_json());
         data.push(self.path.to_json());
         data.push(self.desc.to_json());
         data.push(self.parent_idx.to_json());
         data.push(self.search_type.to_json());
+        // Appended last, and only when known, so that the index keeps its
+        // layout and size for everything else.
+        let words = index_words(&self.name);
+        if self.since.is_some() || words.is_some() {
+            data.push(self.since.to_json());
+        }
+        if let Some(words) = words {
+            data.push(words.to_json());
+        }
 
         Json::Array(data)
     }
@@ -382,6 +843,8 @@ impl ToJson for IndexItem {
 struct Type {
     name: Option<String>,
     generics: Option<Vec<String>>,
+    /// The name of the argument of this type, for function inputs.
+    arg_name: Option<String>,
 }
 
 impl ToJson for Type {
@@ -393,6 +856,9 @@ impl ToJson for Type {
                 if let Some(ref generics) = self.generics {
                     data.insert("generics".to_owned(), generics.to_json());
                 }
+                if let Some(ref arg_name) = self.arg_name {
+                    data.insert("arg".to_owned(), arg_name.to_json());
+                }
                 Json::Object(data)
             },
             None => Json::Null
@@ -447,6 +913,7 @@ fn init_ids() -> FxHashMap<String, usize> {
      "render-detail",
      "associated-types",
      "associated-const",
+     "associated-consts",
      "required-methods",
      "provided-methods",
      "implementors",
@@ -454,6 +921,9 @@ fn init_ids() -> FxHashMap<String, usize> {
      "methods",
      "deref-methods",
      "implementations",
+     "synthetic-implementations",
+     "blanket-implementations",
+     "arms",
     ].into_iter().map(|id| (String::from(*id), 1)).collect()
 }
 
@@ -470,20 +940,46 @@ pub fn reset_ids(embedded: bool) {
     });
 }
 
-pub fn derive_id(candidate: String) -> String {
-    USED_ID_MAP.with(|map| {
-        let id = match map.borrow_mut().get_mut(&candidate) {
-            None => candidate,
-            Some(a) => {
-                let id = format!("{}-{}", candidate, *a);
-                *a += 1;
-                id
+/// Returns, sorted, every id handed out by `derive_id` since the last call to
+/// `reset_ids`.
+fn used_ids() -> Vec<String> {
+    let reserved = init_ids();
+    let mut ids = USED_ID_MAP.with(|map| {
+        let mut ids = vec![];
+        for (candidate, &count) in map.borrow().iter() {
+            // Reserved ids are used by the page layout itself, only their
+            // derived forms come from `derive_id`.
+            if !reserved.contains_key(candidate) {
+                ids.push(candidate.clone());
             }
-        };
+            for i in 1..count {
+                ids.push(format!("{}-{}", candidate, i));
+            }
+        }
+        ids
+    });
+    ids.sort();
+    ids
+}
 
-        map.borrow_mut().insert(id.clone(), 1);
-        id
-    })
+pub fn derive_id(candidate: String) -> String {
+    USED_ID_MAP.with(|map| derive_id_in(&mut map.borrow_mut(), candidate))
+}
+
+/// Like `derive_id`, but using the ids of `map` rather than the ones of the
+/// current page.
+fn derive_id_in(map: &mut FxHashMap<String, usize>, candidate: String) -> String {
+    let id = match map.get_mut(&candidate) {
+        None => candidate,
+        Some(a) => {
+            let id = format!("{}-{}", candidate, *a);
+            *a += 1;
+            id
+        }
+    };
+
+    map.insert(id.clone(), 1);
+    id
 }
 
 /// Generates the documentation for `crate` into the directory `dst`
@@ -495,7 +991,8 @@ pub fn run(mut krate: clean::Crate,
            css_file_extension: Option<PathBuf>,
            renderinfo: RenderInfo,
            render_type: RenderType,
-           sort_modules_alphabetically: bool) -> Result<(), Error> {
+           options: RenderOptions,
+           sink: Box<OutputSink>) -> Result<(), Error> {
     let src_root = match krate.src {
         FileName::Real(ref p) => match p.parent() {
             Some(p) => p.to_path_buf(),
@@ -503,24 +1000,57 @@ pub fn run(mut krate: clean::Crate,
         },
         _ => PathBuf::new(),
     };
+    let mut assets = HashMap::new();
+    if options.hashed_assets {
+        for &(name, contents) in STYLE_AND_SCRIPT_FILES {
+            assets.insert(name.to_string(), hashed_name(name, contents));
+        }
+        if let Some(ref css) = css_file_extension {
+            let content = read_css(css)?;
+            assets.insert("theme.css".to_string(), hashed_name("theme.css", content.as_bytes()));
+        }
+        for theme in &options.themes {
+            let name = format!("theme-{}.css", theme_name(theme));
+            let content = read_css(theme)?;
+            let hashed = hashed_name(&name, content.as_bytes());
+            assets.insert(name, hashed);
+        }
+    }
     let mut scx = SharedContext {
         src_root,
         passes,
         include_sources: true,
         local_sources: FxHashMap(),
+        source_hashes: FxHashMap(),
         issue_tracker_base_url: None,
+        issue_tracker_link_format: None,
         layout: layout::Layout {
             logo: "".to_string(),
             favicon: "".to_string(),
             external_html: external_html.clone(),
             krate: krate.name.clone(),
+            help: "".to_string(),
+            themes: options.themes.iter().map(|t| theme_name(t).to_string()).collect(),
+            search: !options.no_search,
+            footer: options.footer.clone().unwrap_or_default(),
+            assets,
         },
         css_file_extension: css_file_extension.clone(),
         markdown_warnings: RefCell::new(vec![]),
-        created_dirs: RefCell::new(FxHashSet()),
-        sort_modules_alphabetically,
+        sink,
+        options,
+        anchors: RefCell::new(BTreeMap::new()),
+        summaries: RefCell::new(FxHashMap()),
+        plain_summaries: RefCell::new(FxHashMap()),
+        item_files: RefCell::new(FxHashMap()),
+        absolute_root_url: None,
+        doc_links: RefCell::new(vec![]),
+        redirects: RefCell::new(BTreeMap::new()),
+        missing_src_hrefs: RefCell::new(FxHashSet()),
     };
 
+    markdown::SHOW_HIDDEN_LINES.with(|slot| slot.set(scx.options.show_hidden_lines));
+
     // If user passed in `--playground-url` arg, we fill in crate name here
     if let Some(url) = playground_url {
         markdown::PLAYGROUND.with(|slot| {
@@ -530,6 +1060,7 @@ pub fn run(mut krate: clean::Crate,
 
     // Crawl the crate attributes looking for attributes which control how we're
     // going to emit HTML
+    let mut crate_help = None;
     if let Some(attrs) = krate.module.as_ref().map(|m| &m.attrs) {
         for attr in attrs.lists("doc") {
             let name = attr.name().map(|s| s.as_str());
@@ -549,22 +1080,52 @@ pub fn run(mut krate: clean::Crate,
                 (Some("issue_tracker_base_url"), Some(s)) => {
                     scx.issue_tracker_base_url = Some(s.to_string());
                 }
+                (Some("issue_tracker_link_format"), Some(s)) => {
+                    scx.issue_tracker_link_format = Some(s.to_string());
+                }
+                (Some("html_help"), Some(s)) => {
+                    crate_help = Some(s.to_string());
+                }
                 (Some("html_no_source"), None) if attr.is_word() => {
                     scx.include_sources = false;
                 }
+                (Some("html_root_url"), Some(s)) if scx.options.absolute_links => {
+                    let mut url = s.to_string();
+                    if !url.ends_with('/') {
+                        url.push('/');
+                    }
+                    scx.absolute_root_url = Some(url);
+                }
                 _ => {}
             }
         }
     }
-    try_err!(fs::create_dir_all(&dst), &dst);
-    krate = render_sources(&dst, &mut scx, krate)?;
-    let cx = Context {
-        current: Vec::new(),
-        dst,
-        render_redirect_pages: false,
-        shared: Arc::new(scx),
-        render_type,
-    };
+    // The urls given on the command line take precedence over the attributes.
+    if let Some(ref url) = scx.options.favicon_url {
+        scx.layout.favicon = url.clone();
+    }
+    if let Some(ref url) = scx.options.logo_url {
+        scx.layout.logo = url.clone();
+    }
+    if let Some(help) = crate_help {
+        let span = krate.module.as_ref().map_or_else(Span::empty, |m| m.source.clone());
+        scx.layout.help = CrateHelp(&help, span, render_type, &scx).to_string();
+    }
+
+    let output_format = scx.options.output_format;
+    // The cache drops the impl items, so the source files are collected first. Linking them to
+    // the documentation of the items they use needs the cache, so they're then only rendered
+    // once it's built.
+    let mut pending_sources = Vec::new();
+    if output_format == OutputFormat::Html {
+        let (folded, files) = collect_sources(&mut scx, krate);
+        krate = folded;
+        if scx.options.link_to_definition {
+            pending_sources = files;
+        } else {
+            render_sources(&dst, &mut scx, &krate.name, files, &FxHashMap())?;
+        }
+    }
 
     // Crawl the crate to build various caches used for the output
     let RenderInfo {
@@ -574,6 +1135,8 @@ pub fn run(mut krate: clean::Crate,
         deref_trait_did,
         deref_mut_trait_did,
         owned_box_did,
+        marker_trait_dids,
+        source_links,
     } = renderinfo;
 
     let external_paths = external_paths.into_iter()
@@ -588,6 +1151,8 @@ pub fn run(mut krate: clean::Crate,
         stack: Vec::new(),
         parent_stack: Vec::new(),
         search_index: Vec::new(),
+        index_items: !scx.options.no_search || scx.options.show_type_usage,
+        count_type_usage: scx.options.show_type_usage,
         parent_is_trait_impl: false,
         extern_locations: FxHashMap(),
         primitive_locations: FxHashMap(),
@@ -599,8 +1164,12 @@ pub fn run(mut krate: clean::Crate,
         deref_trait_did,
         deref_mut_trait_did,
         owned_box_did,
+        marker_trait_dids,
         masked_crates: mem::replace(&mut krate.masked_crates, FxHashSet()),
         typarams: external_typarams,
+        typedefs: FxHashMap(),
+        stabilized: FxHashMap(),
+        type_usage: FxHashMap(),
     };
 
     // Cache where all our extern crates are located
@@ -612,8 +1181,8 @@ pub fn run(mut krate: clean::Crate,
             },
             _ => PathBuf::new(),
         };
-        cache.extern_locations.insert(n, (e.name.clone(), src_root,
-                                          extern_location(e, &cx.dst)));
+        let location = extern_location(e, &dst, &scx.options.extern_html_root_urls);
+        cache.extern_locations.insert(n, (e.name.clone(), src_root, location));
 
         let did = DefId { krate: n, index: CRATE_DEF_INDEX };
         cache.external_paths.insert(did, (vec![e.name.to_string()], ItemType::Module));
@@ -636,27 +1205,162 @@ pub fn run(mut krate: clean::Crate,
     krate = cache.fold_crate(krate);
 
     // Build our search index
-    let index = build_index(&krate, &mut cache);
+    let index = if cache.index_items {
+        build_index(&krate, &mut cache)
+    } else {
+        Json::Null
+    };
 
     // Freeze the cache now that the index has been built. Put an Arc into TLS
     // for future parallelization opportunities
     let cache = Arc::new(cache);
     CACHE_KEY.with(|v| *v.borrow_mut() = cache.clone());
+
+    if scx.options.emit_cache_json {
+        let json = cache_json(&krate.name, &cache).to_string();
+        scx.write(&dst.join("cache.json"), json.as_bytes())?;
+    }
+
+    if !pending_sources.is_empty() {
+        render_sources(&dst, &mut scx, &krate.name, pending_sources, &source_links)?;
+    }
     CURRENT_LOCATION_KEY.with(|s| s.borrow_mut().clear());
+    let cx = Context {
+        current: Vec::new(),
+        dst,
+        render_redirect_pages: false,
+        shared: Arc::new(scx),
+        render_type,
+    };
+
+    // The static files, search index and implementors only serve the HTML pages.
+    if output_format == OutputFormat::Html {
+        write_shared(&cx, &krate, &*cache, index)?;
+    }
 
-    write_shared(&cx, &krate, &*cache, index)?;
+    if cx.shared.options.api_snapshot {
+        let mut lines = vec![];
+        if let Some(ref module) = krate.module {
+            collect_api_snapshot(module, &mut vec![], &*cache, &mut lines);
+        }
+        lines.sort();
+        lines.dedup();
+        let mut snapshot = String::new();
+        for line in &lines {
+            snapshot.push_str(line);
+            snapshot.push('\n');
+        }
+        cx.shared.write(&cx.dst.join("api-snapshot.txt"), snapshot.as_bytes())?;
+    }
 
     let scx = cx.shared.clone();
+    let anchors_dst = cx.dst.join("anchors.json");
+    let aliases_dst = cx.dst.join("type-aliases.html");
+    let stabilized_dst = cx.dst.join("stabilized.html");
+    let index_dst = cx.dst.join("index.html");
+    let all_dst = cx.dst.join(&krate.name).join("all.html");
+    let doc_dst = cx.dst.clone();
+    let sitemap_dst = cx.dst.join("sitemap.xml");
+    let redirects_dst = cx.dst.join("redirects.json");
 
     // And finally render the whole crate's documentation
     let result = cx.krate(krate);
 
+    // The pages and files below only describe or link to the HTML pages.
+    let html = output_format == OutputFormat::Html;
+
+    if result.is_ok() && html && scx.options.emit_anchors {
+        let anchors = as_json(&*scx.anchors.borrow()).to_string();
+        scx.write(&anchors_dst, anchors.as_bytes())?;
+    }
+
+    if result.is_ok() && html && scx.options.type_aliases_page {
+        write_root_page(&scx, &aliases_dst, "Type aliases", &TypeAliases(&cache))?;
+    }
+
+    if result.is_ok() && html && scx.options.stabilized_page {
+        write_root_page(&scx, &stabilized_dst, "Stabilized items", &Stabilized(&cache))?;
+    }
+
+    if result.is_ok() && html {
+        render_all_page(&scx, &all_dst, &cache)?;
+    }
+
+    if let Some(ref base_url) = scx.options.sitemap_base_url {
+        if result.is_ok() && html {
+            write_sitemap(&scx, &sitemap_dst, base_url, &cache)?;
+        }
+    }
+
+    if let Some(ref markdown) = scx.options.index_page {
+        if result.is_ok() && html {
+            render_index_page(&scx, &index_dst, markdown, render_type)?;
+        }
+    }
+
+    if scx.options.emit_redirect_map && result.is_ok() {
+        // The map is shared by all the crates documented in `doc_dst`.
+        let _lock = try_err!(scx.sink.lock(&doc_dst), &doc_dst);
+        let mut redirects = try_err!(collect_redirects(&*scx.sink, &redirects_dst,
+                                                       &scx.layout.krate),
+                                     &redirects_dst);
+        redirects.extend(scx.redirects.borrow().iter().map(|(k, v)| (k.clone(), v.clone())));
+        scx.write(&redirects_dst, redirects.to_json().to_string().as_bytes())?;
+    }
+
+    if scx.options.check_links && result.is_ok() {
+        report_broken_links(&scx, &doc_dst);
+    }
+
     let markdown_warnings = scx.markdown_warnings.borrow();
     if !markdown_warnings.is_empty() {
         let mut intro_msg = false;
-        for &(ref span, ref text, ref diffs) in &*markdown_warnings {
-            for d in diffs {
-                render_difference(d, &mut intro_msg, span, text);
+        let (mut differences, mut blocks, mut broken) = (0, 0, 0);
+        for &(ref span, ref warning) in &*markdown_warnings {
+            match *warning {
+                MarkdownWarning::Differences(ref text, ref diffs) => {
+                    let mut reported = false;
+                    for d in diffs {
+                        if render_difference(d, &mut intro_msg, span, text) {
+                            differences += 1;
+                            reported = true;
+                        }
+                    }
+                    if reported {
+                        blocks += 1;
+                    }
+                }
+                MarkdownWarning::BrokenLink(ref link) => {
+                    println!("WARNING: broken link `{}`", link);
+                    println!("   --> {}:{}:{}", span.filename, span.loline, span.locol);
+                    broken += 1;
+                }
+            }
+        }
+        if differences > 0 {
+            println!("WARNING: {} markdown rendering difference{} across {} doc block{}",
+                     differences, if differences == 1 { "" } else { "s" },
+                     blocks, if blocks == 1 { "" } else { "s" });
+        }
+        if broken > 0 {
+            println!("WARNING: {} broken link{} in the documentation",
+                     broken, if broken == 1 { "" } else { "s" });
+        }
+        if scx.options.deny_markdown_warnings && result.is_ok() {
+            let mut denied = vec![];
+            if differences > 0 {
+                denied.push(format!("{} markdown rendering difference{}",
+                                    differences, if differences == 1 { "" } else { "s" }));
+            }
+            if broken > 0 {
+                denied.push(format!("{} broken link{}",
+                                    broken, if broken == 1 { "" } else { "s" }));
+            }
+            if !denied.is_empty() {
+                let msg = format!("{} {} denied by `--deny-markdown-warnings`",
+                                  denied.join(" and "),
+                                  if differences + broken == 1 { "was" } else { "were" });
+                return Err(Error::new(io::Error::new(io::ErrorKind::Other, msg), &doc_dst));
             }
         }
     }
@@ -664,77 +1368,442 @@ pub fn run(mut krate: clean::Crate,
     result
 }
 
-// A short, single-line view of `s`.
-fn concise_str(mut s: &str) -> String {
-    if s.contains('\n') {
-        s = s.lines().next().expect("Impossible! We just found a newline");
+/// Records as warnings the relative links of the documentation pointing to pages of the crate,
+/// written under `root`, which weren't generated, or to sections those pages don't have. Links
+/// to a section of the same page and to the pages of other crates aren't checked.
+fn report_broken_links(scx: &SharedContext, root: &Path) {
+    fn key(link: &(Span, PathBuf, String)) -> (&FileName, usize, usize, &Path, &str) {
+        let (ref span, ref dir, ref link) = *link;
+        (&span.filename, span.loline, span.locol, dir, link)
     }
-    if s.len() > 70 {
-        let mut lo = 50;
-        let mut hi = s.len() - 20;
-        while !s.is_char_boundary(lo) {
-            lo -= 1;
+
+    let crate_dir = normalize_path(&root.join(&scx.layout.krate));
+    let mut pages = scx.item_files.borrow().values().map(|p| normalize_path(p))
+                                                    .collect::<FxHashSet<_>>();
+    pages.insert(crate_dir.join("all.html"));
+    let anchors = scx.anchors.borrow();
+
+    let mut doc_links = scx.doc_links.borrow_mut();
+    // Items documented on several pages, such as trait methods, are only
+    // reported once per page.
+    doc_links.sort_by(|a, b| key(a).cmp(&key(b)));
+    doc_links.dedup_by(|a, b| key(a) == key(b));
+    let mut warnings = scx.markdown_warnings.borrow_mut();
+    for &(ref span, ref dir, ref link) in doc_links.iter() {
+        let mut parts = link.splitn(2, '#');
+        let path = parts.next().unwrap().split('?').next().unwrap();
+        let fragment = parts.next().unwrap_or("");
+        // Skip links to the same page, absolute paths, and urls with a scheme.
+        if path.is_empty() || path.starts_with('/') ||
+           path.split('/').next().unwrap().contains(':') {
+            continue;
         }
-        while !s.is_char_boundary(hi) {
-            hi += 1;
+        let target = normalize_path(&dir.join(path));
+        if !target.starts_with(&crate_dir) {
+            continue;
+        }
+        let broken = if !pages.contains(&target) {
+            true
+        } else if fragment.is_empty() {
+            false
+        } else {
+            // The anchors are keyed by the path of their page relative to `root`.
+            let page = target.strip_prefix(&normalize_path(root)).unwrap()
+                             .components()
+                             .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                             .collect::<Vec<_>>()
+                             .join("/");
+            anchors.get(&page).map_or(false, |ids| !ids.iter().any(|id| id == fragment))
+        };
+        if broken {
+            warnings.push((span.clone(), MarkdownWarning::BrokenLink(link.clone())));
         }
-        return format!("{} ... {}", &s[..lo], &s[hi..]);
     }
-    s.to_owned()
 }
 
-// Returns short versions of s1 and s2, starting from where the strings differ.
-fn concise_compared_strs(s1: &str, s2: &str) -> (String, String) {
-    let s1 = s1.trim();
-    let s2 = s2.trim();
-    if !s1.contains('\n') && !s2.contains('\n') && s1.len() <= 70 && s2.len() <= 70 {
-        return (s1.to_owned(), s2.to_owned());
+/// Resolves the `.` and `..` components of `path` without touching the file system, which may
+/// not hold the generated pages.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            c => normalized.push(c.as_os_str()),
+        }
     }
+    normalized
+}
 
-    let mut start_byte = 0;
-    for (c1, c2) in s1.chars().zip(s2.chars()) {
-        if c1 != c2 {
-            break;
-        }
+/// The static files linked from the `<head>` of every page, which are named
+/// after a hash of their contents under `--hashed-assets`. The fonts keep their
+/// names since the stylesheets refer to them.
+const STYLE_AND_SCRIPT_FILES: &'static [(&'static str, &'static [u8])] = &[
+    ("normalize.css", include_bytes!("static/normalize.css")),
+    ("rustdoc.css", include_bytes!("static/rustdoc.css")),
+    ("main.css", include_bytes!("static/styles/main.css")),
+    ("main.js", include_bytes!("static/main.js")),
+];
 
-        start_byte += c1.len_utf8();
-    }
+/// Inserts a hash of `contents` before the extension of `name`, so that
+/// `main.js` becomes e.g. `main.1a2b3c4d5e6f7a8b.js`.
+fn hashed_name(name: &str, contents: &[u8]) -> String {
+    let (stem, ext) = name.split_at(name.rfind('.').unwrap_or(name.len()));
+    format!("{}.{:016x}{}", stem, fnv_hash(contents), ext)
+}
 
-    if start_byte == 0 {
-        return (concise_str(s1), concise_str(s2));
+/// Reads the user-provided stylesheet at `path`.
+fn read_css(path: &Path) -> Result<String, Error> {
+    let mut content = String::new();
+    let mut f = try_err!(File::open(path), path);
+    try_err!(f.read_to_string(&mut content), path);
+    Ok(content)
+}
+
+/// Returns whether `ty` refers to a local type which has no page, usually
+/// because it is `#[doc(hidden)]`.
+fn mentions_undocumented_type(ty: &clean::Type, cache: &Cache) -> bool {
+    match *ty {
+        clean::ResolvedPath { did, .. } if did.is_local() && !cache.paths.contains_key(&did) => {
+            true
+        }
+        clean::BorrowedRef { ref type_, .. } |
+        clean::RawPointer(_, ref type_) |
+        clean::Slice(ref type_) |
+        clean::Array(ref type_, _) => mentions_undocumented_type(type_, cache),
+        clean::Tuple(ref types) => types.iter().any(|t| mentions_undocumented_type(t, cache)),
+        _ => ty.generics().map_or(false, |types| {
+            types.iter().any(|t| mentions_undocumented_type(t, cache))
+        }),
     }
+}
 
-    let s1 = &s1[start_byte..];
-    let s2 = &s2[start_byte..];
-    (format!("...{}", concise_str(s1)), format!("...{}", concise_str(s2)))
+/// Returns the name of the theme stored in the CSS file at `path`, which was
+/// checked when parsing the command line.
+fn theme_name(path: &Path) -> &str {
+    path.file_stem().and_then(|s| s.to_str()).expect("theme has no name")
 }
 
-fn print_message(msg: &str, intro_msg: &mut bool, span: &Span, text: &str) {
-    if !*intro_msg {
-        println!("WARNING: documentation for this crate may be rendered \
-                  differently using the new Pulldown renderer.");
-        println!("    See https://github.com/rust-lang/rust/issues/44229 for details.");
-        *intro_msg = true;
-    }
-    println!("WARNING: rendering difference in `{}`", concise_str(text));
-    println!("   --> {}:{}:{}", span.filename, span.loline, span.locol);
-    println!("{}", msg);
+/// Writes a crate-wide page, such as the type aliases page, at the root of
+/// the documentation.
+fn write_root_page<T: fmt::Display>(scx: &SharedContext, dst: &Path, what: &str,
+                                    content: &T) -> Result<(), Error> {
+    CURRENT_LOCATION_KEY.with(|s| s.borrow_mut().clear());
+    let title = format!("{} of {} - Rust", what, scx.layout.krate);
+    let desc = format!("{} of the Rust `{}` crate.", what, scx.layout.krate);
+    let page = layout::Page {
+        title: &title,
+        css_class: "mod",
+        root_path: "",
+        description: &desc,
+        keywords: BASIC_KEYWORDS,
+        rtl: scx.options.rtl,
+        canonical: None,
+    };
+    let mut buf = Vec::new();
+    try_err!(layout::render(&mut buf, &scx.layout, &page, &(""), content,
+                            scx.css_file_extension.is_some()),
+             dst);
+    scx.write(dst, &buf)
 }
 
-pub fn render_difference(diff: &html_diff::Difference,
-                         intro_msg: &mut bool,
-                         span: &Span,
-                         text: &str) {
-    match *diff {
-        html_diff::Difference::NodeType { ref elem, ref opposite_elem } => {
-            print_message(&format!("    {} Types differ: expected: `{}`, found: `{}`",
-                                   elem.path, elem.element_name, opposite_elem.element_name),
-                          intro_msg, span, text);
+/// Writes the list of all the items of the crate to `dst`, inside the crate's
+/// directory.
+fn render_all_page(scx: &SharedContext, dst: &Path, cache: &Cache) -> Result<(), Error> {
+    CURRENT_LOCATION_KEY.with(|s| *s.borrow_mut() = vec![scx.layout.krate.clone()]);
+    let title = format!("List of all items in {} - Rust", scx.layout.krate);
+    let desc = format!("List of all the items of the Rust `{}` crate.", scx.layout.krate);
+    let page = layout::Page {
+        title: &title,
+        css_class: "mod",
+        root_path: "../",
+        description: &desc,
+        keywords: BASIC_KEYWORDS,
+        rtl: scx.options.rtl,
+        canonical: None,
+    };
+    let mut buf = Vec::new();
+    try_err!(layout::render(&mut buf, &scx.layout, &page, &(""), &AllItems(cache),
+                            scx.css_file_extension.is_some()),
+             dst);
+    scx.write(dst, &buf)
+}
+
+/// Writes at `dst` the sitemap of the pages of the public items of the crate,
+/// whose documentation is hosted at `base_url`.
+fn write_sitemap(scx: &SharedContext, dst: &Path, base_url: &str,
+                 cache: &Cache) -> Result<(), Error> {
+    let base_url = base_url.trim_right_matches('/');
+    let mut urls = cache.paths.iter().filter_map(|(did, &(ref fqp, ty))| {
+        if !did.is_local() || !cache.access_levels.is_public(*did) {
+            return None;
         }
-        html_diff::Difference::NodeName { ref elem, ref opposite_elem } => {
-            print_message(&format!("    {} Tags differ: expected: `{}`, found: `{}`",
-                                   elem.path, elem.element_name, opposite_elem.element_name),
-                          intro_msg, span, text);
+        // Other items, such as variants, are documented on the page of their parent.
+        match ty {
+            ItemType::Module | ItemType::Primitive | ItemType::Macro | ItemType::Struct |
+            ItemType::Enum | ItemType::Constant | ItemType::Static | ItemType::Trait |
+            ItemType::Function | ItemType::Typedef | ItemType::Union |
+            ItemType::ForeignType => {}
+            _ => return None,
+        }
+        let mut url = format!("{}/", base_url);
+        for name in &fqp[..fqp.len() - 1] {
+            url.push_str(name);
+            url.push('/');
+        }
+        url.push_str(&item_path(ty, fqp.last().unwrap()));
+        Some(url)
+    }).collect::<Vec<_>>();
+    urls.push(format!("{}/{}/index.html", base_url, scx.layout.krate));
+    urls.sort();
+    urls.dedup();
+
+    let mut xml = String::new();
+    writeln!(&mut xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(&mut xml, r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#).unwrap();
+    for url in &urls {
+        writeln!(&mut xml, "<url><loc>{}</loc></url>", Escape(url)).unwrap();
+    }
+    writeln!(&mut xml, "</urlset>").unwrap();
+    scx.write(dst, xml.as_bytes())
+}
+
+/// The version of the schema of `cache.json`, to be bumped whenever a field is
+/// removed or changes meaning.
+const CACHE_JSON_VERSION: u32 = 1;
+
+/// Describes the items, trait implementors and external crates known to the
+/// cache, for `cache.json`. `DefId`s are written as `"krate:index"`, which is
+/// only meaningful within a single dump, and implementors as the types they
+/// implement the trait for.
+fn cache_json(krate: &str, cache: &Cache) -> Json {
+    fn def_id(did: DefId) -> String {
+        format!("{}:{}", did.krate.as_u32(), did.index.as_u32())
+    }
+
+    let mut paths = BTreeMap::new();
+    for (&did, &(ref fqp, ty)) in cache.external_paths.iter().chain(cache.paths.iter()) {
+        let mut path = BTreeMap::new();
+        path.insert("path".to_owned(), fqp.to_json());
+        path.insert("kind".to_owned(), ty.css_class().to_json());
+        path.insert("local".to_owned(), did.is_local().to_json());
+        paths.insert(def_id(did), Json::Object(path));
+    }
+
+    let mut implementors = BTreeMap::new();
+    for (&did, imps) in &cache.implementors {
+        let mut types = imps.iter().map(|imp| format!("{:#}", imp.impl_.for_))
+                                   .collect::<Vec<_>>();
+        types.sort();
+        types.dedup();
+        implementors.insert(def_id(did), types.to_json());
+    }
+
+    let mut extern_locations = BTreeMap::new();
+    for (&cnum, &(ref name, _, ref location)) in &cache.extern_locations {
+        let mut extern_crate = BTreeMap::new();
+        extern_crate.insert("name".to_owned(), name.to_json());
+        let location = match *location {
+            Remote(ref url) => {
+                extern_crate.insert("url".to_owned(), url.to_json());
+                "remote"
+            }
+            Local => "local",
+            Unknown => "unknown",
+        };
+        extern_crate.insert("location".to_owned(), location.to_json());
+        extern_locations.insert(cnum.as_u32().to_string(), Json::Object(extern_crate));
+    }
+
+    let mut data = BTreeMap::new();
+    data.insert("format_version".to_owned(), CACHE_JSON_VERSION.to_json());
+    data.insert("crate".to_owned(), krate.to_json());
+    data.insert("crate_version".to_owned(), cache.crate_version.to_json());
+    data.insert("paths".to_owned(), Json::Object(paths));
+    data.insert("implementors".to_owned(), Json::Object(implementors));
+    data.insert("extern_locations".to_owned(), Json::Object(extern_locations));
+    Json::Object(data)
+}
+
+/// Renders the given markdown as the landing page of the output directory at
+/// `dst`, followed by links to the crates documented there.
+fn render_index_page(scx: &SharedContext, dst: &Path, markdown: &str,
+                     render_type: RenderType) -> Result<(), Error> {
+    let mut crates = scx.options.index_page_crates.clone();
+    crates.push(scx.layout.krate.clone());
+    crates.sort();
+    crates.dedup();
+
+    let mut content = format!("<div class='docblock'>{}</div>", Markdown(markdown, render_type));
+    content.push_str("<h2 id='crates' class='section-header'><a href='#crates'>Crates</a></h2>\
+                      <ul class='crate-list'>");
+    for krate in &crates {
+        content.push_str(&format!("<li><a class='mod' href='{0}/index.html'>{0}</a></li>",
+                                  krate));
+    }
+    content.push_str("</ul>");
+
+    CURRENT_LOCATION_KEY.with(|s| s.borrow_mut().clear());
+    let page = layout::Page {
+        title: "Crates - Rust",
+        css_class: "mod",
+        root_path: "",
+        description: "List of the documented crates.",
+        keywords: BASIC_KEYWORDS,
+        rtl: scx.options.rtl,
+        canonical: None,
+    };
+    let mut buf = Vec::new();
+    try_err!(layout::render(&mut buf, &scx.layout, &page, &(""), &content,
+                            scx.css_file_extension.is_some()),
+             dst);
+    scx.write(dst, &buf)
+}
+
+/// The length, in bytes, over which summaries and warning excerpts get shortened.
+const SUMMARY_LEN: usize = 70;
+
+/// The default length past which the values of associated constants are shortened.
+const ASSOC_CONST_VALUE_LEN: usize = 200;
+
+/// Where over-long strings get cut.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncateAt {
+    /// Keep the beginning and the end of the string, as in `abc ... xyz`.
+    Middle,
+    /// Keep the beginning of the string only, as in `abcdef...`.
+    End,
+}
+
+/// How over-long strings get shortened.
+#[derive(Clone, Debug)]
+pub struct Truncation {
+    pub at: TruncateAt,
+    /// The string replacing the part that was cut.
+    pub ellipsis: String,
+}
+
+impl Truncation {
+    /// Creates a new `Truncation`, defaulting to an ellipsis fitting the truncation position.
+    pub fn new(at: TruncateAt, ellipsis: Option<String>) -> Truncation {
+        let ellipsis = ellipsis.unwrap_or_else(|| match at {
+            TruncateAt::Middle => " ... ".to_string(),
+            TruncateAt::End => "...".to_string(),
+        });
+        Truncation { at, ellipsis }
+    }
+
+    /// Shortens `s` if it is longer than `max` bytes, always cutting on character boundaries.
+    fn truncate(&self, s: &str, max: usize) -> String {
+        if s.len() <= max {
+            return s.to_owned();
+        }
+        match self.at {
+            TruncateAt::Middle => {
+                let mut lo = max - max * 2 / 7;
+                let mut hi = s.len() - max * 2 / 7;
+                while !s.is_char_boundary(lo) {
+                    lo -= 1;
+                }
+                while !s.is_char_boundary(hi) {
+                    hi += 1;
+                }
+                format!("{}{}{}", &s[..lo], self.ellipsis, &s[hi..])
+            }
+            TruncateAt::End => {
+                let mut end = max;
+                while !s.is_char_boundary(end) {
+                    end -= 1;
+                }
+                format!("{}{}", &s[..end], self.ellipsis)
+            }
+        }
+    }
+}
+
+impl Default for Truncation {
+    fn default() -> Truncation {
+        Truncation::new(TruncateAt::Middle, None)
+    }
+}
+
+// A short, single-line view of `s`.
+fn concise_str(mut s: &str) -> String {
+    if s.contains('\n') {
+        s = s.lines().next().expect("Impossible! We just found a newline");
+    }
+    if s.len() > 70 {
+        let mut lo = 50;
+        let mut hi = s.len() - 20;
+        while !s.is_char_boundary(lo) {
+            lo -= 1;
+        }
+        while !s.is_char_boundary(hi) {
+            hi += 1;
+        }
+        return format!("{} ... {}", &s[..lo], &s[hi..]);
+    }
+    s.to_owned()
+}
+
+// Returns short versions of s1 and s2, starting from where the strings differ.
+fn concise_compared_strs(s1: &str, s2: &str) -> (String, String) {
+    let s1 = s1.trim();
+    let s2 = s2.trim();
+    if !s1.contains('\n') && !s2.contains('\n') && s1.len() <= 70 && s2.len() <= 70 {
+        return (s1.to_owned(), s2.to_owned());
+    }
+
+    let mut start_byte = 0;
+    for (c1, c2) in s1.chars().zip(s2.chars()) {
+        if c1 != c2 {
+            break;
+        }
+
+        start_byte += c1.len_utf8();
+    }
+
+    if start_byte == 0 {
+        return (concise_str(s1), concise_str(s2));
+    }
+
+    let s1 = &s1[start_byte..];
+    let s2 = &s2[start_byte..];
+    (format!("...{}", concise_str(s1)), format!("...{}", concise_str(s2)))
+}
+
+fn print_message(msg: &str, intro_msg: &mut bool, span: &Span, text: &str) {
+    if !*intro_msg {
+        println!("WARNING: documentation for this crate may be rendered \
+                  differently using the new Pulldown renderer.");
+        println!("    See https://github.com/rust-lang/rust/issues/44229 for details.");
+        *intro_msg = true;
+    }
+    println!("WARNING: rendering difference in `{}`", concise_str(text));
+    println!("   --> {}:{}:{}", span.filename, span.loline, span.locol);
+    println!("{}", msg);
+}
+
+/// Prints the difference `diff` between the Hoedown and Pulldown renderings of `text`,
+/// returning whether it was worth reporting.
+pub fn render_difference(diff: &html_diff::Difference,
+                         intro_msg: &mut bool,
+                         span: &Span,
+                         text: &str) -> bool {
+    match *diff {
+        html_diff::Difference::NodeType { ref elem, ref opposite_elem } => {
+            print_message(&format!("    {} Types differ: expected: `{}`, found: `{}`",
+                                   elem.path, elem.element_name, opposite_elem.element_name),
+                          intro_msg, span, text);
+            true
+        }
+        html_diff::Difference::NodeName { ref elem, ref opposite_elem } => {
+            print_message(&format!("    {} Tags differ: expected: `{}`, found: `{}`",
+                                   elem.path, elem.element_name, opposite_elem.element_name),
+                          intro_msg, span, text);
+            true
         }
         html_diff::Difference::NodeAttributes { ref elem,
                                                 ref elem_attributes,
@@ -745,6 +1814,7 @@ pub fn render_difference(diff: &html_diff::Difference,
                                    elem.path, elem.element_name, elem_attributes,
                                    opposite_elem_attributes),
                           intro_msg, span, text);
+            true
         }
         html_diff::Difference::NodeText { ref elem, ref elem_text, ref opposite_elem_text, .. } => {
             if elem_text.split("\n")
@@ -755,6 +1825,9 @@ pub fn render_difference(diff: &html_diff::Difference,
                                         found:    `{}`",
                                        elem.path, s1, s2),
                               intro_msg, span, text);
+                true
+            } else {
+                false
             }
         }
         html_diff::Difference::NotPresent { ref elem, ref opposite_elem } => {
@@ -762,10 +1835,12 @@ pub fn render_difference(diff: &html_diff::Difference,
                 print_message(&format!("    {} One element is missing: expected: `{}`",
                                        elem.path, elem.element_name),
                               intro_msg, span, text);
+                true
             } else if let Some(ref elem) = *opposite_elem {
                 if elem.element_name.is_empty() {
                     print_message(&format!("    {} One element is missing: expected: `{}`",
-                                           elem.path, concise_str(&elem.element_content)),
+                                           elem.path,
+                                           concise_str(&elem.element_content)),
                                   intro_msg, span, text);
                 } else {
                     print_message(&format!("    {} Unexpected element `{}`: found: `{}`",
@@ -773,25 +1848,34 @@ pub fn render_difference(diff: &html_diff::Difference,
                                            concise_str(&elem.element_content)),
                                   intro_msg, span, text);
                 }
+                true
+            } else {
+                false
             }
         }
     }
 }
 
 /// Build the search index from the collected metadata
-fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
+fn build_index(krate: &clean::Crate, cache: &mut Cache) -> Json {
     let mut nodeid_to_pathid = FxHashMap();
     let mut crate_items = Vec::with_capacity(cache.search_index.len());
     let mut crate_paths = Vec::<Json>::new();
+    let mut crate_aliases = BTreeMap::<String, Vec<usize>>::new();
 
     let Cache { ref mut search_index,
                 ref orphan_impl_items,
-                ref mut paths, .. } = *cache;
+                ref mut paths,
+                count_type_usage,
+                ref mut type_usage, .. } = *cache;
 
     // Attach all orphan items to the type's definition if the type
     // has since been learned.
     for &(did, ref item) in orphan_impl_items {
         if let Some(&(ref fqp, _)) = paths.get(&did) {
+            if count_type_usage {
+                add_type_usage(type_usage, item);
+            }
             search_index.push(IndexItem {
                 ty: item.type_(),
                 name: item.name.clone().unwrap(),
@@ -800,6 +1884,8 @@ fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
                 parent: Some(did),
                 parent_idx: None,
                 search_type: get_index_search_type(&item),
+                aliases: doc_aliases(&item),
+                since: index_since(&item),
             });
         }
     }
@@ -830,6 +1916,9 @@ fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
         } else {
             lastpath = item.path.clone();
         }
+        for alias in &item.aliases {
+            crate_aliases.entry(alias.to_lowercase()).or_insert(vec![]).push(crate_items.len());
+        }
         crate_items.push(item.to_json());
     }
 
@@ -841,125 +1930,307 @@ fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
     crate_data.insert("doc".to_owned(), Json::String(crate_doc));
     crate_data.insert("items".to_owned(), Json::Array(crate_items));
     crate_data.insert("paths".to_owned(), Json::Array(crate_paths));
+    if !crate_aliases.is_empty() {
+        crate_data.insert("aliases".to_owned(), crate_aliases.to_json());
+    }
 
-    // Collect the index into a string
-    format!("searchIndex[{}] = {};",
-            as_json(&krate.name),
-            Json::Object(crate_data))
+    Json::Object(crate_data)
+}
+
+/// Collects into `lines` a normalized, plain text line for every public item
+/// reachable from `item`, whose parent path is `path`. Each line starts with the
+/// path of the item, so that sorting the lines sorts the items by path.
+fn collect_api_snapshot(item: &clean::Item, path: &mut Vec<String>, cache: &Cache,
+                        lines: &mut Vec<String>) {
+    fn push(lines: &mut Vec<String>, line: String) {
+        lines.push(line.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+
+    if item.is_stripped() {
+        return;
+    }
+    // Impls aren't part of the access levels, their items are public as soon
+    // as the type they are implemented on is.
+    if let clean::ImplItem(ref i) = item.inner {
+        let type_path = match i.for_.def_id().and_then(|did| cache.paths.get(&did)) {
+            Some(&(ref fqp, _)) => fqp.join("::"),
+            None => format!("{:#}", i.for_),
+        };
+        if i.trait_.is_some() {
+            push(lines, format!("{} {:#}", type_path, i));
+        } else {
+            for member in &i.items {
+                if let Some(ref member_name) = member.name {
+                    if member.visibility == Some(clean::Public) {
+                        push(lines, format!("{}::{} {}", type_path, member_name,
+                                            api_snapshot_member(member)));
+                    }
+                }
+            }
+        }
+        return;
+    }
+    if !item.is_mod() && !cache.access_levels.is_public(item.def_id) {
+        return;
+    }
+    let name = item.name.clone().unwrap_or_default();
+    let fqn = path.iter().chain(Some(&name)).cloned().collect::<Vec<_>>().join("::");
+
+    match item.inner {
+        clean::ModuleItem(ref m) => {
+            if !path.is_empty() {
+                push(lines, format!("{} mod", fqn));
+            }
+            path.push(name);
+            for item in &m.items {
+                collect_api_snapshot(item, path, cache, lines);
+            }
+            path.pop();
+        }
+        clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => {
+            push(lines, format!("{} {}{}fn{:#}{:#}{:#}",
+                                fqn, ConstnessSpace(f.constness), UnsafetySpace(f.unsafety),
+                                f.generics, f.decl,
+                                snapshot_where_clause(&f.generics)));
+        }
+        clean::StructItem(clean::Struct { ref generics, ref fields, .. }) |
+        clean::UnionItem(clean::Union { ref generics, ref fields, .. }) => {
+            push(lines, format!("{} {}{:#}{:#}",
+                                fqn, item.type_(), generics,
+                                snapshot_where_clause(generics)));
+            for field in fields {
+                if let (&Some(ref field_name), &clean::StructFieldItem(ref ty)) =
+                        (&field.name, &field.inner) {
+                    if field.visibility == Some(clean::Public) {
+                        push(lines, format!("{}::{} field: {:#}", fqn, field_name, ty));
+                    }
+                }
+            }
+        }
+        clean::EnumItem(ref e) => {
+            push(lines, format!("{} enum{:#}{:#}",
+                                fqn, e.generics,
+                                snapshot_where_clause(&e.generics)));
+            for variant in &e.variants {
+                if let Some(ref variant_name) = variant.name {
+                    push(lines, format!("{}::{} variant", fqn, variant_name));
+                }
+            }
+        }
+        clean::TraitItem(ref t) => {
+            let bounds = if t.bounds.is_empty() {
+                String::new()
+            } else {
+                format!(": {:#}", TyParamBounds(&t.bounds))
+            };
+            push(lines, format!("{} {}trait{:#}{}{:#}",
+                                fqn, UnsafetySpace(t.unsafety), t.generics, bounds,
+                                snapshot_where_clause(&t.generics)));
+            for member in &t.items {
+                if let Some(ref member_name) = member.name {
+                    push(lines, format!("{}::{} {}", fqn, member_name,
+                                        api_snapshot_member(member)));
+                }
+            }
+        }
+        clean::TypedefItem(ref t, _) => {
+            push(lines, format!("{} type{:#} = {:#}", fqn, t.generics, t.type_));
+        }
+        clean::ConstantItem(ref c) => push(lines, format!("{} const: {:#}", fqn, c.type_)),
+        clean::StaticItem(ref s) | clean::ForeignStaticItem(ref s) => {
+            push(lines, format!("{} static {}{:#}", fqn, MutableSpace(s.mutability), s.type_));
+        }
+        clean::ForeignTypeItem => push(lines, format!("{} foreigntype", fqn)),
+        clean::MacroItem(..) => push(lines, format!("{} macro", fqn)),
+        _ => {}
+    }
+}
+
+/// Returns the where clause of `gens`, formatted for the API snapshot.
+fn snapshot_where_clause(gens: &clean::Generics) -> WhereClause {
+    WhereClause { gens, indent: 0, end_newline: false, group_bounds: false }
+}
+
+/// Renders the normalized signature of an associated item for the API
+/// snapshot.
+fn api_snapshot_member(item: &clean::Item) -> String {
+    match item.inner {
+        clean::TyMethodItem(clean::TyMethod { ref decl, ref generics, unsafety, .. }) |
+        clean::MethodItem(clean::Method { ref decl, ref generics, unsafety, .. }) => {
+            format!("{}fn{:#}{:#}{:#}", UnsafetySpace(unsafety), generics, decl,
+                    snapshot_where_clause(generics))
+        }
+        clean::AssociatedConstItem(ref ty, _) => format!("const: {:#}", ty),
+        clean::AssociatedTypeItem(ref bounds, _) => {
+            if bounds.is_empty() {
+                "type".to_string()
+            } else {
+                format!("type: {:#}", TyParamBounds(bounds))
+            }
+        }
+        _ => item.type_().to_string(),
+    }
 }
 
 fn write_shared(cx: &Context,
                 krate: &clean::Crate,
                 cache: &Cache,
-                search_index: String) -> Result<(), Error> {
+                search_index: Json) -> Result<(), Error> {
     // Write out the shared files. Note that these are shared among all rustdoc
     // docs placed in the output directory, so this needs to be a synchronized
     // operation with respect to all other rustdocs running around.
-    let _lock = flock::Lock::panicking_new(&cx.dst.join(".lock"), true, true, true);
+    let _lock = try_err!(cx.shared.sink.lock(&cx.dst), &cx.dst);
 
     // Add all the static files. These may already exist, but we just
     // overwrite them anyway to make sure that they're fresh and up-to-date.
 
-    write(cx.dst.join("main.js"),
-          include_bytes!("static/main.js"))?;
-    write(cx.dst.join("rustdoc.css"),
-          include_bytes!("static/rustdoc.css"))?;
-    write(cx.dst.join("main.css"),
-          include_bytes!("static/styles/main.css"))?;
+    let layout = &cx.shared.layout;
+    for &(name, contents) in STYLE_AND_SCRIPT_FILES {
+        cx.shared.write(&cx.dst.join(layout.asset(name)), contents)?;
+    }
     if let Some(ref css) = cx.shared.css_file_extension {
-        let mut content = String::new();
-        let css = css.as_path();
-        let mut f = try_err!(File::open(css), css);
-
-        try_err!(f.read_to_string(&mut content), css);
-        let css = cx.dst.join("theme.css");
-        let css = css.as_path();
-        let mut f = try_err!(File::create(css), css);
-        try_err!(write!(f, "{}", &content), css);
-    }
-    write(cx.dst.join("normalize.css"),
-          include_bytes!("static/normalize.css"))?;
-    write(cx.dst.join("FiraSans-Regular.woff"),
-          include_bytes!("static/FiraSans-Regular.woff"))?;
-    write(cx.dst.join("FiraSans-Medium.woff"),
-          include_bytes!("static/FiraSans-Medium.woff"))?;
-    write(cx.dst.join("FiraSans-LICENSE.txt"),
-          include_bytes!("static/FiraSans-LICENSE.txt"))?;
-    write(cx.dst.join("Heuristica-Italic.woff"),
-          include_bytes!("static/Heuristica-Italic.woff"))?;
-    write(cx.dst.join("Heuristica-LICENSE.txt"),
-          include_bytes!("static/Heuristica-LICENSE.txt"))?;
-    write(cx.dst.join("SourceSerifPro-Regular.woff"),
-          include_bytes!("static/SourceSerifPro-Regular.woff"))?;
-    write(cx.dst.join("SourceSerifPro-Bold.woff"),
-          include_bytes!("static/SourceSerifPro-Bold.woff"))?;
-    write(cx.dst.join("SourceSerifPro-LICENSE.txt"),
-          include_bytes!("static/SourceSerifPro-LICENSE.txt"))?;
-    write(cx.dst.join("SourceCodePro-Regular.woff"),
-          include_bytes!("static/SourceCodePro-Regular.woff"))?;
-    write(cx.dst.join("SourceCodePro-Semibold.woff"),
-          include_bytes!("static/SourceCodePro-Semibold.woff"))?;
-    write(cx.dst.join("SourceCodePro-LICENSE.txt"),
-          include_bytes!("static/SourceCodePro-LICENSE.txt"))?;
-    write(cx.dst.join("LICENSE-MIT.txt"),
-          include_bytes!("static/LICENSE-MIT.txt"))?;
-    write(cx.dst.join("LICENSE-APACHE.txt"),
-          include_bytes!("static/LICENSE-APACHE.txt"))?;
-    write(cx.dst.join("COPYRIGHT.txt"),
-          include_bytes!("static/COPYRIGHT.txt"))?;
-
-    fn collect(path: &Path, krate: &str,
-               key: &str) -> io::Result<Vec<String>> {
+        let content = read_css(css)?;
+        cx.shared.write(&cx.dst.join(layout.asset("theme.css")), content.as_bytes())?;
+    }
+    for theme in &cx.shared.options.themes {
+        let content = read_css(theme)?;
+        let name = format!("theme-{}.css", theme_name(theme));
+        cx.shared.write(&cx.dst.join(layout.asset(&name)), content.as_bytes())?;
+    }
+    cx.shared.write(&cx.dst.join("FiraSans-Regular.woff"),
+                    include_bytes!("static/FiraSans-Regular.woff"))?;
+    cx.shared.write(&cx.dst.join("FiraSans-Medium.woff"),
+                    include_bytes!("static/FiraSans-Medium.woff"))?;
+    cx.shared.write(&cx.dst.join("FiraSans-LICENSE.txt"),
+                    include_bytes!("static/FiraSans-LICENSE.txt"))?;
+    cx.shared.write(&cx.dst.join("Heuristica-Italic.woff"),
+                    include_bytes!("static/Heuristica-Italic.woff"))?;
+    cx.shared.write(&cx.dst.join("Heuristica-LICENSE.txt"),
+                    include_bytes!("static/Heuristica-LICENSE.txt"))?;
+    cx.shared.write(&cx.dst.join("SourceSerifPro-Regular.woff"),
+                    include_bytes!("static/SourceSerifPro-Regular.woff"))?;
+    cx.shared.write(&cx.dst.join("SourceSerifPro-Bold.woff"),
+                    include_bytes!("static/SourceSerifPro-Bold.woff"))?;
+    cx.shared.write(&cx.dst.join("SourceSerifPro-LICENSE.txt"),
+                    include_bytes!("static/SourceSerifPro-LICENSE.txt"))?;
+    cx.shared.write(&cx.dst.join("SourceCodePro-Regular.woff"),
+                    include_bytes!("static/SourceCodePro-Regular.woff"))?;
+    cx.shared.write(&cx.dst.join("SourceCodePro-Semibold.woff"),
+                    include_bytes!("static/SourceCodePro-Semibold.woff"))?;
+    cx.shared.write(&cx.dst.join("SourceCodePro-LICENSE.txt"),
+                    include_bytes!("static/SourceCodePro-LICENSE.txt"))?;
+    cx.shared.write(&cx.dst.join("LICENSE-MIT.txt"),
+                    include_bytes!("static/LICENSE-MIT.txt"))?;
+    cx.shared.write(&cx.dst.join("LICENSE-APACHE.txt"),
+                    include_bytes!("static/LICENSE-APACHE.txt"))?;
+    cx.shared.write(&cx.dst.join("COPYRIGHT.txt"),
+                    include_bytes!("static/COPYRIGHT.txt"))?;
+
+    fn collect_json(sink: &OutputSink, path: &Path, krate: &str) -> io::Result<Vec<String>> {
+        let own = format!("{}:", Json::String(krate.to_string()));
         let mut ret = Vec::new();
-        if path.exists() {
-            for line in BufReader::new(File::open(path)?).lines() {
-                let line = line?;
-                if !line.starts_with(key) {
-                    continue;
-                }
-                if line.starts_with(&format!(r#"{}["{}"]"#, key, krate)) {
-                    continue;
-                }
-                ret.push(line.to_string());
+        for line in read_shared(sink, path)?.lines() {
+            let line = line.trim_right_matches(',');
+            if !line.starts_with('"') || line.starts_with(&own) {
+                continue;
             }
+            ret.push(line.to_string());
         }
         Ok(ret)
     }
 
-    // Update the search index
-    let dst = cx.dst.join("search-index.js");
-    let mut all_indexes = try_err!(collect(&dst, &krate.name, "searchIndex"), &dst);
-    all_indexes.push(search_index);
-    // Sort the indexes by crate so the file will be generated identically even
-    // with rustdoc running in parallel.
-    all_indexes.sort();
-    let mut w = try_err!(File::create(&dst), &dst);
-    try_err!(writeln!(&mut w, "var searchIndex = {{}};"), &dst);
-    for index in &all_indexes {
-        try_err!(writeln!(&mut w, "{}", *index), &dst);
+    // Update the search index, unless the pages were rendered without a search.
+    if cx.shared.layout.search {
+        let dst = cx.dst.join("search-index.js");
+        if cx.shared.options.split_search_index {
+            // Each crate's index goes in its own file, so that only the small
+            // loader listing those files needs to be read back and updated.
+            let file = format!("search-index-{}.js", krate.name);
+            let index = format!("{} = {};\n", crate_entry("searchIndex", &krate.name),
+                                search_index);
+            cx.shared.write(&cx.dst.join(&file), index.as_bytes())?;
+
+            let mut all_files = try_err!(collect(&*cx.shared.sink, &dst, &krate.name,
+                                                 "searchIndexFiles"), &dst);
+            all_files.push(format!("{} = {};",
+                                   crate_entry("searchIndexFiles", &krate.name), as_json(&file)));
+            all_files.sort();
+            // The indexes written inline by crates documented without a split index are kept
+            // as they are, since they are loaded along with the loader.
+            let inline_indexes = try_err!(collect(&*cx.shared.sink, &dst, &krate.name,
+                                                  "searchIndex"), &dst);
+            let mut w = String::new();
+            writeln!(&mut w, "var searchIndex = {{}};").unwrap();
+            writeln!(&mut w, "var searchIndexFiles = {{}};").unwrap();
+            for index in &inline_indexes {
+                writeln!(&mut w, "{}", *index).unwrap();
+            }
+            for file in &all_files {
+                writeln!(&mut w, "{}", *file).unwrap();
+            }
+            writeln!(&mut w, "loadSearchIndexes(searchIndexFiles);").unwrap();
+            cx.shared.write(&dst, w.as_bytes())?;
+        } else {
+            let mut all_indexes = try_err!(collect(&*cx.shared.sink, &dst, &krate.name,
+                                                   "searchIndex"), &dst);
+            // The indexes of the crates documented with a split index are moved back inline.
+            let files = try_err!(collect(&*cx.shared.sink, &dst, &krate.name,
+                                         "searchIndexFiles"), &dst);
+            for line in &files {
+                let file = line.splitn(2, " = ").nth(1)
+                               .and_then(|f| Json::from_str(f.trim_right_matches(';')).ok())
+                               .and_then(|f| f.as_string().map(|f| f.to_string()));
+                if let Some(file) = file {
+                    let path = cx.dst.join(&file);
+                    all_indexes.extend(try_err!(collect(&*cx.shared.sink, &path, &krate.name,
+                                                        "searchIndex"), &path));
+                }
+            }
+            all_indexes.push(format!("{} = {};", crate_entry("searchIndex", &krate.name),
+                                     search_index));
+            // Sort the indexes by crate so the file will be generated identically even
+            // with rustdoc running in parallel.
+            all_indexes.sort();
+            let mut w = String::new();
+            writeln!(&mut w, "var searchIndex = {{}};").unwrap();
+            for index in &all_indexes {
+                writeln!(&mut w, "{}", *index).unwrap();
+            }
+            writeln!(&mut w, "initSearch(searchIndex);").unwrap();
+            cx.shared.write(&dst, w.as_bytes())?;
+        }
+
+        // Update the JSON search index, which holds the same data without the JS
+        // wrapper and keeps one line per crate, like `search-index.js`.
+        let dst = cx.dst.join("search-index.json");
+        let mut all_indexes = try_err!(collect_json(&*cx.shared.sink, &dst, &krate.name), &dst);
+        all_indexes.push(format!("{}:{}", as_json(&krate.name), search_index));
+        all_indexes.sort();
+        let w = format!("{{\n{}\n}}\n", all_indexes.join(",\n"));
+        cx.shared.write(&dst, w.as_bytes())?;
     }
-    try_err!(writeln!(&mut w, "initSearch(searchIndex);"), &dst);
 
     // Update the list of all implementors for traits
     let dst = cx.dst.join("implementors");
-    for (&did, imps) in &cache.implementors {
-        // Private modules can leak through to this phase of rustdoc, which
-        // could contain implementations for otherwise private types. In some
-        // rare cases we could find an implementation for an item which wasn't
-        // indexed, so we just skip this step in that case.
-        //
-        // FIXME: this is a vague explanation for why this can't be a `get`, in
-        //        theory it should be...
-        let &(ref remote_path, remote_item_type) = match cache.paths.get(&did) {
-            Some(p) => p,
-            None => match cache.external_paths.get(&did) {
-                Some(p) => p,
-                None => continue,
-            }
-        };
-
+    // Private modules can leak through to this phase of rustdoc, which
+    // could contain implementations for otherwise private types. In some
+    // rare cases we could find an implementation for an item which wasn't
+    // indexed, so we just skip this step in that case.
+    //
+    // FIXME: this is a vague explanation for why this can't be a `get`, in
+    //        theory it should be...
+    let mut traits = cache.implementors.iter().filter_map(|(&did, imps)| {
+        cache.paths.get(&did).or_else(|| cache.external_paths.get(&did))
+             .map(|&(ref remote_path, remote_item_type)| {
+                 (remote_path, remote_item_type, did, imps)
+             })
+    }).collect::<Vec<_>>();
+    // Write the files in the order of the paths of their traits rather than in
+    // the order of the hash map, so that runs can be compared with each other.
+    traits.sort_by(|a, b| (a.0, a.1.css_class()).cmp(&(b.0, b.1.css_class())));
+    for (remote_path, remote_item_type, did, imps) in traits {
         let mut have_impls = false;
-        let mut implementors = format!(r#"implementors["{}"] = ["#, krate.name);
+        let mut implementors = format!("{} = [", crate_entry("implementors", &krate.name));
         for imp in imps {
             // If the trait and implementation are in the same crate, then
             // there's no need to emit information about it (there's inlining
@@ -969,6 +2240,9 @@ fn write_shared(cx: &Context,
             // If the implementation is from another crate then that crate
             // should add it.
             if !imp.def_id.is_local() { continue }
+            // Neither should the implementations that aren't documented here,
+            // as those for `Box<T>` where `T` is `#[doc(hidden)]`.
+            if imp.hidden || mentions_undocumented_type(&imp.impl_.for_, cache) { continue }
             have_impls = true;
             write!(implementors, "{},", as_json(&imp.impl_.to_string())).unwrap();
         }
@@ -985,50 +2259,172 @@ fn write_shared(cx: &Context,
         for part in &remote_path[..remote_path.len() - 1] {
             mydst.push(part);
         }
-        try_err!(fs::create_dir_all(&mydst), &mydst);
         mydst.push(&format!("{}.{}.js",
                             remote_item_type.css_class(),
                             remote_path[remote_path.len() - 1]));
 
-        let mut all_implementors = try_err!(collect(&mydst, &krate.name, "implementors"), &mydst);
+        let mut all_implementors = try_err!(collect(&*cx.shared.sink, &mydst, &krate.name,
+                                                 "implementors"), &mydst);
         all_implementors.push(implementors);
         // Sort the implementors by crate so the file will be generated
         // identically even with rustdoc running in parallel.
         all_implementors.sort();
 
-        let mut f = try_err!(File::create(&mydst), &mydst);
-        try_err!(writeln!(&mut f, "(function() {{var implementors = {{}};"), &mydst);
+        let mut f = String::new();
+        writeln!(&mut f, "(function() {{var implementors = {{}};").unwrap();
         for implementor in &all_implementors {
-            try_err!(writeln!(&mut f, "{}", *implementor), &mydst);
+            writeln!(&mut f, "{}", *implementor).unwrap();
         }
-        try_err!(writeln!(&mut f, "{}", r"
+        writeln!(&mut f, "{}", r"
             if (window.register_implementors) {
                 window.register_implementors(implementors);
             } else {
                 window.pending_implementors = implementors;
             }
-        "), &mydst);
-        try_err!(writeln!(&mut f, r"}})()"), &mydst);
+        ").unwrap();
+        writeln!(&mut f, r"}})()").unwrap();
+        cx.shared.write(&mydst, f.as_bytes())?;
     }
     Ok(())
 }
 
-fn render_sources(dst: &Path, scx: &mut SharedContext,
-                  krate: clean::Crate) -> Result<clean::Crate, Error> {
+/// Reads back the file at `path` shared by all crates from `sink`, which is
+/// empty if it hasn't been written yet.
+fn read_shared(sink: &OutputSink, path: &Path) -> io::Result<String> {
+    match sink.read(path)? {
+        Some(contents) => String::from_utf8(contents).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, e)
+        }),
+        None => Ok(String::new()),
+    }
+}
+
+/// Reads back the lines of the file at `path` shared by all crates which
+/// belong to other crates than `krate`, each of them starting with `key`.
+fn collect(sink: &OutputSink, path: &Path, krate: &str,
+           key: &str) -> io::Result<Vec<String>> {
+    let mut ret = Vec::new();
+    let prefix = format!("{}[", key);
+    for line in read_shared(sink, path)?.lines() {
+        if !line.starts_with(&prefix) {
+            continue;
+        }
+        if line.starts_with(&crate_entry(key, krate)) {
+            continue;
+        }
+        ret.push(line.to_string());
+    }
+    Ok(ret)
+}
+
+/// Reads the redirections listed in the `redirects.json` file at `path` by the other crates
+/// documented into the same directory, leaving out the ones of `krate` which is being
+/// documented again.
+fn collect_redirects(sink: &OutputSink, path: &Path,
+                     krate: &str) -> io::Result<BTreeMap<String, String>> {
+    let mut ret = BTreeMap::new();
+    let contents = read_shared(sink, path)?;
+    if contents.is_empty() {
+        return Ok(ret);
+    }
+    let json = Json::from_str(&contents).map_err(|e| {
+        io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+    })?;
+    let (page, source) = (format!("{}/", krate), format!("src/{}/", krate));
+    for (from, to) in json.as_object().into_iter().flat_map(|o| o) {
+        if let Some(to) = to.as_string() {
+            if !from.starts_with(&page) && !from.starts_with(&source) {
+                ret.insert(from.clone(), to.to_string());
+            }
+        }
+    }
+    Ok(ret)
+}
+
+/// Returns the JS expression of the entry of `krate` in the object `key`, which
+/// starts its line in the files shared by all crates.
+fn crate_entry(key: &str, krate: &str) -> String {
+    format!("{}[{}]", key, as_json(&krate))
+}
+
+/// Collects the local source files of `krate` along with the line spans of the module-level
+/// items they define.
+fn collect_sources(scx: &mut SharedContext, krate: clean::Crate)
+                   -> (clean::Crate, Vec<(PathBuf, Vec<(usize, usize)>)>) {
+    let no_links = FxHashMap();
+    let mut folder = SourceCollector {
+        dst: PathBuf::new(),
+        scx,
+        files: Vec::new(),
+        parent_is_mod: false,
+        source_links: &no_links,
+    };
+    let krate = folder.fold_crate(krate);
+    (krate, folder.files)
+}
+
+fn render_sources(dst: &Path, scx: &mut SharedContext, krate: &str,
+                  files: Vec<(PathBuf, Vec<(usize, usize)>)>,
+                  source_links: &FxHashMap<PathBuf, FxHashMap<usize, DefId>>)
+                  -> Result<(), Error> {
     info!("emitting source files");
-    let dst = dst.join("src").join(&krate.name);
-    try_err!(fs::create_dir_all(&dst), &dst);
+    let hashes_dst = dst.join("src").join("source-hashes.txt");
+    scx.source_hashes = try_err!(load_source_hashes(&*scx.sink, &hashes_dst, krate),
+                                 &hashes_dst);
+    let dst = dst.join("src").join(krate);
     let mut folder = SourceCollector {
         dst,
         scx,
+        files: Vec::new(),
+        parent_is_mod: false,
+        source_links,
     };
-    Ok(folder.fold_crate(krate))
+
+    for (path, spans) in files {
+        // If it turns out that we couldn't read this file, then we probably
+        // can't read any of the files (generating html output from json or
+        // something like that), so just don't include sources for the
+        // entire crate. The other option is maintaining this mapping on a
+        // per-file basis, but that's probably not worth it...
+        if let Err(e) = folder.emit_source(&path, &spans) {
+            println!("warning: source code was requested to be rendered, \
+                      but processing `{}` had an error: {}",
+                     path.display(), e);
+            println!("         skipping rendering of source code");
+            folder.scx.include_sources = false;
+            break;
+        }
+    }
+
+    if folder.scx.include_sources {
+        let mut hashes = folder.scx.source_hashes.iter().map(|(hash, &(ref href, ref path))| {
+            format!("{:016x} {}\t{}\n", hash, href, path.display())
+        }).collect::<Vec<_>>();
+        hashes.sort();
+        folder.scx.write(&hashes_dst, hashes.concat().as_bytes())?;
+    }
+    Ok(())
 }
 
-/// Writes the entire contents of a string to a destination, not attempting to
-/// catch any errors.
-fn write(dst: PathBuf, contents: &[u8]) -> Result<(), Error> {
-    Ok(try_err!(try_err!(File::create(&dst), &dst).write_all(contents), &dst))
+/// Reads the hashes of the source files emitted into the same directory by
+/// previous runs, leaving out the ones of `krate` which is being documented
+/// again.
+fn load_source_hashes(sink: &OutputSink, path: &Path, krate: &str)
+                      -> io::Result<FxHashMap<u64, (String, PathBuf)>> {
+    let mut hashes = FxHashMap();
+    let prefix = format!("{}/", krate);
+    for line in read_shared(sink, path)?.lines() {
+        let mut parts = line.splitn(2, ' ');
+        let hash = parts.next().map(|h| u64::from_str_radix(h, 16));
+        let mut parts = parts.next().unwrap_or("").splitn(2, '\t');
+        match (hash, parts.next(), parts.next()) {
+            (Some(Ok(hash)), Some(href), Some(source)) if !href.starts_with(&prefix) => {
+                hashes.insert(hash, (href.to_string(), PathBuf::from(source)));
+            }
+            _ => {}
+        }
+    }
+    Ok(hashes)
 }
 
 /// Takes a path to a source file and cleans the path to it. This canonicalizes
@@ -1053,15 +2449,41 @@ fn clean_srcpath<F>(src_root: &Path, p: &Path, keep_filename: bool, mut f: F) wh
 
         match c {
             Component::ParentDir => f("up"),
-            Component::Normal(c) => f(c.to_str().unwrap()),
+            // Non-UTF-8 components are mapped lossily so that both the source pages and
+            // the links to them agree on the same (valid) names.
+            Component::Normal(c) => f(&c.to_string_lossy()),
             _ => continue,
         }
     }
 }
 
+/// Returns the path of the file defining `item` (relative to the crate source
+/// root) along with the line it starts on, or `None` if the item doesn't come
+/// from a local source file.
+fn impl_source_location(cx: &Context, item: &clean::Item) -> Option<String> {
+    if !item.def_id.is_local() {
+        return None;
+    }
+    let file = match item.source.filename {
+        FileName::Real(ref path) => path,
+        _ => return None,
+    };
+
+    let mut path = String::new();
+    clean_srcpath(&cx.shared.src_root, file, true, |component| {
+        if !path.is_empty() {
+            path.push('/');
+        }
+        path.push_str(component);
+    });
+    Some(format!("{}:{}", path, item.source.loline))
+}
+
 /// Attempts to find where an external crate is located, given that we're
-/// rendering in to the specified source destination.
-fn extern_location(e: &clean::ExternalCrate, dst: &Path) -> ExternalLocation {
+/// rendering in to the specified source destination and the base URLs passed
+/// through `--extern-html-root-url`.
+fn extern_location(e: &clean::ExternalCrate, dst: &Path,
+                   root_urls: &HashMap<String, String>) -> ExternalLocation {
     // See if there's documentation generated into the local directory
     let local_location = dst.join(&e.name);
     if local_location.is_dir() {
@@ -1073,8 +2495,10 @@ fn extern_location(e: &clean::ExternalCrate, dst: &Path) -> ExternalLocation {
     e.attrs.lists("doc")
      .filter(|a| a.check_name("html_root_url"))
      .filter_map(|a| a.value_str())
-     .map(|url| {
-        let mut url = url.to_string();
+     .map(|url| url.to_string())
+     // Then see if we were told where its documentation lives
+     .chain(root_urls.get(&e.name).cloned())
+     .map(|mut url| {
         if !url.ends_with("/") {
             url.push('/')
         }
@@ -1085,65 +2509,53 @@ fn extern_location(e: &clean::ExternalCrate, dst: &Path) -> ExternalLocation {
 impl<'a> DocFolder for SourceCollector<'a> {
     fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
         // If we're including source files, and we haven't seen this file yet,
-        // then we need to render it out to the filesystem.
+        // then we need to render it out to the filesystem once every item
+        // defined in it has been seen.
         if self.scx.include_sources
-            // skip all invalid or macro spans
-            && item.source.filename.is_real()
             // skip non-local items
             && item.def_id.is_local() {
-
-            // If it turns out that we couldn't read this file, then we probably
-            // can't read any of the files (generating html output from json or
-            // something like that), so just don't include sources for the
-            // entire crate. The other option is maintaining this mapping on a
-            // per-file basis, but that's probably not worth it...
-            self.scx
-                .include_sources = match self.emit_source(&item.source.filename) {
-                Ok(()) => true,
-                Err(e) => {
-                    println!("warning: source code was requested to be rendered, \
-                              but processing `{}` had an error: {}",
-                             item.source.filename, e);
-                    println!("         skipping rendering of source code");
-                    false
+            // skip all invalid or macro spans
+            if let FileName::Real(ref path) = item.source.filename {
+                let idx = match self.files.iter().position(|&(ref p, _)| p == path) {
+                    Some(idx) => idx,
+                    None => {
+                        self.files.push((path.clone(), Vec::new()));
+                        self.files.len() - 1
+                    }
+                };
+                if self.scx.options.collapsible_source && self.parent_is_mod && !item.is_mod()
+                    && item.source.loline < item.source.hiline {
+                    self.files[idx].1.push((item.source.loline, item.source.hiline));
                 }
-            };
+            }
         }
-        self.fold_item_recur(item)
+        let parent_is_mod = mem::replace(&mut self.parent_is_mod, item.is_mod());
+        let ret = self.fold_item_recur(item);
+        self.parent_is_mod = parent_is_mod;
+        ret
     }
 }
 
 impl<'a> SourceCollector<'a> {
-    /// Renders the given filename into its corresponding HTML source file.
-    fn emit_source(&mut self, filename: &FileName) -> io::Result<()> {
-        let p = match *filename {
-            FileName::Real(ref file) => file,
-            _ => return Ok(()),
-        };
-        if self.scx.local_sources.contains_key(&**p) {
+    /// Renders the given file into its corresponding HTML source file, letting
+    /// each of the given line spans be collapsed.
+    fn emit_source(&mut self, p: &Path, spans: &[(usize, usize)]) -> io::Result<()> {
+        if self.scx.local_sources.contains_key(p) {
             // We've already emitted this source
             return Ok(());
         }
 
-        let mut contents = Vec::new();
-        File::open(&p).and_then(|mut f| f.read_to_end(&mut contents))?;
-
-        let contents = str::from_utf8(&contents).unwrap();
+        let contents = read_source(p)?;
+        let contents = &contents[..];
 
-        // Remove the utf-8 BOM if any
-        let contents = if contents.starts_with("\u{feff}") {
-            &contents[3..]
-        } else {
-            contents
-        };
+        let hash = fnv_hash(contents.as_bytes());
 
         // Create the intermediate directories
         let mut cur = self.dst.clone();
         let mut root_path = String::from("../../");
-        let mut href = String::new();
+        let mut href = format!("{}/", self.scx.layout.krate);
         clean_srcpath(&self.scx.src_root, &p, false, |component| {
             cur.push(component);
-            fs::create_dir_all(&cur).unwrap();
             root_path.push_str("../");
             href.push_str(component);
             href.push('/');
@@ -1154,26 +2566,65 @@ impl<'a> SourceCollector<'a> {
         cur.push(&fname);
         href.push_str(&fname.to_string_lossy());
 
-        let mut w = BufWriter::new(File::create(&cur)?);
+        let mut links = FxHashMap();
+        let link_to_definition = self.scx.options.link_to_definition;
+        if let (true, Some(targets)) = (link_to_definition, self.source_links.get(p)) {
+            // Make `href` compute urls relative to the source page.
+            let depth = root_path.len() / "../".len();
+            CURRENT_LOCATION_KEY.with(|s| *s.borrow_mut() = vec![String::new(); depth]);
+            for (&offset, &did) in targets {
+                if let Some((url, _, _)) = href(did) {
+                    links.insert(offset, url);
+                }
+            }
+        }
+
+        let mut w = Vec::new();
+        if let Some((canonical, source)) = self.scx.source_hashes.get(&hash).cloned() {
+            // Only redirect if the contents actually match, not just their hashes.
+            let same = read_source(&source).map(|s| s == contents).unwrap_or(false);
+            if canonical != href && same {
+                // The same file was already emitted, point there instead.
+                layout::redirect(&mut w, &format!("{}src/{}", root_path, canonical))?;
+                self.scx.sink.write(&cur, &w)?;
+                self.scx.record_redirect(format!("src/{}", href), format!("src/{}", canonical));
+                self.scx.local_sources.insert(p.to_path_buf(), canonical);
+                return Ok(());
+            }
+        }
         let title = format!("{} -- source", cur.file_name().unwrap()
                                                .to_string_lossy());
-        let desc = format!("Source to the Rust file `{}`.", filename);
+        let desc = format!("Source to the Rust file `{}`.", p.display());
         let page = layout::Page {
             title: &title,
             css_class: "source",
             root_path: &root_path,
             description: &desc,
             keywords: BASIC_KEYWORDS,
+            rtl: self.scx.options.rtl,
+            canonical: None,
         };
         layout::render(&mut w, &self.scx.layout,
-                       &page, &(""), &Source(contents),
+                       &page, &(""), &Source(contents, spans, &links),
                        self.scx.css_file_extension.is_some())?;
-        w.flush()?;
-        self.scx.local_sources.insert(p.clone(), href);
+        let w = self.scx.transform_html(w);
+        self.scx.sink.write(&cur, &w)?;
+        self.scx.source_hashes.insert(hash, (href.clone(), p.to_path_buf()));
+        self.scx.local_sources.insert(p.to_path_buf(), href);
         Ok(())
     }
 }
 
+/// Reads the source file at `path`, without its utf-8 BOM if any.
+fn read_source(path: &Path) -> io::Result<String> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+    if contents.starts_with("\u{feff}") {
+        contents.drain(..3);
+    }
+    Ok(contents)
+}
+
 impl DocFolder for Cache {
     fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
         // If this is a stripped module,
@@ -1197,6 +2648,34 @@ impl DocFolder for Cache {
             self.traits.entry(item.def_id).or_insert_with(|| t.clone());
         }
 
+        // Collect the targets of all the type aliases.
+        if let clean::TypedefItem(ref t, false) = item.inner {
+            if !self.stripped_mod {
+                self.typedefs.insert(item.def_id,
+                                     (t.type_.clone(), item.doc_value().map(str::to_owned)));
+            }
+        }
+
+        // Collect the version every stable item was stabilized in.
+        if let Some(ref stab) = item.stability {
+            if stab.level == stability::Stable && !stab.since.is_empty() && !self.stripped_mod &&
+               !self.parent_is_trait_impl {
+                let parent = match item.inner {
+                    clean::MethodItem(..) | clean::TyMethodItem(..) |
+                    clean::AssociatedConstItem(..) | clean::AssociatedTypeItem(..) |
+                    clean::TypedefItem(_, true) | clean::StructFieldItem(..) |
+                    clean::VariantItem(..) => {
+                        self.parent_stack.last().map(|&did| {
+                            (did, item.type_(), item.name.clone().unwrap_or_default())
+                        })
+                    }
+                    _ => None,
+                };
+                self.stabilized.entry(stab.since.clone()).or_insert(vec![])
+                               .push((item.def_id, parent));
+            }
+        }
+
         // Collect all the implementors of traits.
         if let clean::ImplItem(ref i) = item.inner {
             if !self.masked_crates.contains(&item.def_id.krate) {
@@ -1206,6 +2685,7 @@ impl DocFolder for Cache {
                             def_id: item.def_id,
                             stability: item.stability.clone(),
                             impl_: i.clone(),
+                            hidden: item.is_doc_hidden(),
                         });
                     }
                 }
@@ -1261,7 +2741,10 @@ impl DocFolder for Cache {
                     // A crate has a module at its root, containing all items,
                     // which should not be indexed. The crate-item itself is
                     // inserted later on when serializing the search-index.
-                    if item.def_id.index != CRATE_DEF_INDEX {
+                    if item.def_id.index != CRATE_DEF_INDEX && self.index_items {
+                        if self.count_type_usage {
+                            add_type_usage(&mut self.type_usage, &item);
+                        }
                         self.search_index.push(IndexItem {
                             ty: item.type_(),
                             name: s.to_string(),
@@ -1270,6 +2753,8 @@ impl DocFolder for Cache {
                             parent,
                             parent_idx: None,
                             search_type: get_index_search_type(&item),
+                            aliases: if parent.is_some() { doc_aliases(&item) } else { vec![] },
+                            since: index_since(&item),
                         });
                     }
                 }
@@ -1434,7 +2919,29 @@ impl Context {
     /// String representation of how to get back to the root path of the 'doc/'
     /// folder in terms of a relative URL.
     fn root_path(&self) -> String {
-        repeat("../").take(self.current.len()).collect::<String>()
+        match self.shared.absolute_root_url {
+            Some(ref url) => url.clone(),
+            None => repeat("../").take(self.current.len()).collect::<String>(),
+        }
+    }
+
+    /// Returns the url of the `id` section of the page of `it`, which is being
+    /// rendered, going through the root path. `main.js` turns it into a full
+    /// permalink that can be copied.
+    fn permalink(&self, it: &clean::Item, id: &str) -> String {
+        let mut url = self.root_path();
+        for name in &self.current {
+            url.push_str(name);
+            url.push('/');
+        }
+        if it.is_mod() {
+            url.push_str("index.html");
+        } else {
+            url.push_str(&item_path(it.type_(), it.name.as_ref().unwrap()));
+        }
+        url.push('#');
+        url.push_str(id);
+        url
     }
 
     /// Recurse in the directory structure and change the "root path" to make
@@ -1464,8 +2971,11 @@ impl Context {
 
     /// Main method for rendering a crate.
     ///
-    /// This currently isn't parallelized, but it'd be pretty easy to add
-    /// parallelization to this function.
+    /// This isn't parallelized: the items to render can't be sent to other
+    /// threads, as their attributes hold `Rc`-based token streams and interned
+    /// symbols only valid on this thread. Rendering also relies on thread-local
+    /// state (`CACHE_KEY`, `USED_ID_MAP`, `markdown::PLAYGROUND`) and on the
+    /// `RefCell`s of `SharedContext`.
     fn krate(self, mut krate: clean::Crate) -> Result<(), Error> {
         let mut item = match krate.module.take() {
             Some(i) => i,
@@ -1495,6 +3005,27 @@ impl Context {
             *slot.borrow_mut() = self.current.clone();
         });
 
+        if self.shared.options.output_format == OutputFormat::Markdown {
+            // Redirections can't be expressed in Markdown.
+            if !self.render_redirect_pages {
+                write!(writer, "{}", MarkdownItem { cx: self, item: it })?;
+            }
+            return Ok(());
+        }
+
+        // The page of the item in the module defining it, which copies of the
+        // page for re-exports should defer to.
+        let canonical_path = cache().paths.get(&it.def_id).map(|&(ref names, ty)| {
+            let mut path = String::new();
+            for name in &names[..names.len() - 1] {
+                path.push_str(name);
+                path.push_str("/");
+            }
+            path.push_str(&item_path(ty, names.last().unwrap()));
+            path
+        });
+        let canonical = canonical_path.as_ref().map(|path| format!("{}{}", self.root_path(), path));
+
         let mut title = if it.is_primitive() {
             // No need to include the namespace for primitive types
             String::new()
@@ -1507,6 +3038,11 @@ impl Context {
             }
             title.push_str(it.name.as_ref().unwrap());
         }
+        let structured_data = if self.shared.options.structured_data {
+            Some(item_structured_data(it, &title, canonical.as_ref().map(|s| &**s)))
+        } else {
+            None
+        };
         title.push_str(" - Rust");
         let tyname = it.type_().css_class();
         let desc = if it.is_crate() {
@@ -1523,6 +3059,8 @@ impl Context {
             title: &title,
             description: &desc,
             keywords: &keywords,
+            rtl: self.shared.options.rtl,
+            canonical: canonical.as_ref().map(|s| &**s),
         };
 
         reset_ids(true);
@@ -1530,22 +3068,54 @@ impl Context {
         if !self.render_redirect_pages {
             layout::render(writer, &self.shared.layout, &page,
                            &Sidebar{ cx: self, item: it },
-                           &Item{ cx: self, item: it },
+                           &Item {
+                               cx: self,
+                               item: it,
+                               structured_data: structured_data.as_ref().map(|s| &**s),
+                           },
                            self.shared.css_file_extension.is_some())?;
-        } else {
-            let mut url = self.root_path();
-            if let Some(&(ref names, ty)) = cache().paths.get(&it.def_id) {
-                for name in &names[..names.len() - 1] {
-                    url.push_str(name);
-                    url.push_str("/");
-                }
-                url.push_str(&item_path(ty, names.last().unwrap()));
-                layout::redirect(writer, &url)?;
-            }
+        } else if let Some(ref url) = canonical {
+            layout::redirect(writer, url)?;
+            let file_name = if pushname {
+                item_path(it.type_(), it.name.as_ref().unwrap())
+            } else {
+                "index.html".to_string()
+            };
+            self.shared.record_redirect(self.page_path(&file_name),
+                                        canonical_path.clone().unwrap());
         }
         Ok(())
     }
 
+    /// Returns the path of the page `file_name` of the current module,
+    /// relative to the root of the documentation.
+    fn page_path(&self, file_name: &str) -> String {
+        let mut path = String::new();
+        for name in &self.current {
+            path.push_str(name);
+            path.push('/');
+        }
+        path.push_str(file_name);
+        path
+    }
+
+    /// Records the anchor ids generated while rendering the page `file_name`
+    /// of the current module, if an anchors manifest was requested or the
+    /// links are checked.
+    fn record_anchors(&self, file_name: &str) {
+        let options = &self.shared.options;
+        if !(options.emit_anchors || options.check_links) || self.render_redirect_pages {
+            return;
+        }
+        let mut page = String::new();
+        for name in &self.current {
+            page.push_str(name);
+            page.push('/');
+        }
+        page.push_str(file_name);
+        self.shared.anchors.borrow_mut().insert(page, used_ids());
+    }
+
     /// Non-parallelized version of rendering an item. This will take the input
     /// item, render its contents, and then invoke the specified closure with
     /// all sub-items which need to be rendered.
@@ -1577,10 +3147,17 @@ impl Context {
                 this.render_item(&mut buf, &item, false).unwrap();
                 // buf will be empty if the module is stripped and there is no redirect for it
                 if !buf.is_empty() {
-                    try_err!(this.shared.ensure_dir(&this.dst), &this.dst);
-                    let joint_dst = this.dst.join("index.html");
-                    let mut dst = try_err!(File::create(&joint_dst), &joint_dst);
-                    try_err!(dst.write_all(&buf), &joint_dst);
+                    let file_name = match this.shared.options.output_format {
+                        OutputFormat::Html => "index.html",
+                        OutputFormat::Markdown => "index.md",
+                    };
+                    if this.shared.options.output_format == OutputFormat::Html {
+                        buf = this.shared.transform_html(buf);
+                    }
+                    let dst = this.dst.join(file_name);
+                    this.shared.record_item_file(&dst)?;
+                    this.record_anchors(file_name);
+                    this.shared.write(&dst, &buf)?;
                 }
 
                 let m = match item.inner {
@@ -1590,12 +3167,15 @@ impl Context {
                 };
 
                 // Render sidebar-items.js used throughout this module.
-                if !this.render_redirect_pages {
-                    let items = this.build_sidebar_items(&m);
-                    let js_dst = this.dst.join("sidebar-items.js");
-                    let mut js_out = BufWriter::new(try_err!(File::create(&js_dst), &js_dst));
-                    try_err!(write!(&mut js_out, "initSidebarItems({});",
-                                    as_json(&items)), &js_dst);
+                if !this.render_redirect_pages &&
+                    this.shared.options.output_format == OutputFormat::Html {
+                    // The categories are written in order, which a JSON object
+                    // can't convey, hence the hand-written object.
+                    let items = this.build_sidebar_items(&m).iter().map(|&(ty, ref items)| {
+                        format!("{}:{}", as_json(&ty.css_class()), as_json(items))
+                    }).collect::<Vec<_>>();
+                    let js = format!("initSidebarItems({{{}}});", items.join(","));
+                    this.shared.write(&this.dst.join("sidebar-items.js"), js.as_bytes())?;
                 }
 
                 for item in m.items {
@@ -1611,56 +3191,72 @@ impl Context {
             if !buf.is_empty() {
                 let name = item.name.as_ref().unwrap();
                 let item_type = item.type_();
-                let file_name = &item_path(item_type, name);
-                try_err!(self.shared.ensure_dir(&self.dst), &self.dst);
-                let joint_dst = self.dst.join(file_name);
-                let mut dst = try_err!(File::create(&joint_dst), &joint_dst);
-                try_err!(dst.write_all(&buf), &joint_dst);
+                let file_name = &item_file_name(item_type, name, self.shared.options.output_format);
+                let dst = self.dst.join(file_name);
+                if self.shared.options.output_format == OutputFormat::Html {
+                    buf = self.shared.transform_html(buf);
+                }
+                self.shared.record_item_file(&dst)?;
+                self.record_anchors(file_name);
+                self.shared.write(&dst, &buf)?;
+                if self.shared.options.output_format == OutputFormat::Markdown {
+                    return Ok(());
+                }
 
                 // Redirect from a sane URL using the namespace to Rustdoc's
                 // URL for the page.
                 let redir_name = format!("{}.{}.html", name, item_type.name_space());
-                let redir_dst = self.dst.join(redir_name);
-                if let Ok(redirect_out) = OpenOptions::new().create_new(true)
-                                                                .write(true)
-                                                                .open(&redir_dst) {
-                    let mut redirect_out = BufWriter::new(redirect_out);
-                    try_err!(layout::redirect(&mut redirect_out, file_name), &redir_dst);
-                }
+                let redir_dst = self.dst.join(&redir_name);
+                let mut redirect_out = Vec::new();
+                try_err!(layout::redirect(&mut redirect_out, file_name), &redir_dst);
+                try_err!(self.shared.sink.write_new(&redir_dst, &redirect_out), &redir_dst);
+                self.shared.record_redirect(self.page_path(&redir_name),
+                                            self.page_path(file_name));
 
                 // If the item is a macro, redirect from the old macro URL (with !)
                 // to the new one (without).
                 // FIXME(#35705) remove this redirect.
                 if item_type == ItemType::Macro {
                     let redir_name = format!("{}.{}!.html", item_type, name);
-                    let redir_dst = self.dst.join(redir_name);
-                    let redirect_out = try_err!(File::create(&redir_dst), &redir_dst);
-                    let mut redirect_out = BufWriter::new(redirect_out);
+                    let redir_dst = self.dst.join(&redir_name);
+                    let mut redirect_out = Vec::new();
                     try_err!(layout::redirect(&mut redirect_out, file_name), &redir_dst);
+                    self.shared.write(&redir_dst, &redirect_out)?;
+                    self.shared.record_redirect(self.page_path(&redir_name),
+                                                self.page_path(file_name));
                 }
             }
         }
         Ok(())
     }
 
-    fn build_sidebar_items(&self, m: &clean::Module) -> BTreeMap<String, Vec<NameDoc>> {
-        // BTreeMap instead of HashMap to get a sorted output
-        let mut map = BTreeMap::new();
+    fn build_sidebar_items(&self, m: &clean::Module) -> Vec<(ItemType, Vec<NameDoc>)> {
+        let mut map: Vec<(ItemType, Vec<NameDoc>)> = Vec::new();
         for item in &m.items {
             if item.is_stripped() { continue }
 
-            let short = item.type_().css_class();
+            let ty = item.type_();
             let myname = match item.name {
                 None => continue,
                 Some(ref s) => s.to_string(),
             };
-            let short = short.to_string();
-            map.entry(short).or_insert(vec![])
-                .push((myname, Some(plain_summary_line(item.doc_value()))));
+            let summary = memoized_summary(&self.shared.plain_summaries, item.def_id, || {
+                let summary = plain_summary_line(item.doc_value());
+                match self.shared.options.summary_truncation {
+                    Some(ref truncation) => truncation.truncate(&summary, SUMMARY_LEN),
+                    None => summary,
+                }
+            });
+            match map.iter().position(|&(t, _)| t == ty) {
+                Some(i) => map[i].1.push((myname, Some(summary))),
+                None => map.push((ty, vec![(myname, Some(summary))])),
+            }
         }
 
-        if self.shared.sort_modules_alphabetically {
-            for (_, items) in &mut map {
+        // Same order as the categories of `item_module`.
+        map.sort_by_key(|&(ty, _)| reorder(ty));
+        if self.shared.options.sort_modules_alphabetically {
+            for &mut (_, ref mut items) in &mut map {
                 items.sort();
             }
         }
@@ -1677,8 +3273,28 @@ impl<'a> Item<'a> {
     ///
     /// If `None` is returned, then a source link couldn't be generated. This
     /// may happen, for example, with externally inlined items where the source
-    /// of their crate documentation isn't known.
+    /// of their crate documentation isn't known. See `try_src_href` for the
+    /// reason why.
     fn src_href(&self) -> Option<String> {
+        match self.try_src_href() {
+            Ok(href) => Some(href),
+            Err(err) => {
+                if self.cx.shared.options.verbose {
+                    let span = &self.item.source;
+                    let location = format!("{}:{}:{}", span.filename, span.loline, span.locol);
+                    let mut reported = self.cx.shared.missing_src_hrefs.borrow_mut();
+                    if reported.insert((self.item.def_id, location.clone())) {
+                        println!("note: no source link for this item: {}", err);
+                        println!("   --> {}", location);
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// Like `src_href`, but returns why the source link couldn't be generated.
+    fn try_src_href(&self) -> Result<String, SrcHrefError> {
         let mut root = self.cx.root_path();
 
         let cache = cache();
@@ -1687,14 +3303,14 @@ impl<'a> Item<'a> {
         // We can safely ignore macros from other libraries
         let file = match self.item.source.filename {
             FileName::Real(ref path) => path,
-            _ => return None,
+            ref name => return Err(SrcHrefError::NotARealFile(name.clone())),
         };
 
-        let (krate, path) = if self.item.def_id.is_local() {
+        let path = if self.item.def_id.is_local() {
             if let Some(path) = self.cx.shared.local_sources.get(file) {
-                (&self.cx.shared.layout.krate, path)
+                path
             } else {
-                return None;
+                return Err(SrcHrefError::SourceNotRendered(file.clone()));
             }
         } else {
             let (krate, src_root) = match cache.extern_locations.get(&self.item.def_id.krate) {
@@ -1703,9 +3319,17 @@ impl<'a> Item<'a> {
                     root = s.to_string();
                     (name, src)
                 }
-                Some(&(_, _, Unknown)) | None => return None,
+                Some(&(ref name, ref src, Unknown)) => {
+                    return match self.cx.shared.options.extern_src_roots.get(name) {
+                        Some(base) => Ok(extern_src_href(base, src, file, &self.item.source)),
+                        None => Err(SrcHrefError::UnknownExternLocation(name.clone())),
+                    };
+                }
+                None => return Err(SrcHrefError::NoExternLocation(self.item.def_id.krate)),
             };
 
+            path.push_str(krate);
+            path.push('/');
             clean_srcpath(&src_root, file, false, |component| {
                 path.push_str(component);
                 path.push('/');
@@ -1714,7 +3338,7 @@ impl<'a> Item<'a> {
                                 .to_os_string();
             fname.push(".html");
             path.push_str(&fname.to_string_lossy());
-            (krate, &path)
+            &path
         };
 
         let lines = if self.item.source.loline == self.item.source.hiline {
@@ -1722,11 +3346,29 @@ impl<'a> Item<'a> {
         } else {
             format!("{}-{}", self.item.source.loline, self.item.source.hiline)
         };
-        Some(format!("{root}src/{krate}/{path}#{lines}",
-                     root = Escape(&root),
-                     krate = krate,
-                     path = path,
-                     lines = lines))
+        Ok(format!("{root}src/{path}#{lines}",
+                   root = Escape(&root),
+                   path = path,
+                   lines = lines))
+    }
+}
+
+/// Links to the lines of `span` in the raw source `file` of an external crate,
+/// whose sources are found under `base`.
+fn extern_src_href(base: &str, src_root: &Path, file: &Path, span: &clean::Span) -> String {
+    let mut url = base.to_string();
+    if !url.ends_with('/') {
+        url.push('/');
+    }
+    clean_srcpath(src_root, file, false, |component| {
+        url.push_str(component);
+        url.push('/');
+    });
+    url.push_str(&file.file_name().expect("source has no filename").to_string_lossy());
+    if span.loline == span.hiline {
+        format!("{}#L{}", Escape(&url), span.loline)
+    } else {
+        format!("{}#L{}-L{}", Escape(&url), span.loline, span.hiline)
     }
 }
 
@@ -1734,6 +3376,9 @@ impl<'a> fmt::Display for Item<'a> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         debug_assert!(!self.item.is_stripped());
         // Write the breadcrumb trail header for the top
+        if let Some(data) = self.structured_data {
+            write!(fmt, "<script type=\"application/ld+json\">{}</script>", data)?;
+        }
         write!(fmt, "\n<h1 class='fqn'><span class='in-band'>")?;
         match self.item.inner {
             clean::ModuleItem(ref m) => if m.is_crate {
@@ -1757,6 +3402,12 @@ impl<'a> fmt::Display for Item<'a> {
                 unreachable!();
             }
         }
+        // Paths read left-to-right whatever the page direction, so isolate
+        // them from the surrounding text.
+        let rtl = self.cx.shared.options.rtl;
+        if rtl {
+            write!(fmt, "<bdi dir='ltr'>")?;
+        }
         if !self.item.is_primitive() {
             let cur = &self.cx.current;
             let amt = if self.item.is_mod() { cur.len() - 1 } else { cur.len() };
@@ -1769,10 +3420,14 @@ impl<'a> fmt::Display for Item<'a> {
         }
         write!(fmt, "<a class=\"{}\" href=''>{}</a>",
                self.item.type_(), self.item.name.as_ref().unwrap())?;
+        if rtl {
+            write!(fmt, "</bdi>")?;
+        }
 
         write!(fmt, "</span>")?; // in-band
         write!(fmt, "<span class='out-of-band'>")?;
-        if let Some(version) = self.item.stable_since() {
+        if let (false, Some(version)) = (self.cx.shared.options.hide_stable_since,
+                                         self.item.stable_since()) {
             write!(fmt, "<span class='since' title='Stable since Rust version {0}'>{0}</span>",
                    version)?;
         }
@@ -1800,6 +3455,23 @@ impl<'a> fmt::Display for Item<'a> {
 
         write!(fmt, "</h1>\n")?;
 
+        if self.cx.shared.options.at_a_glance {
+            let badges = glance_badges(self.item);
+            if !badges.is_empty() {
+                write!(fmt, "<div class='at-a-glance'>")?;
+                for badge in badges {
+                    write!(fmt, "<span class='glance-badge'>{}</span>", badge)?;
+                }
+                write!(fmt, "</div>")?;
+            }
+        }
+
+        if self.cx.shared.options.show_import_path {
+            if let Some(path) = import_path(self.cx, self.item) {
+                write!(fmt, "<div class='import-path'><code>use {};</code></div>", path)?;
+            }
+        }
+
         match self.item.inner {
             clean::ModuleItem(ref m) => {
                 item_module(fmt, self.cx, self.item, &m.items)
@@ -1825,6 +3497,63 @@ impl<'a> fmt::Display for Item<'a> {
     }
 }
 
+/// The order of item types in the module listings, and in the sidebar.
+fn reorder(ty: ItemType) -> u8 {
+    match ty {
+        ItemType::ExternCrate     => 0,
+        ItemType::Import          => 1,
+        ItemType::Primitive       => 2,
+        ItemType::Module          => 3,
+        ItemType::Macro           => 4,
+        ItemType::Struct          => 5,
+        ItemType::Enum            => 6,
+        ItemType::Constant        => 7,
+        ItemType::Static          => 8,
+        ItemType::Trait           => 9,
+        ItemType::Function        => 10,
+        ItemType::Typedef         => 12,
+        ItemType::Union           => 13,
+        _                         => 14 + ty as u8,
+    }
+}
+
+// ordering taken from item_module, reorder, where it prioritized elements in a certain order
+// to print its headings
+const ITEM_SECTIONS: &'static [ItemType] = &[
+    ItemType::Primitive, ItemType::Module, ItemType::Macro, ItemType::Struct,
+    ItemType::Enum, ItemType::Constant, ItemType::Static, ItemType::Trait,
+    ItemType::Function, ItemType::Typedef, ItemType::Union, ItemType::Impl,
+    ItemType::TyMethod, ItemType::Method, ItemType::StructField, ItemType::Variant,
+    ItemType::AssociatedType, ItemType::AssociatedConst, ItemType::ForeignType,
+];
+
+/// Returns the id and the title of the section listing items of type `ty`.
+fn item_ty_to_strs(ty: &ItemType) -> (&'static str, &'static str) {
+    match *ty {
+        ItemType::ExternCrate |
+        ItemType::Import          => ("reexports", "Reexports"),
+        ItemType::Module          => ("modules", "Modules"),
+        ItemType::Struct          => ("structs", "Structs"),
+        ItemType::Union           => ("unions", "Unions"),
+        ItemType::Enum            => ("enums", "Enums"),
+        ItemType::Function        => ("functions", "Functions"),
+        ItemType::Typedef         => ("types", "Type Definitions"),
+        ItemType::Static          => ("statics", "Statics"),
+        ItemType::Constant        => ("constants", "Constants"),
+        ItemType::Trait           => ("traits", "Traits"),
+        ItemType::Impl            => ("impls", "Implementations"),
+        ItemType::TyMethod        => ("tymethods", "Type Methods"),
+        ItemType::Method          => ("methods", "Methods"),
+        ItemType::StructField     => ("fields", "Struct Fields"),
+        ItemType::Variant         => ("variants", "Variants"),
+        ItemType::Macro           => ("macros", "Macros"),
+        ItemType::Primitive       => ("primitives", "Primitive Types"),
+        ItemType::AssociatedType  => ("associated-types", "Associated Types"),
+        ItemType::AssociatedConst => ("associated-consts", "Associated Constants"),
+        ItemType::ForeignType     => ("foreign-types", "Foreign Types"),
+    }
+}
+
 fn item_path(ty: ItemType, name: &str) -> String {
     match ty {
         ItemType::Module => format!("{}/index.html", name),
@@ -1832,6 +3561,75 @@ fn item_path(ty: ItemType, name: &str) -> String {
     }
 }
 
+/// Returns the name of the page documenting an item of type `ty` named `name`
+/// in the given format.
+fn item_file_name(ty: ItemType, name: &str, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Html => item_path(ty, name),
+        OutputFormat::Markdown => match ty {
+            ItemType::Module => format!("{}/index.md", name),
+            _ => format!("{}.{}.md", ty.css_class(), name),
+        },
+    }
+}
+
+/// Collects the key facts about `item` otherwise scattered across its page: its stability,
+/// deprecation, and the qualifiers of its signature.
+fn glance_badges(item: &clean::Item) -> Vec<String> {
+    let mut badges = vec![];
+    if let Some(ref stab) = item.stability {
+        if stab.level == stability::Unstable {
+            badges.push("unstable".to_string());
+        } else if !stab.since.is_empty() {
+            badges.push(format!("stable since {}", Escape(&stab.since)));
+        }
+    }
+    if is_deprecated(item) {
+        badges.push("deprecated".to_string());
+    }
+    match item.inner {
+        clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => {
+            if f.constness == hir::Constness::Const {
+                badges.push("const".to_string());
+            }
+            if f.unsafety == hir::Unsafety::Unsafe {
+                badges.push("unsafe".to_string());
+            }
+            if f.abi != abi::Abi::Rust {
+                badges.push(format!("extern &quot;{}&quot;", f.abi.name()));
+            }
+        }
+        clean::TraitItem(ref t) if t.unsafety == hir::Unsafety::Unsafe => {
+            badges.push("unsafe".to_string());
+        }
+        clean::StaticItem(ref s) | clean::ForeignStaticItem(ref s)
+            if s.mutability == clean::Mutable => {
+            badges.push("mutable".to_string());
+        }
+        _ => {}
+    }
+    badges
+}
+
+/// Returns the path to write in a `use` statement importing `item`: the shortest of the path
+/// the item is documented at and the path recorded for it in the cache.
+fn import_path(cx: &Context, item: &clean::Item) -> Option<String> {
+    match item.inner {
+        clean::PrimitiveItem(..) | clean::MacroItem(..) => return None,
+        _ if item.is_crate() => return None,
+        _ => {}
+    }
+    let &(ref fqp, _) = cache().paths.get(&item.def_id)?;
+    let mut path = cx.current.clone();
+    if !item.is_mod() {
+        path.push(item.name.clone()?);
+    }
+    if fqp.len() < path.len() {
+        path = fqp.clone();
+    }
+    Some(path.join("::"))
+}
+
 fn full_path(cx: &Context, item: &clean::Item) -> String {
     let mut s = cx.current.join("::");
     s.push_str("::");
@@ -1852,6 +3650,32 @@ fn shorter<'a>(s: Option<&'a str>) -> String {
     }
 }
 
+/// Returns the first paragraph of `s` as plain text on a single line, shortened according to
+/// `truncation`. The markdown is stripped first so that the cut can't fall in the middle of a
+/// link or a code span.
+fn truncated_summary(s: &str, truncation: &Truncation) -> String {
+    truncation.truncate(&plain_summary_line(Some(s)), SUMMARY_LEN)
+}
+
+/// Describes `it`, whose full name is `name`, in JSON-LD for search engines and other tools.
+fn item_structured_data(it: &clean::Item, name: &str, url: Option<&str>) -> String {
+    let mut data = BTreeMap::new();
+    data.insert("@context".to_owned(), "https://schema.org".to_json());
+    data.insert("@type".to_owned(), "APIReference".to_json());
+    data.insert("name".to_owned(), name.to_json());
+    data.insert("genre".to_owned(), it.type_().css_class().to_json());
+    let description = plain_summary_line(it.doc_value());
+    if !description.is_empty() {
+        data.insert("description".to_owned(), description.to_json());
+    }
+    if let Some(url) = url {
+        data.insert("url".to_owned(), url.to_json());
+    }
+    // `<` can only appear inside of strings, where escaping it keeps the script
+    // from being closed early.
+    Json::Object(data).to_string().replace("<", "\\u003c")
+}
+
 #[inline]
 fn plain_summary_line(s: Option<&str>) -> String {
     let line = shorter(s).replace("\n", " ");
@@ -1862,14 +3686,17 @@ fn document(w: &mut fmt::Formatter, cx: &Context, item: &clean::Item) -> fmt::Re
     if let Some(ref name) = item.name {
         info!("Documenting {}", name);
     }
+    if cx.shared.options.signatures_only {
+        return Ok(());
+    }
     document_stability(w, cx, item)?;
-    let prefix = render_assoc_const_value(item);
+    let prefix = render_assoc_const_value(cx, item);
     document_full(w, item, cx, &prefix)?;
     Ok(())
 }
 
 /// Render md_text as markdown. Warns the user if there are difference in
-/// rendering between Pulldown and Hoedown.
+/// rendering between Pulldown and Hoedown, unless only Pulldown is used.
 fn render_markdown(w: &mut fmt::Formatter,
                    md_text: &str,
                    span: Span,
@@ -1877,6 +3704,16 @@ fn render_markdown(w: &mut fmt::Formatter,
                    prefix: &str,
                    scx: &SharedContext)
                    -> fmt::Result {
+    let md_text = if scx.options.include_lines {
+        expand_included_lines(md_text, &span, &scx.src_root)
+    } else {
+        Cow::Borrowed(md_text)
+    };
+    let md_text = &*md_text;
+    if scx.options.pulldown_only {
+        return write!(w, "<div class='docblock'>{}{}</div>",
+                      prefix, Markdown(md_text, RenderType::Pulldown));
+    }
     let (hoedown_output, pulldown_output) = render_text(|ty| format!("{}", Markdown(md_text, ty)));
     let mut differences = html_diff::get_differences(&pulldown_output, &hoedown_output);
     differences.retain(|s| {
@@ -1892,7 +3729,8 @@ fn render_markdown(w: &mut fmt::Formatter,
     });
 
     if !differences.is_empty() {
-        scx.markdown_warnings.borrow_mut().push((span, md_text.to_owned(), differences));
+        let warning = MarkdownWarning::Differences(md_text.to_owned(), differences);
+        scx.markdown_warnings.borrow_mut().push((span, warning));
     }
 
     write!(w, "<div class='docblock'>{}{}</div>",
@@ -1900,12 +3738,108 @@ fn render_markdown(w: &mut fmt::Formatter,
            if render_type == RenderType::Pulldown { pulldown_output } else { hoedown_output })
 }
 
+/// Parses an `{{include-lines path:start:end}}` directive into the path and
+/// the 1-based, inclusive range of lines to include.
+fn parse_include_lines(line: &str) -> Option<(&str, usize, usize)> {
+    let line = line.trim();
+    if !line.starts_with("{{include-lines ") || !line.ends_with("}}") {
+        return None;
+    }
+    let directive = line["{{include-lines ".len()..line.len() - 2].trim();
+    let mut parts = directive.rsplitn(3, ':');
+    let end = parts.next().and_then(|n| n.parse().ok());
+    let start = parts.next().and_then(|n| n.parse().ok());
+    match (parts.next(), start, end) {
+        (Some(path), Some(start), Some(end)) if !path.is_empty() => Some((path, start, end)),
+        _ => None,
+    }
+}
+
+/// Replaces every `{{include-lines path:start:end}}` directive standing on its
+/// own line in `md_text` by a rust code block holding the given lines of
+/// `path`, resolved relative to `src_root`. Directives inside fenced code
+/// blocks are kept as is. Directives which can't be expanded, including the
+/// ones pointing outside of `src_root`, are left untouched and reported.
+fn expand_included_lines<'a>(md_text: &'a str, span: &Span, src_root: &Path) -> Cow<'a, str> {
+    if !md_text.contains("{{include-lines ") {
+        return Cow::Borrowed(md_text);
+    }
+    let mut out = String::with_capacity(md_text.len());
+    // The fence opening the code block the current line is in, if any.
+    let mut fence: Option<String> = None;
+    for line in md_text.lines() {
+        let trimmed = line.trim_left();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            let marker = trimmed.chars().next().unwrap();
+            let len = trimmed.chars().take_while(|&c| c == marker).count();
+            let closes = match fence {
+                Some(ref open) => {
+                    open.starts_with(marker) && len >= open.len() &&
+                    trimmed[len..].trim().is_empty()
+                }
+                None => false,
+            };
+            if closes {
+                fence = None;
+            } else if fence.is_none() {
+                fence = Some(trimmed[..len].to_string());
+            }
+        }
+        let directive = if fence.is_none() { parse_include_lines(line) } else { None };
+        let (path, start, end) = match directive {
+            Some(directive) => directive,
+            None => {
+                out.push_str(line);
+                out.push('\n');
+                continue;
+            }
+        };
+        let inside_root = Path::new(path).components().all(|c| match c {
+            Component::Normal(_) | Component::CurDir => true,
+            _ => false,
+        });
+        if !inside_root {
+            println!("warning: refusing to include `{}`, which is outside of the source root",
+                     path);
+            println!("   --> {}:{}:{}", span.filename, span.loline, span.locol);
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+        let file = src_root.join(path);
+        let mut contents = String::new();
+        let included = File::open(&file).and_then(|mut f| f.read_to_string(&mut contents));
+        let lines = contents.lines().collect::<Vec<_>>();
+        if included.is_err() || start == 0 || start > end || end > lines.len() {
+            println!("warning: could not include lines {} to {} of `{}`",
+                     start, end, file.display());
+            println!("   --> {}:{}:{}", span.filename, span.loline, span.locol);
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+        out.push_str("```rust\n");
+        for l in &lines[start - 1..end] {
+            out.push_str(l);
+            out.push('\n');
+        }
+        out.push_str("```\n");
+    }
+    Cow::Owned(out)
+}
+
 fn document_short(w: &mut fmt::Formatter, item: &clean::Item, link: AssocItemLink,
                   cx: &Context, prefix: &str) -> fmt::Result {
+    if cx.shared.options.signatures_only {
+        return Ok(());
+    }
     if let Some(s) = item.doc_value() {
         let markdown = if s.contains('\n') {
-            format!("{} [Read more]({})",
-                    &plain_summary_line(Some(s)), naive_assoc_href(item, link))
+            format!("{} [{}]({})",
+                    &plain_summary_line(Some(s)),
+                    markdown::escape_markdown(cx.shared.options.read_more_text.as_ref()
+                                                .map_or("Read more", |s| &**s)),
+                    naive_assoc_href(item, link))
         } else {
             format!("{}", &plain_summary_line(Some(s)))
         };
@@ -1916,26 +3850,70 @@ fn document_short(w: &mut fmt::Formatter, item: &clean::Item, link: AssocItemLin
     Ok(())
 }
 
-fn render_assoc_const_value(item: &clean::Item) -> String {
+fn render_assoc_const_value(cx: &Context, item: &clean::Item) -> String {
     match item.inner {
         clean::AssociatedConstItem(ref ty, Some(ref default)) => {
-            highlight::render_with_highlighting(
-                &format!("{}: {:#} = {}", item.name.as_ref().unwrap(), ty, default),
+            let name = item.name.as_ref().unwrap();
+            let max_len = cx.shared.options.assoc_const_value_len.unwrap_or(ASSOC_CONST_VALUE_LEN);
+            let full = highlight::render_with_highlighting(
+                &format!("{}: {:#} = {}", name, ty, default),
                 None,
                 None,
                 None,
                 None,
-            )
+            );
+            if default.len() <= max_len {
+                return full;
+            }
+            // Highlighting the shortened value would choke on the string
+            // literals it cuts, so it is shown as is.
+            let short = shorten_const_value(ty, default, max_len);
+            format!("<pre class='rust const-value'>{}</pre>\
+                     <details class='const-value'><summary>\u{2026} (expand)</summary>{}\
+                     </details>",
+                    Escape(&format!("{}: {:#} = {}", name, ty, short)), full)
         }
         _ => String::new(),
     }
 }
 
+/// Cuts the value `default` of a constant of type `ty` down to `max` bytes,
+/// closing the string literal or the array it starts with, if any.
+fn shorten_const_value(ty: &clean::Type, default: &str, max: usize) -> String {
+    let closing = match *ty {
+        clean::BorrowedRef { ref type_, .. } => match **type_ {
+            clean::Primitive(clean::PrimitiveType::Str) if default.starts_with('"') => "\"",
+            clean::Slice(_) if default.starts_with("&[") => "]",
+            _ => "",
+        },
+        clean::Array(..) if default.starts_with('[') => "]",
+        _ => "",
+    };
+    let mut short = Truncation::new(TruncateAt::End, None).truncate(default, max);
+    short.push_str(closing);
+    short
+}
+
 fn document_full(w: &mut fmt::Formatter, item: &clean::Item,
                  cx: &Context, prefix: &str) -> fmt::Result {
+    if cx.shared.options.signatures_only {
+        return Ok(());
+    }
     if let Some(s) = cx.shared.maybe_collapsed_doc_value(item) {
         debug!("Doc block: =====\n{}\n=====", s);
+        if cx.shared.options.show_panics_badge &&
+           (item.is_fn() || item.is_method() || item.is_ty_method()) {
+            if let Some(id) = panics_section_id(&s) {
+                write!(w, "<div class='panics-badge'><a href='#{}'>May panic</a></div>", id)?;
+            }
+        }
         render_markdown(w, &*s, item.source.clone(), cx.render_type, prefix, &cx.shared)?;
+        if cx.shared.options.check_links {
+            let mut doc_links = cx.shared.doc_links.borrow_mut();
+            for link in markdown::markdown_links(&s) {
+                doc_links.push((item.source.clone(), cx.dst.clone(), link));
+            }
+        }
     } else if !prefix.is_empty() {
         write!(w, "<div class='docblock'>{}</div>", prefix)?;
     }
@@ -1955,12 +3933,15 @@ fn document_stability(w: &mut fmt::Formatter, cx: &Context, item: &clean::Item)
 }
 
 fn name_key(name: &str) -> (&str, u64, usize) {
-    // find number at end
-    let split = name.bytes().rposition(|b| b < b'0' || b'9' < b).map_or(0, |s| s + 1);
+    // find the run of ASCII digits at the end, which starts on a char boundary
+    let split = name.char_indices()
+                    .rev()
+                    .find(|&(_, c)| c < '0' || '9' < c)
+                    .map_or(0, |(i, c)| i + c.len_utf8());
 
     // count leading zeroes
     let after_zeroes =
-        name[split..].bytes().position(|b| b != b'0').map_or(name.len(), |extra| split + extra);
+        name[split..].find(|c| c != '0').map_or(name.len(), |extra| split + extra);
 
     // sort leading zeroes last
     let num_zeroes = after_zeroes - split;
@@ -1971,10 +3952,29 @@ fn name_key(name: &str) -> (&str, u64, usize) {
     }
 }
 
+/// Sorts versions such as `1.9.0` and `1.10.0` component by component. As in
+/// semver, pre-releases such as `1.10.0-beta` come before the release.
+fn version_key(version: &str) -> (Vec<(&str, u64, usize)>, bool, Vec<(&str, u64, usize)>) {
+    let (release, pre) = match version.find('-') {
+        Some(i) => (&version[..i], Some(&version[i + 1..])),
+        None => (version, None),
+    };
+    (release.split('.').map(name_key).collect(),
+     pre.is_none(),
+     pre.map_or(vec![], |pre| pre.split('.').map(name_key).collect()))
+}
+
 fn item_module(w: &mut fmt::Formatter, cx: &Context,
                item: &clean::Item, items: &[clean::Item]) -> fmt::Result {
     document(w, cx, item)?;
 
+    // The items of a deprecated module are deprecated along with it.
+    let module_deprecated = is_deprecated(item);
+    if module_deprecated {
+        write!(w, "<div class='stability'><div class='stab deprecated module-deprecation'>\
+                   This module is deprecated, and so are all of its items.</div></div>")?;
+    }
+
     let mut indices = (0..items.len()).filter(|i| {
         if let clean::AutoImplItem(..) = items[*i].inner {
             return false;
@@ -1982,26 +3982,6 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
         !items[*i].is_stripped()
     }).collect::<Vec<usize>>();
 
-    // the order of item types in the listing
-    fn reorder(ty: ItemType) -> u8 {
-        match ty {
-            ItemType::ExternCrate     => 0,
-            ItemType::Import          => 1,
-            ItemType::Primitive       => 2,
-            ItemType::Module          => 3,
-            ItemType::Macro           => 4,
-            ItemType::Struct          => 5,
-            ItemType::Enum            => 6,
-            ItemType::Constant        => 7,
-            ItemType::Static          => 8,
-            ItemType::Trait           => 9,
-            ItemType::Function        => 10,
-            ItemType::Typedef         => 12,
-            ItemType::Union           => 13,
-            _                         => 14 + ty as u8,
-        }
-    }
-
     fn cmp(i1: &clean::Item, i2: &clean::Item, idx1: usize, idx2: usize) -> Ordering {
         let ty1 = i1.type_();
         let ty2 = i2.type_();
@@ -2020,9 +4000,13 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
         name_key(lhs).cmp(&name_key(rhs))
     }
 
-    if cx.shared.sort_modules_alphabetically {
+    if cx.shared.options.sort_modules_alphabetically {
         indices.sort_by(|&i1, &i2| cmp(&items[i1], &items[i2], i1, i2));
     }
+    if cx.shared.options.group_by_cfg {
+        // `sort_by_key` is stable, so the items of a group keep their order.
+        indices.sort_by_key(|&i| (reorder(items[i].type_()), cfg_group(&items[i])));
+    }
     // This call is to remove reexport duplicates in cases such as:
     //
     // ```
@@ -2038,25 +4022,24 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
     //
     // `Double` will appear twice in the generated docs.
     //
-    // FIXME: This code is quite ugly and could be improved. Small issue: DefId
-    // can be identical even if the elements are different (mostly in imports).
-    // So in case this is an import, we keep everything by adding a "unique id"
-    // (which is the position in the vector).
-    indices.dedup_by_key(|i| (items[*i].def_id,
-                              if items[*i].name.as_ref().is_some() {
-                                  Some(full_path(cx, &items[*i]).clone())
-                              } else {
-                                  None
-                              },
-                              items[*i].type_(),
-                              if items[*i].is_import() {
-                                  *i
-                              } else {
-                                  0
-                              }));
+    // Imports which aren't inlined are keyed by what they import rather than
+    // by their `DefId`, which is shared by all the imports of a `use` list
+    // such as `pub use foo::{Double, Single};`, so that each of them is kept.
+    indices.dedup_by_key(|i| {
+        let item = &items[*i];
+        let import = match item.inner {
+            clean::ImportItem(ref import) => Some(import.to_string()),
+            _ => None,
+        };
+        (item.def_id,
+         item.name.as_ref().map(|_| full_path(cx, item)),
+         item.type_(),
+         import)
+    });
 
     debug!("{:?}", indices);
     let mut curty = None;
+    let mut curcfg = None;
     for &idx in &indices {
         let myitem = &items[idx];
         if myitem.is_stripped() {
@@ -2072,33 +4055,21 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
                 write!(w, "</table>")?;
             }
             curty = myty;
-            let (short, name) = match myty.unwrap() {
-                ItemType::ExternCrate |
-                ItemType::Import          => ("reexports", "Reexports"),
-                ItemType::Module          => ("modules", "Modules"),
-                ItemType::Struct          => ("structs", "Structs"),
-                ItemType::Union           => ("unions", "Unions"),
-                ItemType::Enum            => ("enums", "Enums"),
-                ItemType::Function        => ("functions", "Functions"),
-                ItemType::Typedef         => ("types", "Type Definitions"),
-                ItemType::Static          => ("statics", "Statics"),
-                ItemType::Constant        => ("constants", "Constants"),
-                ItemType::Trait           => ("traits", "Traits"),
-                ItemType::Impl            => ("impls", "Implementations"),
-                ItemType::TyMethod        => ("tymethods", "Type Methods"),
-                ItemType::Method          => ("methods", "Methods"),
-                ItemType::StructField     => ("fields", "Struct Fields"),
-                ItemType::Variant         => ("variants", "Variants"),
-                ItemType::Macro           => ("macros", "Macros"),
-                ItemType::Primitive       => ("primitives", "Primitive Types"),
-                ItemType::AssociatedType  => ("associated-types", "Associated Types"),
-                ItemType::AssociatedConst => ("associated-consts", "Associated Constants"),
-                ItemType::ForeignType     => ("foreign-types", "Foreign Types"),
-            };
+            curcfg = None;
+            let (short, name) = item_ty_to_strs(&myty.unwrap());
             write!(w, "<h2 id='{id}' class='section-header'>\
                        <a href=\"#{id}\">{name}</a></h2>\n<table>",
                    id = derive_id(short.to_owned()), name = name)?;
         }
+        if cx.shared.options.group_by_cfg {
+            let mycfg = cfg_group(myitem);
+            if mycfg != curcfg {
+                if let Some(ref cfg) = mycfg {
+                    write!(w, "</table><h3 class='cfg-group'>{}</h3>\n<table>", cfg)?;
+                }
+                curcfg = mycfg;
+            }
+        }
 
         match myitem.inner {
             clean::ExternCrateItem(ref name, ref src) => {
@@ -2128,7 +4099,11 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
             _ => {
                 if myitem.name.is_none() { continue }
 
-                let stabilities = short_stability(myitem, cx, false);
+                let mut stabilities = short_stability(myitem, cx, false);
+                if module_deprecated && !is_deprecated(myitem) {
+                    stabilities.insert(0, "<div class='stab deprecated'>Deprecated</div>"
+                                              .to_string());
+                }
 
                 let stab_docs = if !stabilities.is_empty() {
                     stabilities.iter()
@@ -2148,27 +4123,60 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
                     _ => "",
                 };
 
-                let doc_value = myitem.doc_value().unwrap_or("");
+                let abi_flag = match myitem.inner {
+                    clean::FunctionItem(ref func) if func.abi != abi::Abi::Rust => {
+                        format!("<sup class='abi-flag' title='extern \"{0}\" function'>\
+                                 {0}</sup>", func.abi.name())
+                    }
+                    _ => String::new(),
+                };
+
+                let since = if cx.shared.options.module_since_column &&
+                               !cx.shared.options.hide_stable_since {
+                    format!("<td class='since-column'>{}</td>",
+                            match myitem.stable_since() {
+                                Some(v) if !v.is_empty() => {
+                                    format!("<span class='since' \
+                                             title='Stable since Rust version {0}'>{0}</span>",
+                                            v)
+                                }
+                                _ => String::new(),
+                            })
+                } else {
+                    String::new()
+                };
+
+                let docs = memoized_summary(&cx.shared.summaries, myitem.def_id, || {
+                    let doc_value = myitem.doc_value().unwrap_or("");
+                    if let Some(ref truncation) = cx.shared.options.summary_truncation {
+                        format!("{}", Escape(&truncated_summary(doc_value, truncation)))
+                    } else if cx.render_type == RenderType::Hoedown {
+                        format!("{}",
+                                shorter(Some(&Markdown(doc_value,
+                                                       RenderType::Hoedown).to_string())))
+                    } else {
+                        format!("{}", MarkdownSummaryLine(doc_value))
+                    }
+                });
                 write!(w, "
-                       <tr class='{stab} module-item'>
+                       <tr class='{stab} module-item'{deprecated}>
                            <td><a class=\"{class}\" href=\"{href}\"
-                                  title='{title_type} {title}'>{name}</a>{unsafety_flag}</td>
+                                  title='{title_type} {title}'>{name}</a>{unsafety_flag}\
+                                  {abi_flag}</td>
+                           {since}
                            <td class='docblock-short'>
                                {stab_docs} {docs}
                            </td>
                        </tr>",
                        name = *myitem.name.as_ref().unwrap(),
+                       since = since,
                        stab_docs = stab_docs,
-                       docs = if cx.render_type == RenderType::Hoedown {
-                           format!("{}",
-                                   shorter(Some(&Markdown(doc_value,
-                                                          RenderType::Hoedown).to_string())))
-                       } else {
-                           format!("{}", MarkdownSummaryLine(doc_value))
-                       },
+                       docs = docs,
                        class = myitem.type_(),
                        stab = myitem.stability_class().unwrap_or("".to_string()),
+                       deprecated = if module_deprecated { " data-deprecated" } else { "" },
                        unsafety_flag = unsafety_flag,
+                       abi_flag = abi_flag,
                        href = item_path(myitem.type_(), myitem.name.as_ref().unwrap()),
                        title_type = myitem.type_(),
                        title = full_path(cx, myitem))?;
@@ -2179,9 +4187,54 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
     if curty.is_some() {
         write!(w, "</table>")?;
     }
+
+    if item.is_crate() && !cx.shared.options.related_crates.is_empty() {
+        render_related_crates(w, cx)?;
+    }
     Ok(())
 }
 
+/// Returns the short rendering of the `cfg` of `item`, under which it is listed
+/// in its module with `--group-by-cfg`.
+fn cfg_group(item: &clean::Item) -> Option<String> {
+    item.attrs.cfg.as_ref().map(|cfg| cfg.render_short_html())
+}
+
+/// Renders the crate root section linking to the documentation of the crates passed with
+/// `--related-crate`. Crates we know to be documented remotely link there, all the others
+/// are assumed to live next to this crate in the output directory.
+fn render_related_crates(w: &mut fmt::Formatter, cx: &Context) -> fmt::Result {
+    let cache = cache();
+    write!(w, "<h2 id='{id}' class='section-header'>\
+               <a href=\"#{id}\">Related crates</a></h2>\n<table>",
+           id = derive_id("related-crates".to_owned()))?;
+    for name in &cx.shared.options.related_crates {
+        let location = cache.extern_locations.values().find(|&&(ref krate, ..)| krate == name);
+        let root = match location {
+            Some(&(_, _, Remote(ref s))) => s.to_string(),
+            _ => cx.root_path(),
+        };
+        write!(w, "<tr class='module-item'><td><a class='mod' href='{root}{name}/index.html'>\
+                   {name}</a></td></tr>",
+               root = Escape(&root), name = Escape(name))?;
+    }
+    write!(w, "</table>")
+}
+
+/// Returns whether `item` carries a deprecation of its own.
+fn is_deprecated(item: &clean::Item) -> bool {
+    item.deprecation.is_some() ||
+        item.stability.as_ref().map_or(false, |stab| !stab.deprecated_since.is_empty())
+}
+
+/// Returns the escaped text of the link to the tracking issue `issue_no`.
+fn issue_link_text(cx: &Context, issue_no: u32) -> String {
+    match cx.shared.issue_tracker_link_format {
+        Some(ref format) => Escape(format).to_string().replace("{}", &issue_no.to_string()),
+        None => format!("#{}", issue_no),
+    }
+}
+
 fn short_stability(item: &clean::Item, cx: &Context, show_reason: bool) -> Vec<String> {
     let mut stability = vec![];
 
@@ -2209,11 +4262,12 @@ fn short_stability(item: &clean::Item, cx: &Context, show_reason: bool) -> Vec<S
                                             &cx.shared.issue_tracker_base_url,
                                             stab.issue) {
                     (true, &Some(ref tracker_url), Some(issue_no)) if issue_no > 0 =>
-                        format!(" (<code>{} </code><a href=\"{}{}\">#{}</a>)",
-                                Escape(&stab.feature), tracker_url, issue_no, issue_no),
+                        format!(" (<code>{} </code><a href=\"{}{}\">{}</a>)",
+                                Escape(&stab.feature), tracker_url, issue_no,
+                                issue_link_text(cx, issue_no)),
                     (false, &Some(ref tracker_url), Some(issue_no)) if issue_no > 0 =>
-                        format!(" (<a href=\"{}{}\">#{}</a>)", Escape(&tracker_url), issue_no,
-                                issue_no),
+                        format!(" (<a href=\"{}{}\">{}</a>)", Escape(&tracker_url), issue_no,
+                                issue_link_text(cx, issue_no)),
                     (true, ..) =>
                         format!(" (<code>{}</code>)", Escape(&stab.feature)),
                     _ => String::new(),
@@ -2321,15 +4375,201 @@ fn item_function(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
            abi = AbiSpace(f.abi),
            name = it.name.as_ref().unwrap(),
            generics = f.generics,
-           where_clause = WhereClause { gens: &f.generics, indent: 0, end_newline: true },
+           where_clause = WhereClause {
+               gens: &f.generics,
+               indent: 0,
+               end_newline: true,
+               group_bounds: cx.shared.options.group_where_bounds,
+           },
            decl = Method {
               decl: &f.decl,
               name_len,
               indent: 0,
            })?;
+    if let Some(reason) = must_use_reason(it) {
+        write!(w, "<div class='stab must-use'>The return value of this function must be used: \
+                   {}</div>", Escape(&reason))?;
+    }
     document(w, cx, it)
 }
 
+/// Returns the message given to `#[must_use = "..."]` on `it`, if any.
+fn must_use_reason(it: &clean::Item) -> Option<String> {
+    it.attrs.other_attrs.iter()
+        .find(|a| a.check_name("must_use"))
+        .and_then(|a| a.value_str())
+        .map(|reason| reason.to_string())
+}
+
+/// Returns the message given to `#[rustc_on_unimplemented = "..."]` or to the
+/// `message` of `#[rustc_on_unimplemented(message = "...")]` on `it`, if any.
+fn on_unimplemented_message(it: &clean::Item) -> Option<String> {
+    let attr = it.attrs.other_attrs.iter().find(|a| a.check_name("rustc_on_unimplemented"))?;
+    attr.value_str().or_else(|| {
+        attr.meta_item_list()?.iter()
+            .find(|item| item.check_name("message"))
+            .and_then(|item| item.value_str())
+    }).map(|message| message.to_string())
+}
+
+/// Returns the id the `# Panics` section of the documentation `doc` will be
+/// given once rendered, if it has one. Must be called right before `doc` is
+/// rendered, as the ids depend on the ones already used in the page.
+fn panics_section_id(doc: &str) -> Option<String> {
+    let headings = markdown::markdown_headings(doc);
+    let pos = headings.iter().position(|heading| heading.trim() == "Panics")?;
+    USED_ID_MAP.with(|map| {
+        // Replay the ids of the headings up to the section without using them.
+        let mut map = map.borrow().clone();
+        headings[..pos + 1].iter().map(|heading| {
+            derive_id_in(&mut map, heading.chars().filter_map(markdown::slugify).collect())
+        }).last()
+    })
+}
+
+/// Returns the declaration of the trait method `m` as seen through a trait
+/// object, with every `Self` outside of the receiver replaced by `dyn_self`.
+///
+/// Returns `None` if `m` isn't callable on a trait object.
+fn dyn_erased_decl(m: &clean::Item, dyn_self: &clean::Type) -> Option<clean::FnDecl> {
+    let (decl, generics) = match m.inner {
+        clean::TyMethodItem(ref m) => (&m.decl, &m.generics),
+        clean::MethodItem(ref m) => (&m.decl, &m.generics),
+        _ => return None,
+    };
+    // Generic methods, including the ones taking `impl Trait` arguments, can't be called through
+    // a vtable.
+    let impl_trait_args = decl.inputs.values.iter().any(|a| match a.type_ {
+        clean::ImplTrait(..) => true,
+        _ => false,
+    });
+    if decl.self_type().is_none() || !generics.type_params.is_empty() || impl_trait_args {
+        return None;
+    }
+    let requires_sized = generics.where_predicates.iter().any(|p| match *p {
+        clean::WherePredicate::BoundPredicate { ref ty, ref bounds } if ty.is_self_type() => {
+            bounds.iter().any(|b| match *b {
+                clean::TraitBound(clean::PolyTrait {
+                    trait_: clean::ResolvedPath { ref path, .. }, ..
+                }, hir::TraitBoundModifier::None) => path.last_name() == "Sized",
+                _ => false,
+            })
+        }
+        _ => false,
+    });
+    if requires_sized {
+        return None;
+    }
+    // `Self` can't be passed or returned by value through a trait object.
+    let by_value = decl.inputs.values.iter().skip(1).any(|a| holds_self_by_value(&a.type_)) ||
+                   match decl.output {
+                       clean::Return(ref ty) => holds_self_by_value(ty),
+                       clean::DefaultReturn => false,
+                   };
+    if by_value {
+        return None;
+    }
+
+    let mut erased = decl.clone();
+    for arg in erased.inputs.values.iter_mut().skip(1) {
+        arg.type_ = erase_self(&arg.type_, dyn_self);
+    }
+    if let clean::Return(ref mut ty) = erased.output {
+        *ty = erase_self(ty, dyn_self);
+    }
+    Some(erased)
+}
+
+/// Whether `ty` holds a `Self` other than behind a pointer, such as `Self` itself or
+/// `Option<Self>`, which can't be done once `Self` is an unsized trait object.
+fn holds_self_by_value(ty: &clean::Type) -> bool {
+    fn behind_pointer(ty: &clean::Type) -> bool {
+        !ty.is_self_type() && holds_self_by_value(ty)
+    }
+
+    match *ty {
+        clean::Generic(ref name) => name == "Self",
+        clean::ResolvedPath { ref path, .. } => {
+            let pointer = match path.last_name() {
+                "Box" | "Rc" | "Arc" => true,
+                _ => false,
+            };
+            path.segments.iter().any(|segment| match segment.params {
+                clean::PathParameters::AngleBracketed { ref types, .. } => {
+                    types.iter().any(|ty| {
+                        if pointer { behind_pointer(ty) } else { holds_self_by_value(ty) }
+                    })
+                }
+                clean::PathParameters::Parenthesized { .. } => false,
+            })
+        }
+        clean::Tuple(ref tys) => tys.iter().any(holds_self_by_value),
+        clean::Slice(ref ty) | clean::Array(ref ty, _) => holds_self_by_value(ty),
+        clean::Unique(ref ty) | clean::RawPointer(_, ref ty) => behind_pointer(ty),
+        clean::BorrowedRef { ref type_, .. } => behind_pointer(type_),
+        _ => false,
+    }
+}
+
+/// Replaces every occurrence of `Self` in `ty` by `dyn_self`.
+fn erase_self(ty: &clean::Type, dyn_self: &clean::Type) -> clean::Type {
+    match *ty {
+        clean::Generic(ref name) if name == "Self" => dyn_self.clone(),
+        clean::ResolvedPath { ref path, ref typarams, did, is_generic } => {
+            let mut path = path.clone();
+            for segment in &mut path.segments {
+                match segment.params {
+                    clean::PathParameters::AngleBracketed { ref mut types, .. } => {
+                        for ty in types.iter_mut() {
+                            *ty = erase_self(ty, dyn_self);
+                        }
+                    }
+                    clean::PathParameters::Parenthesized { ref mut inputs, ref mut output } => {
+                        for ty in inputs.iter_mut() {
+                            *ty = erase_self(ty, dyn_self);
+                        }
+                        if let Some(ref mut ty) = *output {
+                            *ty = erase_self(ty, dyn_self);
+                        }
+                    }
+                }
+            }
+            clean::ResolvedPath { path, typarams: typarams.clone(), did, is_generic }
+        }
+        clean::Tuple(ref tys) => {
+            clean::Tuple(tys.iter().map(|ty| erase_self(ty, dyn_self)).collect())
+        }
+        clean::Slice(ref ty) => clean::Slice(box erase_self(ty, dyn_self)),
+        clean::Array(ref ty, ref n) => clean::Array(box erase_self(ty, dyn_self), n.clone()),
+        clean::Unique(ref ty) => clean::Unique(box erase_self(ty, dyn_self)),
+        clean::RawPointer(mutability, ref ty) => {
+            clean::RawPointer(mutability, box erase_self(ty, dyn_self))
+        }
+        clean::BorrowedRef { ref lifetime, mutability, ref type_ } => {
+            clean::BorrowedRef {
+                lifetime: lifetime.clone(),
+                mutability,
+                type_: box erase_self(type_, dyn_self),
+            }
+        }
+        _ => ty.clone(),
+    }
+}
+
+/// Explains what makes the impl `impl_did` coherent with regard to the orphan
+/// rules: either its trait or its implementing type has to be defined in the
+/// same crate as the impl.
+fn coherence_note(impl_did: DefId, impl_: &clean::Impl) -> Option<&'static str> {
+    let is_local = |did: Option<DefId>| did.map_or(false, |did| did.krate == impl_did.krate);
+    if is_local(impl_.trait_.def_id()) {
+        Some("Permitted because the trait is defined in the implementing crate.")
+    } else if is_local(impl_.for_.def_id()) {
+        Some("Permitted because the type is defined in the implementing crate.")
+    } else {
+        None
+    }
+}
+
 fn implementor2item<'a>(cache: &'a Cache, imp : &Implementor) -> Option<&'a clean::Item> {
     if let Some(t_did) = imp.impl_.for_.def_id() {
         if let Some(impl_item) = cache.impls.get(&t_did).and_then(|i| i.iter()
@@ -2342,6 +4582,9 @@ fn implementor2item<'a>(cache: &'a Cache, imp : &Implementor) -> Option<&'a clea
     None
 }
 
+/// Length past which the supertrait bounds of a trait are rendered one per line.
+const TRAIT_HEADER_LEN: usize = 80;
+
 fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
               t: &clean::Trait) -> fmt::Result {
     let mut bounds = String::new();
@@ -2361,6 +4604,25 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
             bounds.push_str(&format!("{}", *p));
             bounds_plain.push_str(&format!("{:#}", *p));
         }
+
+        // If the bounds make the header too long, put each of them on its own
+        // line instead.
+        let header_plain = format!("{}{}trait {}{:#}{}",
+                                   VisSpace(&it.visibility),
+                                   UnsafetySpace(t.unsafety),
+                                   it.name.as_ref().unwrap(),
+                                   t.generics,
+                                   bounds_plain);
+        if header_plain.len() > TRAIT_HEADER_LEN {
+            bounds = String::from(":");
+            for (i, p) in t.bounds.iter().enumerate() {
+                bounds.push_str("<br>&nbsp;&nbsp;&nbsp;&nbsp;");
+                if i > 0 {
+                    bounds.push_str("+ ");
+                }
+                bounds.push_str(&format!("{}", *p));
+            }
+        }
     }
 
     // Output the trait definition
@@ -2374,7 +4636,12 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
            bounds)?;
 
     if !t.generics.where_predicates.is_empty() {
-        write!(w, "{}", WhereClause { gens: &t.generics, indent: 0, end_newline: true })?;
+        write!(w, "{}", WhereClause {
+            gens: &t.generics,
+            indent: 0,
+            end_newline: true,
+            group_bounds: cx.shared.options.group_where_bounds,
+        })?;
     } else {
         write!(w, " ")?;
     }
@@ -2436,11 +4703,31 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
     }
     write!(w, "</pre>")?;
 
+    // Summarize the shape of the trait
+    let summary = [(required.len(), "required method"),
+                   (provided.len(), "provided method"),
+                   (types.len(), "associated type"),
+                   (consts.len(), "associated constant")]
+        .iter()
+        .filter(|&&(count, _)| count > 0)
+        .map(|&(count, what)| format!("{} {}{}", count, what, if count == 1 { "" } else { "s" }))
+        .collect::<Vec<_>>();
+    if !summary.is_empty() {
+        write!(w, "<div class='trait-summary'>{}</div>", summary.join(", "))?;
+    }
+
     // Trait documentation
     document(w, cx, it)?;
+    if cx.shared.options.show_on_unimplemented {
+        if let Some(message) = on_unimplemented_message(it) {
+            write!(w, "<div class='stab on-unimplemented'>When this trait is not implemented, \
+                       the compiler reports: {}</div>", Escape(&message))?;
+        }
+    }
+    render_type_usage(w, cx, it)?;
 
-    fn trait_item(w: &mut fmt::Formatter, cx: &Context, m: &clean::Item, t: &clean::Item)
-                  -> fmt::Result {
+    fn trait_item(w: &mut fmt::Formatter, cx: &Context, m: &clean::Item, t: &clean::Item,
+                  dyn_self: Option<&clean::Type>) -> fmt::Result {
         let name = m.name.as_ref().unwrap();
         let item_type = m.type_();
         let id = derive_id(format!("{}.{}", item_type, name));
@@ -2452,21 +4739,48 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                ns_id = ns_id)?;
         render_assoc_item(w, m, AssocItemLink::Anchor(Some(&id)), ItemType::Impl)?;
         write!(w, "</code>")?;
-        render_stability_since(w, m, t)?;
+        render_stability_since(w, cx, m, t)?;
         write!(w, "</span></h3>")?;
+        if let Some(erased) = dyn_self.and_then(|dyn_self| dyn_erased_decl(m, dyn_self)) {
+            let name_len = format!("fn {}", name).len();
+            write!(w, "<div class='dyn-signature'>Through a trait object: \
+                       <code>fn {name}{decl}</code></div>",
+                   name = name,
+                   decl = Method { decl: &erased, name_len, indent: 0 })?;
+        }
         document(w, cx, m)?;
         Ok(())
     }
 
+    let dyn_self = if cx.shared.options.show_dyn_signatures {
+        let mut path = clean::Path::singleton(it.name.clone().unwrap());
+        path.segments[0].params = clean::PathParameters::AngleBracketed {
+            lifetimes: vec![],
+            types: t.generics.type_params.iter()
+                                         .map(|p| clean::Generic(p.name.clone()))
+                                         .collect(),
+            bindings: vec![],
+        };
+        Some(clean::ResolvedPath {
+            path,
+            typarams: None,
+            did: it.def_id,
+            is_generic: false,
+        })
+    } else {
+        None
+    };
+
     if !types.is_empty() {
         write!(w, "
             <h2 id='associated-types' class='small-section-header'>
-              Associated Types<a href='#associated-types' class='anchor'></a>
+              Associated Types
+              <a href='#associated-types' class='anchor' data-permalink='{permalink}'></a>
             </h2>
             <div class='methods'>
-        ")?;
+        ", permalink = cx.permalink(it, "associated-types"))?;
         for t in &types {
-            trait_item(w, cx, *t, it)?;
+            trait_item(w, cx, *t, it, None)?;
         }
         write!(w, "</div>")?;
     }
@@ -2474,12 +4788,13 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
     if !consts.is_empty() {
         write!(w, "
             <h2 id='associated-const' class='small-section-header'>
-              Associated Constants<a href='#associated-const' class='anchor'></a>
+              Associated Constants
+              <a href='#associated-const' class='anchor' data-permalink='{permalink}'></a>
             </h2>
             <div class='methods'>
-        ")?;
+        ", permalink = cx.permalink(it, "associated-const"))?;
         for t in &consts {
-            trait_item(w, cx, *t, it)?;
+            trait_item(w, cx, *t, it, None)?;
         }
         write!(w, "</div>")?;
     }
@@ -2488,24 +4803,26 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
     if !required.is_empty() {
         write!(w, "
             <h2 id='required-methods' class='small-section-header'>
-              Required Methods<a href='#required-methods' class='anchor'></a>
+              Required Methods
+              <a href='#required-methods' class='anchor' data-permalink='{permalink}'></a>
             </h2>
             <div class='methods'>
-        ")?;
+        ", permalink = cx.permalink(it, "required-methods"))?;
         for m in &required {
-            trait_item(w, cx, *m, it)?;
+            trait_item(w, cx, *m, it, dyn_self.as_ref())?;
         }
         write!(w, "</div>")?;
     }
     if !provided.is_empty() {
         write!(w, "
             <h2 id='provided-methods' class='small-section-header'>
-              Provided Methods<a href='#provided-methods' class='anchor'></a>
+              Provided Methods
+              <a href='#provided-methods' class='anchor' data-permalink='{permalink}'></a>
             </h2>
             <div class='methods'>
-        ")?;
+        ", permalink = cx.permalink(it, "provided-methods"))?;
         for m in &provided {
-            trait_item(w, cx, *m, it)?;
+            trait_item(w, cx, *m, it, dyn_self.as_ref())?;
         }
         write!(w, "</div>")?;
     }
@@ -2514,12 +4831,12 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
     render_assoc_items(w, cx, it, it.def_id, AssocItemRender::All)?;
 
     let cache = cache();
-    let impl_header = "
+    let impl_header = format!("
         <h2 id='implementors' class='small-section-header'>
-          Implementors<a href='#implementors' class='anchor'></a>
+          Implementors<a href='#implementors' class='anchor' data-permalink='{permalink}'></a>
         </h2>
         <ul class='item-list' id='implementors-list'>
-    ";
+    ", permalink = cx.permalink(it, "implementors"));
     if let Some(implementors) = cache.implementors.get(&it.def_id) {
         // The DefId is for the first Type found with that name. The bool is
         // if any Types with the same name but different DefId have been found.
@@ -2548,19 +4865,54 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
         if !foreign.is_empty() {
             write!(w, "
                 <h2 id='foreign-impls' class='small-section-header'>
-                  Implementations on Foreign Types<a href='#foreign-impls' class='anchor'></a>
+                  Implementations on Foreign Types
+                  <a href='#foreign-impls' class='anchor' data-permalink='{permalink}'></a>
                 </h2>
-            ")?;
+            ", permalink = cx.permalink(it, "foreign-impls"))?;
 
+            // Group the implementors by the crate defining the type they're
+            // for, sorting them by that type within each crate. The ones whose
+            // crate isn't known come first, without a header.
+            let mut by_crate = BTreeMap::new();
             for implementor in foreign {
-                if let Some(i) = implementor2item(&cache, implementor) {
-                    let impl_ = Impl { impl_item: i.clone() };
-                    let assoc_link = AssocItemLink::GotoSource(
-                        i.def_id, &implementor.impl_.provided_trait_methods
-                    );
-                    render_impl(w, cx, &impl_, assoc_link,
-                                RenderMode::Normal, i.stable_since(), false)?;
+                let krate = implementor.impl_.for_.def_id().and_then(|did| {
+                    if did.krate == LOCAL_CRATE {
+                        Some(&cx.shared.layout.krate[..])
+                    } else {
+                        cache.extern_locations.get(&did.krate).map(|&(ref name, ..)| &name[..])
+                    }
+                });
+                by_crate.entry(krate).or_insert_with(Vec::new).push(implementor);
+            }
+
+            for (krate, mut implementors) in by_crate {
+                implementors.sort_by_key(|i| format!("{:#}", i.impl_.for_));
+                if let Some(krate) = krate {
+                    let id = derive_id(format!("foreign-impls.{}", krate));
+                    write!(w, "<h3 id='{id}' class='foreign-impls-crate'>\
+                                 <code>{krate}</code>\
+                                 <a href='#{id}' class='anchor' data-permalink='{permalink}'>\
+                                 </a>\
+                               </h3>",
+                           id = id, krate = krate, permalink = cx.permalink(it, &id))?;
+                }
+                write!(w, "<div class='foreign-impls-group'>")?;
+                for implementor in implementors {
+                    if let Some(i) = implementor2item(&cache, implementor) {
+                        let impl_ = Impl { impl_item: i.clone() };
+                        let assoc_link = AssocItemLink::GotoSource(
+                            i.def_id, &implementor.impl_.provided_trait_methods
+                        );
+                        if cx.shared.options.show_coherence_notes {
+                            if let Some(note) = coherence_note(i.def_id, &implementor.impl_) {
+                                write!(w, "<div class='coherence-note'>{}</div>", note)?;
+                            }
+                        }
+                        render_impl(w, cx, it, &impl_, assoc_link,
+                                    RenderMode::Normal, i.stable_since(), false)?;
+                    }
                 }
+                write!(w, "</div>")?;
             }
         }
 
@@ -2569,7 +4921,7 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
         for implementor in local {
             write!(w, "<li>")?;
             if let Some(item) = implementor2item(&cache, implementor) {
-                if let Some(l) = (Item { cx, item }).src_href() {
+                if let Some(l) = (Item { cx, item, structured_data: None }).src_href() {
                     write!(w, "<div class='out-of-band'>")?;
                     write!(w, "<a class='srclink' href='{}' title='{}'>[src]</a>",
                                 l, "goto source code")?;
@@ -2667,8 +5019,12 @@ fn assoc_type<W: fmt::Write>(w: &mut W, it: &clean::Item,
 }
 
 fn render_stability_since_raw<'a>(w: &mut fmt::Formatter,
+                                  cx: &Context,
                                   ver: Option<&'a str>,
                                   containing_ver: Option<&'a str>) -> fmt::Result {
+    if cx.shared.options.hide_stable_since {
+        return Ok(());
+    }
     if let Some(v) = ver {
         if containing_ver != ver && v.len() > 0 {
             write!(w, "<div class='since' title='Stable since Rust version {0}'>{0}</div>",
@@ -2679,9 +5035,10 @@ fn render_stability_since_raw<'a>(w: &mut fmt::Formatter,
 }
 
 fn render_stability_since(w: &mut fmt::Formatter,
+                          cx: &Context,
                           item: &clean::Item,
                           containing_item: &clean::Item) -> fmt::Result {
-    render_stability_since_raw(w, item.stable_since(), containing_item.stable_since())
+    render_stability_since_raw(w, cx, item.stable_since(), containing_item.stable_since())
 }
 
 fn render_assoc_item(w: &mut fmt::Formatter,
@@ -2746,12 +5103,13 @@ fn render_assoc_item(w: &mut fmt::Formatter,
                    gens: g,
                    indent,
                    end_newline,
+                   group_bounds: false,
                })
     }
     match item.inner {
         clean::StrippedItem(..) => Ok(()),
         clean::TyMethodItem(ref m) => {
-            method(w, item, m.unsafety, hir::Constness::NotConst,
+            method(w, item, m.unsafety, m.constness,
                    m.abi, &m.generics, &m.decl, link, parent)
         }
         clean::MethodItem(ref m) => {
@@ -2768,6 +5126,23 @@ fn render_assoc_item(w: &mut fmt::Formatter,
     }
 }
 
+/// Renders how many functions of the crate mention the type `it` in their
+/// signature, linking to the corresponding search.
+fn render_type_usage(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item) -> fmt::Result {
+    if !cx.shared.options.show_type_usage {
+        return Ok(());
+    }
+    let name = it.name.as_ref().unwrap();
+    match cache().type_usage.get(&it.def_id) {
+        Some(&count) => {
+            write!(w, "<div class='type-usage'><a href='?search={}'>Used in {} function{}</a>\
+                       </div>",
+                   small_url_encode(name), count, if count == 1 { "" } else { "s" })
+        }
+        None => Ok(()),
+    }
+}
+
 fn item_struct(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                s: &clean::Struct) -> fmt::Result {
     write!(w, "<pre class='rust struct'>")?;
@@ -2778,43 +5153,63 @@ fn item_struct(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                   s.struct_type,
                   &s.fields,
                   "",
-                  true)?;
+                  true,
+                  cx.shared.options.group_where_bounds)?;
     write!(w, "</pre>")?;
+    if it.is_non_exhaustive() {
+        write!(w, "<div class='stab non-exhaustive'>This struct is marked as non-exhaustive: \
+                   more fields may be added in the future, so it can't be built with a struct \
+                   expression and patterns matching it require <code>..</code>.</div>")?;
+    }
 
     document(w, cx, it)?;
-    let mut fields = s.fields.iter().filter_map(|f| {
+    render_type_usage(w, cx, it)?;
+    // Tuple fields are named after their position, hidden ones included, like
+    // in the declaration.
+    let fields = s.fields.iter().enumerate().filter_map(|(i, f)| {
         match f.inner {
-            clean::StructFieldItem(ref ty) => Some((f, ty)),
+            clean::StructFieldItem(ref ty) if !f.is_doc_hidden() => {
+                let name = match s.struct_type {
+                    doctree::Tuple => i.to_string(),
+                    _ => f.name.clone().unwrap(),
+                };
+                Some((name, f, ty))
+            }
             _ => None,
         }
-    }).peekable();
-    if let doctree::Plain = s.struct_type {
-        if fields.peek().is_some() {
-            write!(w, "<h2 id='fields' class='fields small-section-header'>
-                       Fields<a href='#fields' class='anchor'></a></h2>")?;
-            for (field, ty) in fields {
-                let id = derive_id(format!("{}.{}",
-                                           ItemType::StructField,
-                                           field.name.as_ref().unwrap()));
-                let ns_id = derive_id(format!("{}.{}",
-                                              field.name.as_ref().unwrap(),
-                                              ItemType::StructField.name_space()));
-                write!(w, "<span id=\"{id}\" class=\"{item_type} small-section-header\">
-                           <a href=\"#{id}\" class=\"anchor field\"></a>
-                           <span id=\"{ns_id}\" class='invisible'>
-                           <code>{name}: {ty}</code>
-                           </span></span>",
-                       item_type = ItemType::StructField,
-                       id = id,
-                       ns_id = ns_id,
-                       name = field.name.as_ref().unwrap(),
-                       ty = ty)?;
-                if let Some(stability_class) = field.stability_class() {
-                    write!(w, "<span class='stab {stab}'></span>",
-                        stab = stability_class)?;
-                }
-                document(w, cx, field)?;
+    }).collect::<Vec<_>>();
+    // Tuple fields only get a section if they have something to say.
+    let title = match s.struct_type {
+        doctree::Plain if !fields.is_empty() => Some("Fields"),
+        doctree::Tuple if fields.iter().any(|&(_, f, _)| f.doc_value().is_some()) => {
+            Some("Tuple Fields")
+        }
+        _ => None,
+    };
+    if let Some(title) = title {
+        write!(w, "<h2 id='fields' class='fields small-section-header'>
+                   {title}
+                   <a href='#fields' class='anchor' data-permalink='{permalink}'></a></h2>",
+                   title = title,
+                   permalink = cx.permalink(it, "fields"))?;
+        for (name, field, ty) in fields {
+            let id = derive_id(format!("{}.{}", ItemType::StructField, name));
+            let ns_id = derive_id(format!("{}.{}", name, ItemType::StructField.name_space()));
+            write!(w, "<span id=\"{id}\" class=\"{item_type} small-section-header\">
+                       <a href=\"#{id}\" class=\"anchor field\"></a>
+                       <span id=\"{ns_id}\" class='invisible'>
+                       <code>{name}: {ty}</code>
+                       </span></span>",
+                   item_type = ItemType::StructField,
+                   id = id,
+                   ns_id = ns_id,
+                   name = name,
+                   ty = ty)?;
+            if let Some(stability_class) = field.stability_class() {
+                write!(w, "<span class='stab {stab}'></span>",
+                    stab = stability_class)?;
             }
+            document(w, cx, field)?;
         }
     }
     render_assoc_items(w, cx, it, it.def_id, AssocItemRender::All)
@@ -2829,10 +5224,12 @@ fn item_union(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                  Some(&s.generics),
                  &s.fields,
                  "",
-                 true)?;
+                 true,
+                 cx.shared.options.group_where_bounds)?;
     write!(w, "</pre>")?;
 
     document(w, cx, it)?;
+    render_type_usage(w, cx, it)?;
     let mut fields = s.fields.iter().filter_map(|f| {
         match f.inner {
             clean::StructFieldItem(ref ty) => Some((f, ty)),
@@ -2841,7 +5238,9 @@ fn item_union(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
     }).peekable();
     if fields.peek().is_some() {
         write!(w, "<h2 id='fields' class='fields small-section-header'>
-                   Fields<a href='#fields' class='anchor'></a></h2>")?;
+                   Fields
+                   <a href='#fields' class='anchor' data-permalink='{permalink}'></a></h2>",
+                   permalink = cx.permalink(it, "fields"))?;
         for (field, ty) in fields {
             write!(w, "<span id='{shortty}.{name}' class=\"{shortty}\"><code>{name}: {ty}</code>
                        </span>",
@@ -2866,12 +5265,19 @@ fn item_enum(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
            VisSpace(&it.visibility),
            it.name.as_ref().unwrap(),
            e.generics,
-           WhereClause { gens: &e.generics, indent: 0, end_newline: true })?;
-    if e.variants.is_empty() && !e.variants_stripped {
+           WhereClause {
+               gens: &e.generics,
+               indent: 0,
+               end_newline: true,
+               group_bounds: cx.shared.options.group_where_bounds,
+           })?;
+    let variants = e.variants.iter().filter(|v| !v.is_doc_hidden()).collect::<Vec<_>>();
+    let variants_stripped = e.variants_stripped || variants.len() < e.variants.len();
+    if variants.is_empty() && !variants_stripped {
         write!(w, " {{}}")?;
     } else {
         write!(w, " {{\n")?;
-        for v in &e.variants {
+        for v in &variants {
             write!(w, "    ")?;
             let name = v.name.as_ref().unwrap();
             match v.inner {
@@ -2880,11 +5286,22 @@ fn item_enum(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                         clean::VariantKind::CLike => write!(w, "{}", name)?,
                         clean::VariantKind::Tuple(ref tys) => {
                             write!(w, "{}(", name)?;
-                            for (i, ty) in tys.iter().enumerate() {
-                                if i > 0 {
-                                    write!(w, ",&nbsp;")?
+                            // Like function arguments, the fields go on their own lines when
+                            // the variant would not fit in 80 columns.
+                            let plain = tys.iter().map(|ty| format!("{:#}", ty))
+                                           .collect::<Vec<_>>().join(", ");
+                            if format!("    {}({}),", name, plain).len() > 80 {
+                                for ty in tys {
+                                    write!(w, "\n        {},", *ty)?;
+                                }
+                                write!(w, "\n    ")?;
+                            } else {
+                                for (i, ty) in tys.iter().enumerate() {
+                                    if i > 0 {
+                                        write!(w, ",&nbsp;")?
+                                    }
+                                    write!(w, "{}", *ty)?;
                                 }
-                                write!(w, "{}", *ty)?;
                             }
                             write!(w, ")")?;
                         }
@@ -2895,7 +5312,8 @@ fn item_enum(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                                           s.struct_type,
                                           &s.fields,
                                           "    ",
-                                          false)?;
+                                          false,
+                                          cx.shared.options.group_where_bounds)?;
                         }
                     }
                 }
@@ -2904,18 +5322,26 @@ fn item_enum(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
             write!(w, ",\n")?;
         }
 
-        if e.variants_stripped {
-            write!(w, "    // some variants omitted\n")?;
+        if let Some(note) = omitted_note("variants", variants_stripped, it.is_non_exhaustive()) {
+            write!(w, "    // {}\n", note)?;
         }
         write!(w, "}}")?;
     }
     write!(w, "</pre>")?;
+    if it.is_non_exhaustive() {
+        write!(w, "<div class='stab non-exhaustive'>This enum is marked as non-exhaustive: \
+                   more variants may be added in the future, so matching on it requires a \
+                   wildcard <code>_</code> arm.</div>")?;
+    }
 
     document(w, cx, it)?;
-    if !e.variants.is_empty() {
+    render_type_usage(w, cx, it)?;
+    if !variants.is_empty() {
         write!(w, "<h2 id='variants' class='variants small-section-header'>
-                   Variants<a href='#variants' class='anchor'></a></h2>\n")?;
-        for variant in &e.variants {
+                   Variants
+                   <a href='#variants' class='anchor' data-permalink='{permalink}'></a></h2>\n",
+                   permalink = cx.permalink(it, "variants"))?;
+        for variant in &variants {
             let id = derive_id(format!("{}.{}",
                                        ItemType::Variant,
                                        variant.name.as_ref().unwrap()));
@@ -2954,7 +5380,7 @@ fn item_enum(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                        id = variant_id)?;
                 write!(w, "<h3 class='fields'>Fields of <code>{name}</code></h3>\n
                            <table>", name = variant.name.as_ref().unwrap())?;
-                for field in &s.fields {
+                for field in s.fields.iter().filter(|f| !f.is_doc_hidden()) {
                     use clean::StructFieldItem;
                     if let StructFieldItem(ref ty) = field.inner {
                         let id = derive_id(format!("variant.{}.field.{}",
@@ -2979,7 +5405,7 @@ fn item_enum(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                 }
                 write!(w, "</table></span>")?;
             }
-            render_stability_since(w, variant, it)?;
+            render_stability_since(w, cx, variant, it)?;
         }
     }
     render_assoc_items(w, cx, it, it.def_id, AssocItemRender::All)?;
@@ -3027,7 +5453,7 @@ fn render_attributes(w: &mut fmt::Formatter, it: &clean::Item) -> fmt::Result {
             continue;
         }
         if let Some(s) = render_attribute(&attr.meta().unwrap()) {
-            attrs.push_str(&format!("#[{}]\n", s));
+            attrs.push_str(&format!("#[{}]\n", Escape(&s)));
         }
     }
     if attrs.len() > 0 {
@@ -3036,12 +5462,25 @@ fn render_attributes(w: &mut fmt::Formatter, it: &clean::Item) -> fmt::Result {
     Ok(())
 }
 
+/// Returns the note telling that some of the `kind` (fields or variants) of a
+/// type aren't shown, because they are hidden or because more of them may be
+/// added through `#[non_exhaustive]`.
+fn omitted_note(kind: &str, stripped: bool, non_exhaustive: bool) -> Option<String> {
+    match (stripped, non_exhaustive) {
+        (true, true) => Some(format!("some {} omitted, more may be added", kind)),
+        (true, false) => Some(format!("some {} omitted", kind)),
+        (false, true) => Some(format!("more {} may be added", kind)),
+        (false, false) => None,
+    }
+}
+
 fn render_struct(w: &mut fmt::Formatter, it: &clean::Item,
                  g: Option<&clean::Generics>,
                  ty: doctree::StructType,
                  fields: &[clean::Item],
                  tab: &str,
-                 structhead: bool) -> fmt::Result {
+                 structhead: bool,
+                 group_bounds: bool) -> fmt::Result {
     write!(w, "{}{}{}",
            VisSpace(&it.visibility),
            if structhead {"struct "} else {""},
@@ -3052,11 +5491,16 @@ fn render_struct(w: &mut fmt::Formatter, it: &clean::Item,
     match ty {
         doctree::Plain => {
             if let Some(g) = g {
-                write!(w, "{}", WhereClause { gens: g, indent: 0, end_newline: true })?
+                write!(w, "{}", WhereClause {
+                    gens: g,
+                    indent: 0,
+                    end_newline: true,
+                    group_bounds,
+                })?
             }
             let mut has_visible_fields = false;
             write!(w, " {{")?;
-            for field in fields {
+            for field in fields.iter().filter(|f| !f.is_doc_hidden()) {
                 if let clean::StructFieldItem(ref ty) = field.inner {
                     write!(w, "\n{}    {}{}: {},",
                            tab,
@@ -3067,15 +5511,18 @@ fn render_struct(w: &mut fmt::Formatter, it: &clean::Item,
                 }
             }
 
+            let stripped = it.has_stripped_fields().unwrap() ||
+                           fields.iter().any(|f| f.is_doc_hidden());
+            let omitted = omitted_note("fields", stripped, it.is_non_exhaustive());
             if has_visible_fields {
-                if it.has_stripped_fields().unwrap() {
-                    write!(w, "\n{}    // some fields omitted", tab)?;
+                if let Some(note) = omitted {
+                    write!(w, "\n{}    // {}", tab, note)?;
                 }
                 write!(w, "\n{}", tab)?;
-            } else if it.has_stripped_fields().unwrap() {
+            } else if let Some(note) = omitted {
                 // If there are no visible fields we can just display
                 // `{ /* fields omitted */ }` to save space.
-                write!(w, " /* fields omitted */ ")?;
+                write!(w, " /* {} */ ", note)?;
             }
             write!(w, "}}")?;
         }
@@ -3089,6 +5536,9 @@ fn render_struct(w: &mut fmt::Formatter, it: &clean::Item,
                     clean::StrippedItem(box clean::StructFieldItem(..)) => {
                         write!(w, "_")?
                     }
+                    clean::StructFieldItem(..) if field.is_doc_hidden() => {
+                        write!(w, "_")?
+                    }
                     clean::StructFieldItem(ref ty) => {
                         write!(w, "{}{}", VisSpace(&field.visibility), *ty)?
                     }
@@ -3097,14 +5547,24 @@ fn render_struct(w: &mut fmt::Formatter, it: &clean::Item,
             }
             write!(w, ")")?;
             if let Some(g) = g {
-                write!(w, "{}", WhereClause { gens: g, indent: 0, end_newline: false })?
+                write!(w, "{}", WhereClause {
+                    gens: g,
+                    indent: 0,
+                    end_newline: false,
+                    group_bounds,
+                })?
             }
             write!(w, ";")?;
         }
         doctree::Unit => {
             // Needed for PhantomData.
             if let Some(g) = g {
-                write!(w, "{}", WhereClause { gens: g, indent: 0, end_newline: false })?
+                write!(w, "{}", WhereClause {
+                    gens: g,
+                    indent: 0,
+                    end_newline: false,
+                    group_bounds,
+                })?
             }
             write!(w, ";")?;
         }
@@ -3116,14 +5576,20 @@ fn render_union(w: &mut fmt::Formatter, it: &clean::Item,
                 g: Option<&clean::Generics>,
                 fields: &[clean::Item],
                 tab: &str,
-                structhead: bool) -> fmt::Result {
+                structhead: bool,
+                group_bounds: bool) -> fmt::Result {
     write!(w, "{}{}{}",
            VisSpace(&it.visibility),
            if structhead {"union "} else {""},
            it.name.as_ref().unwrap())?;
     if let Some(g) = g {
         write!(w, "{}", g)?;
-        write!(w, "{}", WhereClause { gens: g, indent: 0, end_newline: true })?;
+        write!(w, "{}", WhereClause {
+            gens: g,
+            indent: 0,
+            end_newline: true,
+            group_bounds,
+        })?;
     }
 
     write!(w, " {{\n{}", tab)?;
@@ -3180,30 +5646,62 @@ fn render_assoc_items(w: &mut fmt::Formatter,
         Some(v) => v,
         None => return Ok(()),
     };
-    let (non_trait, traits): (Vec<_>, _) = v.iter().partition(|i| {
+    let (non_trait, mut traits): (Vec<_>, Vec<_>) = v.iter().partition(|i| {
         i.inner_impl().trait_.is_none()
     });
+    if cx.shared.options.sort_impls {
+        // `sort_by_key` is stable, so impls of the same trait keep their order.
+        traits.sort_by_key(|i| format!("{:#}", i.inner_impl().trait_.as_ref().unwrap()));
+    }
+    if let AssocItemRender::All = what {
+        if cx.shared.options.assoc_consts_section {
+            let consts = non_trait.iter()
+                                  .flat_map(|i| i.inner_impl().items.iter())
+                                  .filter(|item| item.type_() == ItemType::AssociatedConst)
+                                  .collect::<Vec<_>>();
+            if !consts.is_empty() {
+                write!(w, "
+                    <h2 id='associated-consts' class='small-section-header'>
+                      Associated Constants
+                      <a href='#associated-consts' class='anchor' data-permalink='{permalink}'></a>
+                    </h2>
+                    <div class='methods'>
+                ", permalink = cx.permalink(containing_item, "associated-consts"))?;
+                for item in consts {
+                    render_inherent_assoc_const(w, cx, item, containing_item.stable_since())?;
+                }
+                write!(w, "</div>")?;
+            }
+        }
+    }
     if !non_trait.is_empty() {
         let render_mode = match what {
             AssocItemRender::All => {
                 write!(w, "
                     <h2 id='methods' class='small-section-header'>
-                      Methods<a href='#methods' class='anchor'></a>
+                      Methods<a href='#methods' class='anchor' data-permalink='{permalink}'></a>
                     </h2>
-                ")?;
+                ", permalink = cx.permalink(containing_item, "methods"))?;
                 RenderMode::Normal
             }
             AssocItemRender::DerefFor { trait_, type_, deref_mut_ } => {
                 write!(w, "
                     <h2 id='deref-methods' class='small-section-header'>
-                      Methods from {}&lt;Target = {}&gt;<a href='#deref-methods' class='anchor'></a>
+                      Methods from {}&lt;Target = {}&gt;
+                      <a href='#deref-methods' class='anchor' data-permalink='{permalink}'></a>
                     </h2>
-                ", trait_, type_)?;
+                ", trait_, type_, permalink = cx.permalink(containing_item, "deref-methods"))?;
                 RenderMode::ForDeref { mut_: deref_mut_ }
             }
         };
         for i in &non_trait {
-            render_impl(w, cx, i, AssocItemLink::Anchor(None), render_mode,
+            if cx.shared.options.show_impl_source_files {
+                if let Some(location) = impl_source_location(cx, &i.impl_item) {
+                    write!(w, "<div class='impl-location'>Defined in <code>{}</code></div>",
+                           Escape(&location))?;
+                }
+            }
+            render_impl(w, cx, containing_item, i, AssocItemLink::Anchor(None), render_mode,
                         containing_item.stable_since(), true)?;
         }
     }
@@ -3220,21 +5718,82 @@ fn render_assoc_items(w: &mut fmt::Formatter,
             }).is_some();
             render_deref_methods(w, cx, impl_, containing_item, has_deref_mut)?;
         }
-        write!(w, "
-            <h2 id='implementations' class='small-section-header'>
-              Trait Implementations<a href='#implementations' class='anchor'></a>
-            </h2>
-        ")?;
-        for i in &traits {
-            let did = i.trait_did().unwrap();
-            let assoc_link = AssocItemLink::GotoSource(did, &i.inner_impl().provided_trait_methods);
-            render_impl(w, cx, i, assoc_link,
-                        RenderMode::Normal, containing_item.stable_since(), true)?;
+        let (marker, traits): (Vec<_>, Vec<_>) = traits.into_iter().partition(|i| {
+            i.trait_did().map_or(false, |did| c.marker_trait_dids.contains(&did))
+        });
+        let (blanket, traits): (Vec<_>, Vec<_>) = traits.into_iter().partition(|i| {
+            i.inner_impl().for_.is_generic()
+        });
+        if !traits.is_empty() {
+            write!(w, "
+                <h2 id='implementations' class='small-section-header'>
+                  Trait Implementations
+                  <a href='#implementations' class='anchor' data-permalink='{permalink}'></a>
+                </h2>
+            ", permalink = cx.permalink(containing_item, "implementations"))?;
+            render_trait_impls(w, cx, containing_item, &traits)?;
+        }
+        if !marker.is_empty() {
+            write!(w, "
+                <h2 id='synthetic-implementations' class='small-section-header'>
+                  Auto Trait Implementations
+                  <a href='#synthetic-implementations' class='anchor'
+                     data-permalink='{permalink}'></a>
+                </h2>
+                <div class='synthetic-implementations collapsed'>
+            ", permalink = cx.permalink(containing_item, "synthetic-implementations"))?;
+            render_trait_impls(w, cx, containing_item, &marker)?;
+            write!(w, "</div>")?;
         }
+        if !blanket.is_empty() {
+            write!(w, "
+                <h2 id='blanket-implementations' class='small-section-header'>
+                  Blanket Implementations
+                  <a href='#blanket-implementations' class='anchor'
+                     data-permalink='{permalink}'></a>
+                </h2>
+                <div class='blanket-implementations collapsed'>
+            ", permalink = cx.permalink(containing_item, "blanket-implementations"))?;
+            render_trait_impls(w, cx, containing_item, &blanket)?;
+            write!(w, "</div>")?;
+        }
+    }
+    Ok(())
+}
+
+fn render_trait_impls(w: &mut fmt::Formatter, cx: &Context, containing_item: &clean::Item,
+                      traits: &[&Impl]) -> fmt::Result {
+    for i in traits {
+        let did = i.trait_did().unwrap();
+        let assoc_link = AssocItemLink::GotoSource(did, &i.inner_impl().provided_trait_methods);
+        render_impl(w, cx, containing_item, i, assoc_link,
+                    RenderMode::Normal, containing_item.stable_since(), true)?;
     }
     Ok(())
 }
 
+/// Renders an inherent associated constant outside of its impl block.
+fn render_inherent_assoc_const(w: &mut fmt::Formatter, cx: &Context, item: &clean::Item,
+                               outer_version: Option<&str>) -> fmt::Result {
+    let (ty, default) = match item.inner {
+        clean::AssociatedConstItem(ref ty, ref default) => (ty, default),
+        _ => return Ok(()),
+    };
+    let item_type = item.type_();
+    let name = item.name.as_ref().unwrap();
+    let id = derive_id(format!("{}.{}", item_type, name));
+    let ns_id = derive_id(format!("{}.{}", name, item_type.name_space()));
+    write!(w, "<h4 id='{}' class=\"{}\">", id, item_type)?;
+    write!(w, "<span id='{}' class='invisible'><code>", ns_id)?;
+    assoc_const(w, item, ty, default.as_ref(), AssocItemLink::Anchor(Some(&id)))?;
+    write!(w, "</code></span>")?;
+    render_stability_since_raw(w, cx, item.stable_since(), outer_version)?;
+    write!(w, "</h4>\n")?;
+    let prefix = render_assoc_const_value(cx, item);
+    document_stability(w, cx, item)?;
+    document_full(w, item, cx, &prefix)
+}
+
 fn render_deref_methods(w: &mut fmt::Formatter, cx: &Context, impl_: &Impl,
                         container_item: &clean::Item, deref_mut: bool) -> fmt::Result {
     let deref_type = impl_.inner_impl().trait_.as_ref().unwrap();
@@ -3247,6 +5806,11 @@ fn render_deref_methods(w: &mut fmt::Formatter, cx: &Context, impl_: &Impl,
     let what = AssocItemRender::DerefFor { trait_: deref_type, type_: target,
                                            deref_mut_: deref_mut };
     if let Some(did) = target.def_id() {
+        // A type dereferencing to itself has no methods to gain from it, and
+        // would only see its own listed a second time.
+        if did == container_item.def_id {
+            return Ok(());
+        }
         render_assoc_items(w, cx, container_item, did, what)
     } else {
         if let Some(prim) = target.primitive_type() {
@@ -3302,7 +5866,8 @@ fn render_spotlight_traits(item: &clean::Item) -> Result<String, fmt::Error> {
 
 fn spotlight_decl(decl: &clean::FnDecl) -> Result<String, fmt::Error> {
     let mut out = String::new();
-    let mut trait_ = String::new();
+    let mut for_ = String::new();
+    let mut traits = vec![];
 
     if let Some(did) = decl.output.def_id() {
         let c = cache();
@@ -3316,9 +5881,13 @@ fn spotlight_decl(decl: &clean::FnDecl) -> Result<String, fmt::Error> {
                             &format!("<h3 class=\"important\">Important traits for {}</h3>\
                                       <code class=\"content\">",
                                      impl_.for_));
-                        trait_.push_str(&format!("{}", impl_.for_));
+                        for_.push_str(&format!("{}", impl_.for_));
                     }
+                    traits.push(format!("{}", impl_.trait_.as_ref().unwrap()));
 
+                    // Each impl gets its own block, so that the associated types
+                    // listed below it aren't mistaken for those of another trait.
+                    out.push_str("<span class=\"important-impl\">");
                     //use the "where" class here to make it small
                     out.push_str(&format!("<span class=\"where fmt-newline\">{}</span>", impl_));
                     let t_did = impl_.trait_.def_id().unwrap();
@@ -3331,6 +5900,7 @@ fn spotlight_decl(decl: &clean::FnDecl) -> Result<String, fmt::Error> {
                             out.push_str(";</span>");
                         }
                     }
+                    out.push_str("</span>");
                 }
             }
         }
@@ -3338,38 +5908,116 @@ fn spotlight_decl(decl: &clean::FnDecl) -> Result<String, fmt::Error> {
 
     if !out.is_empty() {
         out.insert_str(0, &format!("<div class=\"important-traits\"><div class='tooltip'>ⓘ\
-                                    <span class='tooltiptext'>Important traits for {}</span></div>\
-                                    <div class=\"content hidden\">",
-                                   trait_));
+                                    <span class='tooltiptext'>Important traits for {}: {}</span>\
+                                    </div><div class=\"content hidden\">",
+                                   for_, traits.join(", ")));
         out.push_str("</code></div></div>");
     }
 
     Ok(out)
 }
 
-fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLink,
+/// How a method lends itself to method chaining.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Chaining {
+    /// The method returns the type it is called on.
+    Chainable,
+    /// The method returns the type it is called on, wrapped in a `Result`.
+    Fallible,
+}
+
+/// Returns how the method declared by `decl` on the type `container` can be
+/// chained, if at all.
+fn method_chaining(decl: &clean::FnDecl, container: Option<DefId>) -> Option<Chaining> {
+    fn is_container(ty: &clean::Type, container: Option<DefId>) -> bool {
+        match *ty {
+            clean::BorrowedRef { ref type_, .. } => is_container(type_, container),
+            _ => ty.is_self_type() || (container.is_some() && ty.def_id() == container),
+        }
+    }
+
+    if decl.self_type().is_none() {
+        return None;
+    }
+    let ty = match decl.output {
+        clean::Return(ref ty) => ty,
+        clean::DefaultReturn => return None,
+    };
+    if is_container(ty, container) {
+        return Some(Chaining::Chainable);
+    }
+    match *ty {
+        clean::ResolvedPath { ref path, .. } if path.last_name() == "Result" => {
+            match ty.generics().and_then(|types| types.first()) {
+                Some(ok) if is_container(ok, container) => Some(Chaining::Fallible),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns a short hash of the header of the given impl, which stays the same
+/// no matter where the impl ends up being rendered.
+fn impl_id_hash(i: &clean::Impl) -> String {
+    format!("{:08x}", fnv_hash(format!("{:#}", i).as_bytes()) as u32)
+}
+
+/// Hashes `bytes` with 64-bit FNV-1a. Unlike the ones of the standard library's hashers, its
+/// results are the same for every build of rustdoc, so they can end up in the output.
+fn fnv_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| (hash ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Derives the id of an item rendered in an impl, appending the given suffix
+/// to the candidate id first if there is one.
+fn impl_item_id(candidate: String, suffix: Option<&str>) -> String {
+    match suffix {
+        Some(suffix) => derive_id(format!("{}-{}", candidate, suffix)),
+        None => derive_id(candidate),
+    }
+}
+
+fn render_impl(w: &mut fmt::Formatter, cx: &Context, containing_item: &clean::Item,
+               i: &Impl, link: AssocItemLink,
                render_mode: RenderMode, outer_version: Option<&str>,
                show_def_docs: bool) -> fmt::Result {
+    let collapsed = if cx.shared.options.collapse_impls && render_mode == RenderMode::Normal {
+        " collapsed"
+    } else {
+        ""
+    };
     if render_mode == RenderMode::Normal {
         let id = derive_id(match i.inner_impl().trait_ {
             Some(ref t) => format!("impl-{}", small_url_encode(&format!("{:#}", t))),
             None => "impl".to_string(),
         });
-        write!(w, "<h3 id='{}' class='impl'><span class='in-band'><code>{}</code>",
-               id, i.inner_impl())?;
-        write!(w, "<a href='#{}' class='anchor'></a>", id)?;
+        let is_unsafe = i.inner_impl().unsafety == hir::Unsafety::Unsafe;
+        write!(w, "<h3 id='{}' class='impl{}{}'><span class='in-band'><code>{}</code>",
+               id, if is_unsafe { " impl-unsafe" } else { "" }, collapsed, i.inner_impl())?;
+        if is_unsafe {
+            write!(w, "<span class='unsafe-impl-badge' title='unsafe trait implementation'>\
+                       ⚠ unsafe impl</span>")?;
+        }
+        write!(w, "<a href='#{}' class='anchor' data-permalink='{}'></a>",
+               id, cx.permalink(containing_item, &id))?;
         write!(w, "</span><span class='out-of-band'>")?;
         let since = i.impl_item.stability.as_ref().map(|s| &s.since[..]);
-        if let Some(l) = (Item { item: &i.impl_item, cx: cx }).src_href() {
+        if let Some(l) = (Item { item: &i.impl_item, cx: cx, structured_data: None }).src_href() {
             write!(w, "<div class='ghost'></div>")?;
-            render_stability_since_raw(w, since, outer_version)?;
+            render_stability_since_raw(w, cx, since, outer_version)?;
             write!(w, "<a class='srclink' href='{}' title='{}'>[src]</a>",
                    l, "goto source code")?;
         } else {
-            render_stability_since_raw(w, since, outer_version)?;
+            render_stability_since_raw(w, cx, since, outer_version)?;
         }
         write!(w, "</span>")?;
         write!(w, "</h3>\n")?;
+        // Blanket impls aren't written for the type whose page they're shown on.
+        if i.inner_impl().for_.is_generic() {
+            write!(w, "<div class='blanket-note'>(via blanket implementation <code>{}</code>)\
+                       </div>", i.inner_impl())?;
+        }
         if let Some(ref dox) = cx.shared.maybe_collapsed_doc_value(&i.impl_item) {
             write!(w, "<div class='docblock'>{}</div>", Markdown(&*dox, cx.render_type))?;
         }
@@ -3378,7 +6026,8 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
     fn doc_impl_item(w: &mut fmt::Formatter, cx: &Context, item: &clean::Item,
                      link: AssocItemLink, render_mode: RenderMode,
                      is_default_item: bool, outer_version: Option<&str>,
-                     trait_: Option<&clean::Trait>, show_def_docs: bool) -> fmt::Result {
+                     trait_: Option<&clean::Trait>, show_def_docs: bool,
+                     container: Option<DefId>, id_suffix: Option<&str>) -> fmt::Result {
         let item_type = item.type_();
         let name = item.name.as_ref().unwrap();
 
@@ -3386,62 +6035,87 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
             RenderMode::Normal => true,
             RenderMode::ForDeref { mut_: deref_mut_ } => should_render_item(&item, deref_mut_),
         };
+        // Items which aren't overridden by the impl are marked as coming from
+        // the trait.
+        let (class, from_default) = if is_default_item {
+            (format!("{} from-default", item_type),
+             "<span class='from-default' title='This item is provided by a default \
+              implementation in the trait'>provided</span>")
+        } else {
+            (item_type.to_string(), "")
+        };
 
         match item.inner {
             clean::MethodItem(clean::Method { ref decl, .. }) |
             clean::TyMethodItem(clean::TyMethod{ ref decl, .. }) => {
                 // Only render when the method is not static or we allow static methods
                 if render_method_item {
-                    let id = derive_id(format!("{}.{}", item_type, name));
-                    let ns_id = derive_id(format!("{}.{}", name, item_type.name_space()));
-                    write!(w, "<h4 id='{}' class=\"{}\">", id, item_type)?;
+                    let id = impl_item_id(format!("{}.{}", item_type, name), id_suffix);
+                    let ns_id = impl_item_id(format!("{}.{}", name, item_type.name_space()),
+                                             id_suffix);
+                    write!(w, "<h4 id='{}' class=\"{}\">", id, class)?;
                     write!(w, "{}", spotlight_decl(decl)?)?;
                     write!(w, "<span id='{}' class='invisible'>", ns_id)?;
                     write!(w, "<code>")?;
                     render_assoc_item(w, item, link.anchor(&id), ItemType::Impl)?;
                     write!(w, "</code>")?;
-                    if let Some(l) = (Item { cx, item }).src_href() {
+                    if cx.shared.options.show_chainable_methods {
+                        match method_chaining(decl, container) {
+                            Some(Chaining::Chainable) => {
+                                write!(w, "<span class='chainable' title='This method returns \
+                                           the type it is called on'>chainable</span>")?;
+                            }
+                            Some(Chaining::Fallible) => {
+                                write!(w, "<span class='chainable' title='This method returns \
+                                           the type it is called on, wrapped in a \
+                                           Result'>chainable?</span>")?;
+                            }
+                            None => {}
+                        }
+                    }
+                    write!(w, "{}", from_default)?;
+                    if let Some(l) = (Item { cx, item, structured_data: None }).src_href() {
                         write!(w, "</span><span class='out-of-band'>")?;
                         write!(w, "<div class='ghost'></div>")?;
-                        render_stability_since_raw(w, item.stable_since(), outer_version)?;
+                        render_stability_since_raw(w, cx, item.stable_since(), outer_version)?;
                         write!(w, "<a class='srclink' href='{}' title='{}'>[src]</a>",
                                l, "goto source code")?;
                     } else {
-                        render_stability_since_raw(w, item.stable_since(), outer_version)?;
+                        render_stability_since_raw(w, cx, item.stable_since(), outer_version)?;
                     }
                     write!(w, "</span></h4>\n")?;
                 }
             }
             clean::TypedefItem(ref tydef, _) => {
-                let id = derive_id(format!("{}.{}", ItemType::AssociatedType, name));
-                let ns_id = derive_id(format!("{}.{}", name, item_type.name_space()));
-                write!(w, "<h4 id='{}' class=\"{}\">", id, item_type)?;
+                let id = impl_item_id(format!("{}.{}", ItemType::AssociatedType, name), id_suffix);
+                let ns_id = impl_item_id(format!("{}.{}", name, item_type.name_space()), id_suffix);
+                write!(w, "<h4 id='{}' class=\"{}\">", id, class)?;
                 write!(w, "<span id='{}' class='invisible'><code>", ns_id)?;
                 assoc_type(w, item, &Vec::new(), Some(&tydef.type_), link.anchor(&id))?;
-                write!(w, "</code></span></h4>\n")?;
+                write!(w, "</code>{}</span></h4>\n", from_default)?;
             }
             clean::AssociatedConstItem(ref ty, ref default) => {
-                let id = derive_id(format!("{}.{}", item_type, name));
-                let ns_id = derive_id(format!("{}.{}", name, item_type.name_space()));
-                write!(w, "<h4 id='{}' class=\"{}\">", id, item_type)?;
+                let id = impl_item_id(format!("{}.{}", item_type, name), id_suffix);
+                let ns_id = impl_item_id(format!("{}.{}", name, item_type.name_space()), id_suffix);
+                write!(w, "<h4 id='{}' class=\"{}\">", id, class)?;
                 write!(w, "<span id='{}' class='invisible'><code>", ns_id)?;
                 assoc_const(w, item, ty, default.as_ref(), link.anchor(&id))?;
-                write!(w, "</code></span></h4>\n")?;
+                write!(w, "</code>{}</span></h4>\n", from_default)?;
             }
             clean::AssociatedTypeItem(ref bounds, ref default) => {
-                let id = derive_id(format!("{}.{}", item_type, name));
-                let ns_id = derive_id(format!("{}.{}", name, item_type.name_space()));
-                write!(w, "<h4 id='{}' class=\"{}\">", id, item_type)?;
+                let id = impl_item_id(format!("{}.{}", item_type, name), id_suffix);
+                let ns_id = impl_item_id(format!("{}.{}", name, item_type.name_space()), id_suffix);
+                write!(w, "<h4 id='{}' class=\"{}\">", id, class)?;
                 write!(w, "<span id='{}' class='invisible'><code>", ns_id)?;
                 assoc_type(w, item, bounds, default.as_ref(), link.anchor(&id))?;
-                write!(w, "</code></span></h4>\n")?;
+                write!(w, "</code>{}</span></h4>\n", from_default)?;
             }
             clean::StrippedItem(..) => return Ok(()),
             _ => panic!("can't make docs for trait item with name {:?}", item.name)
         }
 
         if render_method_item || render_mode == RenderMode::Normal {
-            let prefix = render_assoc_const_value(item);
+            let prefix = render_assoc_const_value(cx, item);
 
             if !is_default_item {
                 if let Some(t) = trait_ {
@@ -3482,10 +6156,28 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
         write!(w, "<span class='docblock autohide'>")?;
     }
 
-    write!(w, "<div class='impl-items'>")?;
+    // Items of trait impls can be given ids suffixed with a hash of the impl
+    // rather than a running counter, so they don't change when the impls of a
+    // type get reordered.
+    let id_suffix = if cx.shared.options.stable_impl_item_ids && i.inner_impl().trait_.is_some() {
+        Some(impl_id_hash(i.inner_impl()))
+    } else {
+        None
+    };
+
+    write!(w, "<div class='impl-items{}'>", collapsed)?;
+    // Inherent associated constants may have already been rendered in their
+    // own section by `render_assoc_items`.
+    let consts_hoisted = cx.shared.options.assoc_consts_section &&
+                         i.inner_impl().trait_.is_none() &&
+                         render_mode == RenderMode::Normal;
     for trait_item in &i.inner_impl().items {
+        if consts_hoisted && trait_item.type_() == ItemType::AssociatedConst {
+            continue;
+        }
         doc_impl_item(w, cx, trait_item, link, render_mode,
-                      false, outer_version, trait_, show_def_docs)?;
+                      false, outer_version, trait_, show_def_docs,
+                      i.inner_impl().for_.def_id(), id_suffix.as_ref().map(|s| &**s))?;
     }
 
     fn render_default_items(w: &mut fmt::Formatter,
@@ -3494,7 +6186,8 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
                             i: &clean::Impl,
                             render_mode: RenderMode,
                             outer_version: Option<&str>,
-                            show_def_docs: bool) -> fmt::Result {
+                            show_def_docs: bool,
+                            id_suffix: Option<&str>) -> fmt::Result {
         for trait_item in &t.items {
             let n = trait_item.name.clone();
             if i.items.iter().find(|m| m.name == n).is_some() {
@@ -3504,7 +6197,7 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
             let assoc_link = AssocItemLink::GotoSource(did, &i.provided_trait_methods);
 
             doc_impl_item(w, cx, trait_item, assoc_link, render_mode, true,
-                          outer_version, None, show_def_docs)?;
+                          outer_version, None, show_def_docs, i.for_.def_id(), id_suffix)?;
         }
         Ok(())
     }
@@ -3512,8 +6205,8 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
     // If we've implemented a trait, then also emit documentation for all
     // default items which weren't overridden in the implementation block.
     if let Some(t) = trait_ {
-        render_default_items(w, cx, t, &i.inner_impl(),
-                             render_mode, outer_version, show_def_docs)?;
+        render_default_items(w, cx, t, &i.inner_impl(), render_mode, outer_version,
+                             show_def_docs, id_suffix.as_ref().map(|s| &**s))?;
     }
     write!(w, "</div>")?;
 
@@ -3531,7 +6224,12 @@ fn item_typedef(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
     write!(w, "type {}{}{where_clause} = {type_};</pre>",
            it.name.as_ref().unwrap(),
            t.generics,
-           where_clause = WhereClause { gens: &t.generics, indent: 0, end_newline: true },
+           where_clause = WhereClause {
+               gens: &t.generics,
+               indent: 0,
+               end_newline: true,
+               group_bounds: cx.shared.options.group_where_bounds,
+           },
            type_ = t.type_)?;
 
     document(w, cx, it)?;
@@ -3597,7 +6295,8 @@ impl<'a> fmt::Display for Sidebar<'a> {
                 }
             }
 
-            write!(fmt, "<div class=\"sidebar-elems\">")?;
+            write!(fmt, "<div class=\"sidebar-elems\" role=\"navigation\" \
+                         aria-label=\"Page and module contents\">")?;
             should_close = true;
             match it.inner {
                 clean::StructItem(ref s) => sidebar_struct(fmt, it, s)?,
@@ -3621,13 +6320,21 @@ impl<'a> fmt::Display for Sidebar<'a> {
         // to navigate the documentation (though slightly inefficiently).
 
         write!(fmt, "<p class='location'>")?;
+        if cx.shared.options.rtl {
+            write!(fmt, "<bdi dir='ltr'>")?;
+        }
         for (i, name) in cx.current.iter().take(parentlen).enumerate() {
             if i > 0 {
                 write!(fmt, "::<wbr>")?;
             }
-            write!(fmt, "<a href='{}index.html'>{}</a>",
-                   &cx.root_path()[..(cx.current.len() - i - 1) * 3],
-                   *name)?;
+            let root = match cx.shared.absolute_root_url {
+                Some(ref url) => format!("{}{}/", url, cx.current[..i + 1].join("/")),
+                None => repeat("../").take(cx.current.len() - i - 1).collect(),
+            };
+            write!(fmt, "<a href='{}index.html'>{}</a>", root, *name)?;
+        }
+        if cx.shared.options.rtl {
+            write!(fmt, "</bdi>")?;
         }
         write!(fmt, "</p>")?;
 
@@ -3674,6 +6381,31 @@ fn get_methods(i: &clean::Impl, for_deref: bool) -> Vec<String> {
     }).collect::<Vec<_>>()
 }
 
+fn get_trait_impl_links(impls: &[&Impl]) -> String {
+    let mut links = HashSet::new();
+    impls.iter()
+         .filter_map(|i| {
+             let is_negative_impl = is_negative_impl(i.inner_impl());
+             if let Some(ref i) = i.inner_impl().trait_ {
+                 let i_display = format!("{:#}", i);
+                 let out = Escape(&i_display);
+                 let encoded = small_url_encode(&format!("{:#}", i));
+                 let generated = format!("<a href=\"#impl-{}\">{}{}</a>",
+                                         encoded,
+                                         if is_negative_impl { "!" } else { "" },
+                                         out);
+                 if !links.contains(&generated) && links.insert(generated.clone()) {
+                     Some(generated)
+                 } else {
+                     None
+                 }
+             } else {
+                 None
+             }
+         })
+         .collect::<String>()
+}
+
 // The point is to url encode any potential character from a type with genericity.
 fn small_url_encode(s: &str) -> String {
     s.replace("<", "%3C")
@@ -3715,7 +6447,10 @@ fn sidebar_assoc_items(it: &clean::Item) -> String {
                 }).next() {
                     let inner_impl = target.def_id().or(target.primitive_type().and_then(|prim| {
                         c.primitive_locations.get(&prim).cloned()
-                    })).and_then(|did| c.impls.get(&did));
+                    })).and_then(|did| {
+                        // Like on the page, a type dereferencing to itself gets no section.
+                        if did == it.def_id { None } else { c.impls.get(&did) }
+                    });
                     if let Some(impls) = inner_impl {
                         out.push_str("<a class=\"sidebar-title\" href=\"#deref-methods\">");
                         out.push_str(&format!("Methods from {:#}&lt;Target={:#}&gt;",
@@ -3730,31 +6465,28 @@ fn sidebar_assoc_items(it: &clean::Item) -> String {
                     }
                 }
             }
-            let mut links = HashSet::new();
-            let ret = v.iter()
-                       .filter_map(|i| {
-                           let is_negative_impl = is_negative_impl(i.inner_impl());
-                           if let Some(ref i) = i.inner_impl().trait_ {
-                               let i_display = format!("{:#}", i);
-                               let out = Escape(&i_display);
-                               let encoded = small_url_encode(&format!("{:#}", i));
-                               let generated = format!("<a href=\"#impl-{}\">{}{}</a>",
-                                                       encoded,
-                                                       if is_negative_impl { "!" } else { "" },
-                                                       out);
-                               if !links.contains(&generated) && links.insert(generated.clone()) {
-                                   Some(generated)
-                               } else {
-                                   None
-                               }
-                           } else {
-                               None
-                           }
-                       })
-                       .collect::<String>();
+            let (marker, traits): (Vec<_>, Vec<_>) = v.iter().partition(|i| {
+                i.trait_did().map_or(false, |did| c.marker_trait_dids.contains(&did))
+            });
+            let (blanket, traits): (Vec<_>, Vec<_>) = traits.into_iter().partition(|i| {
+                i.inner_impl().for_.is_generic()
+            });
+            let ret = get_trait_impl_links(&traits);
+            if !ret.is_empty() {
+                out.push_str("<a class=\"sidebar-title\" href=\"#implementations\">\
+                              Trait Implementations</a>");
+                out.push_str(&format!("<div class=\"sidebar-links\">{}</div>", ret));
+            }
+            let ret = get_trait_impl_links(&marker);
             if !ret.is_empty() {
-                out.push_str("<a class=\"sidebar-title\" href=\"#implementations\">\
-                              Trait Implementations</a>");
+                out.push_str("<a class=\"sidebar-title\" href=\"#synthetic-implementations\">\
+                              Auto Trait Implementations</a>");
+                out.push_str(&format!("<div class=\"sidebar-links\">{}</div>", ret));
+            }
+            let ret = get_trait_impl_links(&blanket);
+            if !ret.is_empty() {
+                out.push_str("<a class=\"sidebar-title\" href=\"#blanket-implementations\">\
+                              Blanket Implementations</a>");
                 out.push_str(&format!("<div class=\"sidebar-links\">{}</div>", ret));
             }
         }
@@ -3815,7 +6547,7 @@ fn sidebar_trait(fmt: &mut fmt::Formatter, it: &clean::Item,
                          _ => None,
                      }
                  })
-                 .collect::<String>();
+                 .collect::<Vec<_>>();
     let consts = t.items
                   .iter()
                   .filter_map(|m| {
@@ -3827,7 +6559,7 @@ fn sidebar_trait(fmt: &mut fmt::Formatter, it: &clean::Item,
                           _ => None,
                       }
                   })
-                  .collect::<String>();
+                  .collect::<Vec<_>>();
     let required = t.items
                     .iter()
                     .filter_map(|m| {
@@ -3839,7 +6571,7 @@ fn sidebar_trait(fmt: &mut fmt::Formatter, it: &clean::Item,
                             _ => None,
                         }
                     })
-                    .collect::<String>();
+                    .collect::<Vec<_>>();
     let provided = t.items
                     .iter()
                     .filter_map(|m| {
@@ -3850,27 +6582,31 @@ fn sidebar_trait(fmt: &mut fmt::Formatter, it: &clean::Item,
                             _ => None,
                         }
                     })
-                    .collect::<String>();
+                    .collect::<Vec<_>>();
 
     if !types.is_empty() {
         sidebar.push_str(&format!("<a class=\"sidebar-title\" href=\"#associated-types\">\
-                                   Associated Types</a><div class=\"sidebar-links\">{}</div>",
-                                  types));
+                                   Associated Types ({})</a>\
+                                   <div class=\"sidebar-links\">{}</div>",
+                                  types.len(), types.concat()));
     }
     if !consts.is_empty() {
         sidebar.push_str(&format!("<a class=\"sidebar-title\" href=\"#associated-const\">\
-                                   Associated Constants</a><div class=\"sidebar-links\">{}</div>",
-                                  consts));
+                                   Associated Constants ({})</a>\
+                                   <div class=\"sidebar-links\">{}</div>",
+                                  consts.len(), consts.concat()));
     }
     if !required.is_empty() {
         sidebar.push_str(&format!("<a class=\"sidebar-title\" href=\"#required-methods\">\
-                                   Required Methods</a><div class=\"sidebar-links\">{}</div>",
-                                  required));
+                                   Required Methods ({})</a>\
+                                   <div class=\"sidebar-links\">{}</div>",
+                                  required.len(), required.concat()));
     }
     if !provided.is_empty() {
         sidebar.push_str(&format!("<a class=\"sidebar-title\" href=\"#provided-methods\">\
-                                   Provided Methods</a><div class=\"sidebar-links\">{}</div>",
-                                  provided));
+                                   Provided Methods ({})</a>\
+                                   <div class=\"sidebar-links\">{}</div>",
+                                  provided.len(), provided.concat()));
     }
 
     let c = cache();
@@ -3932,7 +6668,7 @@ fn sidebar_typedef(fmt: &mut fmt::Formatter, it: &clean::Item,
 fn get_struct_fields_name(fields: &[clean::Item]) -> String {
     fields.iter()
           .filter(|f| if let clean::StructFieldItem(..) = f.inner {
-              true
+              !f.is_doc_hidden()
           } else {
               false
           })
@@ -3967,6 +6703,7 @@ fn sidebar_enum(fmt: &mut fmt::Formatter, it: &clean::Item,
     let mut sidebar = String::new();
 
     let variants = e.variants.iter()
+                             .filter(|v| !v.is_doc_hidden())
                              .filter_map(|v| match v.name {
                                  Some(ref name) => Some(format!("<a href=\"#variant.{name}\">{name}\
                                                                  </a>", name = name)),
@@ -3997,13 +6734,7 @@ fn sidebar_module(fmt: &mut fmt::Formatter, _it: &clean::Item,
                                   name = "Reexports"));
     }
 
-    // ordering taken from item_module, reorder, where it prioritized elements in a certain order
-    // to print its headings
-    for &myty in &[ItemType::Primitive, ItemType::Module, ItemType::Macro, ItemType::Struct,
-                   ItemType::Enum, ItemType::Constant, ItemType::Static, ItemType::Trait,
-                   ItemType::Function, ItemType::Typedef, ItemType::Union, ItemType::Impl,
-                   ItemType::TyMethod, ItemType::Method, ItemType::StructField, ItemType::Variant,
-                   ItemType::AssociatedType, ItemType::AssociatedConst, ItemType::ForeignType] {
+    for &myty in ITEM_SECTIONS {
         if items.iter().any(|it| {
             if let clean::AutoImplItem(..) = it.inner {
                 false
@@ -4011,29 +6742,7 @@ fn sidebar_module(fmt: &mut fmt::Formatter, _it: &clean::Item,
                 !it.is_stripped() && it.type_() == myty
             }
         }) {
-            let (short, name) = match myty {
-                ItemType::ExternCrate |
-                ItemType::Import          => ("reexports", "Reexports"),
-                ItemType::Module          => ("modules", "Modules"),
-                ItemType::Struct          => ("structs", "Structs"),
-                ItemType::Union           => ("unions", "Unions"),
-                ItemType::Enum            => ("enums", "Enums"),
-                ItemType::Function        => ("functions", "Functions"),
-                ItemType::Typedef         => ("types", "Type Definitions"),
-                ItemType::Static          => ("statics", "Statics"),
-                ItemType::Constant        => ("constants", "Constants"),
-                ItemType::Trait           => ("traits", "Traits"),
-                ItemType::Impl            => ("impls", "Implementations"),
-                ItemType::TyMethod        => ("tymethods", "Type Methods"),
-                ItemType::Method          => ("methods", "Methods"),
-                ItemType::StructField     => ("fields", "Struct Fields"),
-                ItemType::Variant         => ("variants", "Variants"),
-                ItemType::Macro           => ("macros", "Macros"),
-                ItemType::Primitive       => ("primitives", "Primitive Types"),
-                ItemType::AssociatedType  => ("associated-types", "Associated Types"),
-                ItemType::AssociatedConst => ("associated-consts", "Associated Constants"),
-                ItemType::ForeignType     => ("foreign-types", "Foreign Types"),
-            };
+            let (short, name) = item_ty_to_strs(&myty);
             sidebar.push_str(&format!("<li><a href=\"#{id}\">{name}</a></li>",
                                       id = short,
                                       name = name));
@@ -4056,7 +6765,7 @@ fn sidebar_foreign_type(fmt: &mut fmt::Formatter, it: &clean::Item) -> fmt::Resu
 
 impl<'a> fmt::Display for Source<'a> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let Source(s) = *self;
+        let Source(s, spans, links) = *self;
         let lines = s.lines().count();
         let mut cols = 0;
         let mut tmp = lines;
@@ -4064,17 +6773,438 @@ impl<'a> fmt::Display for Source<'a> {
             cols += 1;
             tmp /= 10;
         }
+
+        // Only keep the spans which don't overlap an earlier one, as collapsing
+        // nested or interleaved spans independently isn't supported.
+        let mut spans = spans.to_vec();
+        spans.sort();
+        let mut last = 0;
+        spans.retain(|&(lo, hi)| {
+            if lo > last && hi <= lines {
+                last = hi;
+                true
+            } else {
+                false
+            }
+        });
+        let highlighted = if spans.is_empty() {
+            None
+        } else {
+            highlight::render_inner_with_links(s, links).ok()
+        };
+        let highlighted = match highlighted {
+            Some(highlighted) => highlighted,
+            None => {
+                write!(fmt, "<pre class=\"line-numbers\">")?;
+                for i in 1..lines + 1 {
+                    write!(fmt, "<span id=\"{0}\">{0:1$}</span>\n", i, cols)?;
+                }
+                write!(fmt, "</pre>")?;
+                write!(fmt, "{}", highlight::render_with_links(s, links))?;
+                return Ok(());
+            }
+        };
+
+        let numbers = (1..lines + 1).map(|i| format!("<span id=\"{0}\">{0:1$}</span>", i, cols))
+                                    .collect::<Vec<_>>();
         write!(fmt, "<pre class=\"line-numbers\">")?;
-        for i in 1..lines + 1 {
-            write!(fmt, "<span id=\"{0}\">{0:1$}</span>\n", i, cols)?;
-        }
+        write_collapsible_lines(fmt, &numbers, &spans)?;
         write!(fmt, "</pre>")?;
-        write!(fmt, "{}",
-               highlight::render_with_highlighting(s, None, None, None, None))?;
+        write!(fmt, "<pre class=\"rust \">\n")?;
+        write_collapsible_lines(fmt, &split_highlighted_lines(&highlighted), &spans)?;
+        write!(fmt, "</pre>\n")?;
+        Ok(())
+    }
+}
+
+/// Writes out the given lines, wrapping each of the given (sorted and disjoint)
+/// line spans in a `src-item` element whose lines after the first can be hidden
+/// by `main.js`.
+fn write_collapsible_lines(fmt: &mut fmt::Formatter, lines: &[String],
+                           spans: &[(usize, usize)]) -> fmt::Result {
+    let mut spans = spans.iter().peekable();
+    for (i, line) in lines.iter().enumerate() {
+        let lineno = i + 1;
+        let span = spans.peek().map(|&&span| span);
+        if let Some((lo, hi)) = span {
+            if lo == lineno {
+                write!(fmt, "<span class=\"src-item\" data-item-start=\"{}\" \
+                             data-item-end=\"{}\">", lo, hi)?;
+            }
+        }
+        write!(fmt, "{}\n", line)?;
+        if let Some((lo, hi)) = span {
+            if lo == lineno {
+                write!(fmt, "<span class=\"src-item-body\">")?;
+            }
+            if hi == lineno {
+                write!(fmt, "</span></span>")?;
+                spans.next();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits highlighted source code into its lines, closing the elements which
+/// are still open at the end of each line and reopening them on the next one,
+/// so every line is well-formed HTML on its own.
+fn split_highlighted_lines(html: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut open: Vec<&str> = Vec::new();
+    let mut line = String::new();
+    let mut rest = html;
+    while let Some(pos) = rest.find(|c: char| c == '<' || c == '\n') {
+        line.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if rest.starts_with('\n') {
+            for tag in open.iter().rev() {
+                let name = tag[1..].split(|c: char| c == ' ' || c == '>').next().unwrap();
+                line.push_str("</");
+                line.push_str(name);
+                line.push('>');
+            }
+            lines.push(mem::replace(&mut line, open.concat()));
+            rest = &rest[1..];
+        } else {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let tag = &rest[..end];
+            if tag.starts_with("</") {
+                open.pop();
+            } else {
+                open.push(tag);
+            }
+            line.push_str(tag);
+            rest = &rest[end..];
+        }
+    }
+    line.push_str(rest);
+    if !line.is_empty() && line != open.concat() {
+        lines.push(line);
+    }
+    lines
+}
+
+impl<'a> fmt::Display for TypeAliases<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let TypeAliases(cache) = *self;
+        let mut aliases = cache.typedefs.iter().filter_map(|(did, &(ref target, ref doc))| {
+            cache.paths.get(did).map(|&(ref fqp, _)| (fqp, *did, target, doc))
+        }).collect::<Vec<_>>();
+        aliases.sort_by(|a, b| a.0.cmp(b.0));
+
+        write!(fmt, "<h1 class='fqn'><span class='in-band'>Type aliases</span></h1>")?;
+        write!(fmt, "<table>")?;
+        for (fqp, did, target, doc) in aliases {
+            let url = match href(did) {
+                Some((url, _, _)) => url,
+                None => continue,
+            };
+            write!(fmt, "<tr class='module-item'>\
+                           <td><a class='type' href='{url}'>{name}</a></td>\
+                           <td><code>{target}</code></td>\
+                           <td class='docblock-short'>{docs}</td>\
+                         </tr>",
+                   url = url,
+                   name = fqp.join("::"),
+                   target = *target,
+                   docs = MarkdownSummaryLine(doc.as_ref().map_or("", |s| &**s)))?;
+        }
+        write!(fmt, "</table>")
+    }
+}
+
+impl<'a> fmt::Display for Stabilized<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let Stabilized(cache) = *self;
+        let mut versions = cache.stabilized.keys().collect::<Vec<_>>();
+        versions.sort_by_key(|v| version_key(v));
+
+        write!(fmt, "<h1 class='fqn'><span class='in-band'>Stabilized items</span></h1>")?;
+        for version in versions {
+            let mut items = cache.stabilized[version].iter().filter_map(|&(did, ref assoc)| {
+                match *assoc {
+                    None => {
+                        if !cache.access_levels.is_public(did) {
+                            return None;
+                        }
+                        let &(ref fqp, ty) = cache.paths.get(&did)?;
+                        let (url, _, _) = href(did)?;
+                        Some((fqp.join("::"), ty, url))
+                    }
+                    // Associated items link to their section of the page of their parent.
+                    Some((parent, ty, ref name)) => {
+                        if !cache.access_levels.is_public(parent) {
+                            return None;
+                        }
+                        let &(ref fqp, _) = cache.paths.get(&parent)?;
+                        let (url, _, _) = href(parent)?;
+                        Some((format!("{}::{}", fqp.join("::"), name), ty,
+                              format!("{}#{}.{}", url, ty, name)))
+                    }
+                }
+            }).collect::<Vec<_>>();
+            if items.is_empty() {
+                continue;
+            }
+            items.sort();
+            write!(fmt, "<details><summary>{} ({})</summary><table>",
+                   Escape(version), items.len())?;
+            for (name, ty, url) in items {
+                write!(fmt, "<tr class='module-item'>\
+                               <td><a class='{ty}' href='{url}'>{name}</a></td>\
+                             </tr>",
+                       ty = ty.css_class(), url = url, name = name)?;
+            }
+            write!(fmt, "</table></details>")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for AllItems<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let AllItems(cache) = *self;
+        let mut items = cache.paths.iter().filter_map(|(did, &(ref fqp, ty))| {
+            if !did.is_local() || !cache.access_levels.is_public(*did) || fqp.len() < 2 {
+                return None;
+            }
+            // Links are relative to the crate's directory, where the page lives.
+            let mut url = String::new();
+            for name in &fqp[1..fqp.len() - 1] {
+                url.push_str(name);
+                url.push('/');
+            }
+            url.push_str(&item_path(ty, fqp.last().unwrap()));
+            Some((ty, fqp[1..].join("::"), url))
+        }).collect::<Vec<_>>();
+        items.sort_by(|a, b| (&a.1, a.0 as u8).cmp(&(&b.1, b.0 as u8)));
+        // Variants are recorded under the path of their enum.
+        items.dedup();
+
+        write!(fmt, "<h1 class='fqn'><span class='in-band'>List of all items</span></h1>")?;
+        for &myty in ITEM_SECTIONS {
+            let mut section = items.iter().filter(|&&(ty, _, _)| ty == myty).peekable();
+            if section.peek().is_none() {
+                continue;
+            }
+            let (short, name) = item_ty_to_strs(&myty);
+            write!(fmt, "<h2 id='{id}' class='section-header'>\
+                           <a href=\"#{id}\">{name}</a></h2><ul class='all-items'>",
+                   id = short, name = name)?;
+            for &(ty, ref name, ref url) in section {
+                write!(fmt, "<li><a class='{ty}' href='{url}'>{name}</a></li>",
+                       ty = ty.css_class(), url = url, name = name)?;
+            }
+            write!(fmt, "</ul>")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for MarkdownItem<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let MarkdownItem { cx, item } = *self;
+        let kind = match item.inner {
+            clean::ModuleItem(ref m) => if m.is_crate { "Crate" } else { "Module" },
+            clean::FunctionItem(..) => "Function",
+            clean::StructItem(..) => "Struct",
+            clean::TraitItem(..) => "Trait",
+            // Other items don't get a page yet.
+            _ => return Ok(()),
+        };
+        let mut path = cx.current.clone();
+        if !item.is_mod() {
+            path.push(item.name.clone().unwrap());
+        }
+        writeln!(fmt, "# {} `{}`\n", kind, path.join("::"))?;
+
+        match item.inner {
+            clean::FunctionItem(ref f) => {
+                writeln!(fmt, "```rust\n{}\n```\n",
+                         plain_fn_signature(item, f.constness, f.unsafety, f.abi,
+                                            &f.generics, &f.decl))?;
+            }
+            clean::StructItem(ref s) => {
+                write!(fmt, "```rust\n{}struct {}{:#}",
+                       VisSpace(&item.visibility), item.name.as_ref().unwrap(), s.generics)?;
+                let mut omitted = s.fields_stripped;
+                let fields = s.fields.iter().filter_map(|field| match field.inner {
+                    clean::StructFieldItem(ref ty) if !field.is_doc_hidden() => Some((field, ty)),
+                    _ => {
+                        omitted = true;
+                        None
+                    }
+                }).collect::<Vec<_>>();
+                let where_clause = WhereClause {
+                    gens: &s.generics,
+                    indent: 0,
+                    end_newline: true,
+                    group_bounds: false,
+                };
+                match s.struct_type {
+                    doctree::Plain => {
+                        writeln!(fmt, "{:#} {{", where_clause)?;
+                        for (field, ty) in fields {
+                            writeln!(fmt, "    {}{}: {:#},", VisSpace(&field.visibility),
+                                     field.name.as_ref().unwrap(), ty)?;
+                        }
+                        if omitted {
+                            writeln!(fmt, "    // some fields omitted")?;
+                        }
+                        write!(fmt, "}}")?;
+                    }
+                    doctree::Tuple => {
+                        let fields = fields.iter().map(|&(field, ty)| {
+                            format!("{}{:#}", VisSpace(&field.visibility), ty)
+                        }).chain(if omitted { Some("_".to_string()) } else { None });
+                        write!(fmt, "({}){:#};", fields.collect::<Vec<_>>().join(", "),
+                               where_clause)?;
+                    }
+                    doctree::Unit => write!(fmt, "{:#};", where_clause)?,
+                }
+                writeln!(fmt, "\n```\n")?;
+            }
+            clean::TraitItem(ref t) => {
+                write!(fmt, "```rust\n{}{}trait {}{:#}",
+                       VisSpace(&item.visibility), UnsafetySpace(t.unsafety),
+                       item.name.as_ref().unwrap(), t.generics)?;
+                if !t.bounds.is_empty() {
+                    write!(fmt, ": {:#}", TyParamBounds(&t.bounds))?;
+                }
+                writeln!(fmt, "{:#} {{", WhereClause {
+                    gens: &t.generics,
+                    indent: 0,
+                    end_newline: true,
+                    group_bounds: false,
+                })?;
+                for member in &t.items {
+                    if let Some(signature) = plain_member_signature(member) {
+                        writeln!(fmt, "    {};", signature.replace("\n", "\n    "))?;
+                    }
+                }
+                writeln!(fmt, "}}\n```\n")?;
+            }
+            _ => {}
+        }
+
+        if let Some(doc) = cx.shared.maybe_collapsed_doc_value(item) {
+            writeln!(fmt, "{}\n", doc)?;
+        }
+
+        match item.inner {
+            clean::ModuleItem(ref m) => {
+                let mut items = m.items.iter().filter(|it| {
+                    !it.is_stripped() && it.name.is_some() && match it.inner {
+                        clean::ModuleItem(..) | clean::FunctionItem(..) |
+                        clean::StructItem(..) | clean::TraitItem(..) => true,
+                        _ => false,
+                    }
+                }).collect::<Vec<_>>();
+                items.sort_by_key(|it| (reorder(it.type_()), it.name.clone()));
+                if !items.is_empty() {
+                    writeln!(fmt, "## Items\n")?;
+                }
+                for it in items {
+                    let name = it.name.as_ref().unwrap();
+                    write!(fmt, "- [`{}`]({})", name,
+                           item_file_name(it.type_(), name, OutputFormat::Markdown))?;
+                    let summary = plain_summary_line(it.doc_value());
+                    if summary.is_empty() {
+                        writeln!(fmt, "")?;
+                    } else {
+                        writeln!(fmt, ": {}", summary)?;
+                    }
+                }
+            }
+            clean::StructItem(..) => {
+                let cache = cache();
+                let methods = cache.impls.get(&item.def_id).into_iter().flat_map(|impls| {
+                    impls.iter().filter(|i| i.inner_impl().trait_.is_none())
+                }).flat_map(|i| i.inner_impl().items.iter()).filter(|member| {
+                    member.visibility == Some(clean::Public)
+                }).collect::<Vec<_>>();
+                if !methods.is_empty() {
+                    writeln!(fmt, "## Methods\n")?;
+                }
+                for member in methods {
+                    if let Some(signature) = plain_member_signature(member) {
+                        writeln!(fmt, "```rust\n{}\n```\n", signature)?;
+                        if let Some(doc) = cx.shared.maybe_collapsed_doc_value(member) {
+                            writeln!(fmt, "{}\n", doc)?;
+                        }
+                    }
+                }
+            }
+            clean::TraitItem(ref t) => {
+                let documented = t.items.iter().filter(|member| {
+                    member.name.is_some() && member.doc_value().is_some()
+                }).collect::<Vec<_>>();
+                for member in documented {
+                    writeln!(fmt, "## `{}`\n", member.name.as_ref().unwrap())?;
+                    if let Some(doc) = cx.shared.maybe_collapsed_doc_value(member) {
+                        writeln!(fmt, "{}\n", doc)?;
+                    }
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
 }
 
+/// Returns the plain text signature of the function or method `it`.
+fn plain_fn_signature(it: &clean::Item, constness: hir::Constness, unsafety: hir::Unsafety,
+                      abi: abi::Abi, generics: &clean::Generics,
+                      decl: &clean::FnDecl) -> String {
+    let head = format!("{}{}{}{:#}fn {}{:#}",
+                       VisSpace(&it.visibility),
+                       ConstnessSpace(constness),
+                       UnsafetySpace(unsafety),
+                       AbiSpace(abi),
+                       it.name.as_ref().unwrap(),
+                       generics);
+    let decl = Method { decl, name_len: head.len(), indent: 0 };
+    let where_clause = WhereClause {
+        gens: generics,
+        indent: 0,
+        end_newline: true,
+        group_bounds: false,
+    };
+    format!("{}{:#}{:#}", head, decl, where_clause)
+}
+
+/// Returns the plain text signature of the associated item `it`, if it has one.
+fn plain_member_signature(it: &clean::Item) -> Option<String> {
+    let name = it.name.as_ref()?;
+    Some(match it.inner {
+        clean::TyMethodItem(ref m) => {
+            plain_fn_signature(it, m.constness, m.unsafety, m.abi, &m.generics, &m.decl)
+        }
+        clean::MethodItem(ref m) => {
+            plain_fn_signature(it, m.constness, m.unsafety, m.abi, &m.generics, &m.decl)
+        }
+        clean::AssociatedConstItem(ref ty, _) => {
+            format!("{}const {}: {:#}", VisSpace(&it.visibility), name, ty)
+        }
+        clean::AssociatedTypeItem(ref bounds, _) => {
+            if bounds.is_empty() {
+                format!("type {}", name)
+            } else {
+                format!("type {}: {:#}", name, TyParamBounds(bounds))
+            }
+        }
+        _ => return None,
+    })
+}
+
+impl<'a> fmt::Display for CrateHelp<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let CrateHelp(md, ref span, render_type, scx) = *self;
+        render_markdown(fmt, md, span.clone(), render_type, "", scx)
+    }
+}
+
 fn item_macro(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
               t: &clean::Macro) -> fmt::Result {
     w.write_str(&highlight::render_with_highlighting(&t.source,
@@ -4082,7 +7212,26 @@ fn item_macro(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                                                      None,
                                                      None,
                                                      None))?;
-    document(w, cx, it)
+    document(w, cx, it)?;
+
+    // Give each arm its own anchor, so that a particular form of the macro can
+    // be linked to.
+    if t.matchers.len() > 1 {
+        write!(w, "
+            <h2 id='arms' class='small-section-header'>
+              Arms<a href='#arms' class='anchor' data-permalink='{permalink}'></a>
+            </h2>
+            <div class='macro-arms'>
+        ", permalink = cx.permalink(it, "arms"))?;
+        for (i, matcher) in t.matchers.iter().enumerate() {
+            let id = derive_id(format!("arm.{}", i));
+            write!(w, "<div id='{id}' class='macro-arm'><a href='#{id}' class='anchor'></a>\
+                       <code>{matcher} =&gt; {{ ... }}</code></div>",
+                   id = id, matcher = Escape(matcher))?;
+        }
+        write!(w, "</div>")?;
+    }
+    Ok(())
 }
 
 fn item_primitive(w: &mut fmt::Formatter, cx: &Context,
@@ -4098,6 +7247,15 @@ fn make_item_keywords(it: &clean::Item) -> String {
     format!("{}, {}", BASIC_KEYWORDS, it.name.as_ref().unwrap())
 }
 
+/// Returns the aliases given to `item` with `#[doc(alias = "...")]`.
+fn doc_aliases(item: &clean::Item) -> Vec<String> {
+    item.attrs.lists("doc")
+        .filter(|a| a.check_name("alias"))
+        .filter_map(|a| a.value_str())
+        .map(|alias| alias.to_string())
+        .collect()
+}
+
 fn get_index_search_type(item: &clean::Item) -> Option<IndexItemFunctionType> {
     let decl = match item.inner {
         clean::FunctionItem(ref f) => &f.decl,
@@ -4106,7 +7264,15 @@ fn get_index_search_type(item: &clean::Item) -> Option<IndexItemFunctionType> {
         _ => return None
     };
 
-    let inputs = decl.inputs.values.iter().map(|arg| get_index_type(&arg.type_)).collect();
+    let inputs = decl.inputs.values.iter().map(|arg| {
+        let mut ty = get_index_type(&arg.type_);
+        // Patterns other than plain bindings don't make for a meaningful name.
+        if !arg.name.is_empty() && arg.name != "_" &&
+           arg.name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            ty.arg_name = Some(arg.name.clone());
+        }
+        ty
+    }).collect();
     let output = match decl.output {
         clean::FunctionRetTy::Return(ref return_type) => Some(get_index_type(return_type)),
         _ => None
@@ -4115,14 +7281,103 @@ fn get_index_search_type(item: &clean::Item) -> Option<IndexItemFunctionType> {
     Some(IndexItemFunctionType { inputs: inputs, output: output })
 }
 
+/// Counts the function `item` once for every type mentioned in its signature, including the
+/// type parameters of those types.
+fn add_type_usage(type_usage: &mut FxHashMap<DefId, usize>, item: &clean::Item) {
+    fn add_type(ty: &clean::Type, dids: &mut FxHashSet<DefId>) {
+        match *ty {
+            clean::ResolvedPath { did, .. } => {
+                dids.insert(did);
+            }
+            clean::BorrowedRef { ref type_, .. } |
+            clean::RawPointer(_, ref type_) |
+            clean::Slice(ref type_) |
+            clean::Array(ref type_, _) => add_type(type_, dids),
+            clean::Tuple(ref types) => {
+                for ty in types {
+                    add_type(ty, dids);
+                }
+            }
+            _ => {}
+        }
+        for ty in ty.generics().unwrap_or(&[]) {
+            add_type(ty, dids);
+        }
+        for binding in ty.bindings().unwrap_or(&[]) {
+            add_type(&binding.ty, dids);
+        }
+    }
+
+    let decl = match item.inner {
+        clean::FunctionItem(ref f) => &f.decl,
+        clean::MethodItem(ref m) => &m.decl,
+        clean::TyMethodItem(ref m) => &m.decl,
+        _ => return,
+    };
+    let mut dids = FxHashSet();
+    for arg in &decl.inputs.values {
+        add_type(&arg.type_, &mut dids);
+    }
+    if let clean::FunctionRetTy::Return(ref ty) = decl.output {
+        add_type(ty, &mut dids);
+    }
+    for did in dids {
+        *type_usage.entry(did).or_insert(0) += 1;
+    }
+}
+
 fn get_index_type(clean_type: &clean::Type) -> Type {
     let t = Type {
         name: get_index_type_name(clean_type, true).map(|s| s.to_ascii_lowercase()),
         generics: get_generics(clean_type),
+        arg_name: None,
     };
     t
 }
 
+/// Splits `name` into lowercase words, as `hash map` for `HashMap` and `into iter`
+/// for `into_iter`, so that searches can match on word boundaries. Returns `None`
+/// for names made of a single word.
+fn index_words(name: &str) -> Option<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !word.is_empty() {
+                words.push(mem::replace(&mut word, String::new()));
+            }
+            continue;
+        }
+        // A word starts at an uppercase letter following a lowercase one or a
+        // digit, or at the last uppercase letter of an acronym, as in `HTMLParser`.
+        if c.is_uppercase() && !word.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                words.push(mem::replace(&mut word, String::new()));
+            }
+        }
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    if words.len() > 1 {
+        Some(words.join(" "))
+    } else {
+        None
+    }
+}
+
+/// Returns the version of Rust `item` was stabilized in, for the search index.
+fn index_since(item: &clean::Item) -> Option<String> {
+    match item.stable_since() {
+        Some(since) if !since.is_empty() => Some(since.to_owned()),
+        _ => None,
+    }
+}
+
 fn get_index_type_name(clean_type: &clean::Type, accept_generic: bool) -> Option<String> {
     match *clean_type {
         clean::ResolvedPath { ref path, .. } => {
@@ -4130,26 +7385,47 @@ fn get_index_type_name(clean_type: &clean::Type, accept_generic: bool) -> Option
             Some(segments[segments.len() - 1].name.clone())
         }
         clean::Generic(ref s) if accept_generic => Some(s.clone()),
-        clean::Primitive(ref p) => Some(format!("{:?}", p)),
-        clean::BorrowedRef { ref type_, .. } => get_index_type_name(type_, accept_generic),
+        clean::Primitive(ref p) => Some(p.as_str().to_owned()),
+        clean::BorrowedRef { ref type_, .. } |
+        clean::RawPointer(_, ref type_) => get_index_type_name(type_, accept_generic),
+        clean::Tuple(ref types) if !types.is_empty() => Some("tuple".to_owned()),
+        clean::Slice(_) => Some("slice".to_owned()),
+        clean::Array(..) => Some("array".to_owned()),
+        // An opaque type is searched for by its first trait, as in `impl Iterator`.
+        clean::ImplTrait(ref bounds) => bounds.iter().filter_map(|bound| match *bound {
+            clean::TraitBound(ref poly, _) => get_index_type_name(&poly.trait_, accept_generic),
+            clean::RegionBound(_) => None,
+        }).next(),
         // FIXME: add all from clean::Type.
         _ => None
     }
 }
 
 fn get_generics(clean_type: &clean::Type) -> Option<Vec<String>> {
-    clean_type.generics()
-              .and_then(|types| {
-                  let r = types.iter()
-                               .filter_map(|t| get_index_type_name(t, false))
-                               .map(|s| s.to_ascii_lowercase())
-                               .collect::<Vec<_>>();
-                  if r.is_empty() {
-                      None
-                  } else {
-                      Some(r)
-                  }
-              })
+    // The element types of tuples, slices and arrays stand for their generics,
+    // and so do the targets of associated type bindings, as in `Iterator<Item = u8>`.
+    let types = match *clean_type {
+        clean::BorrowedRef { ref type_, .. } |
+        clean::RawPointer(_, ref type_) => return get_generics(type_),
+        clean::Tuple(ref types) => types.iter().collect::<Vec<_>>(),
+        clean::Slice(ref type_) | clean::Array(ref type_, _) => vec![&**type_],
+        _ => {
+            let bindings = clean_type.bindings().unwrap_or(&[]);
+            match clean_type.generics() {
+                Some(types) => types.iter().chain(bindings.iter().map(|b| &b.ty)).collect(),
+                None => return None,
+            }
+        }
+    };
+    let r = types.iter()
+                 .filter_map(|t| get_index_type_name(t, false))
+                 .map(|s| s.to_ascii_lowercase())
+                 .collect::<Vec<_>>();
+    if r.is_empty() {
+        None
+    } else {
+        Some(r)
+    }
 }
 
 pub fn cache() -> Arc<Cache> {
@@ -4175,6 +7451,51 @@ fn test_unique_id() {
     test();
 }
 
+#[cfg(test)]
+#[test]
+fn test_used_ids() {
+    reset_ids(true);
+    for id in &["foo", "examples", "examples", "main", "method.bar", "foo"] {
+        derive_id(id.to_string());
+    }
+    assert_eq!(used_ids(), ["examples", "examples-1", "foo", "foo-1", "main-1", "method.bar"]);
+    reset_ids(true);
+    assert!(used_ids().is_empty());
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_include_lines() {
+    assert_eq!(parse_include_lines("{{include-lines examples/foo.rs:3:7}}"),
+               Some(("examples/foo.rs", 3, 7)));
+    assert_eq!(parse_include_lines("  {{include-lines C:/foo.rs:1:2}} "),
+               Some(("C:/foo.rs", 1, 2)));
+    assert_eq!(parse_include_lines("{{include-lines foo.rs:1}}"), None);
+    assert_eq!(parse_include_lines("{{include-lines :1:2}}"), None);
+    assert_eq!(parse_include_lines("{{include-lines foo.rs:a:2}}"), None);
+    assert_eq!(parse_include_lines("see {{include-lines foo.rs:1:2}}"), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_expand_included_lines() {
+    let dir = ::tempdir::TempDir::new("rustdoc-test").unwrap();
+    File::create(dir.path().join("foo.rs")).unwrap().write_all(b"a\nb\nc\n").unwrap();
+    let expand = |md: &str| expand_included_lines(md, &Span::empty(), dir.path()).into_owned();
+
+    assert_eq!(expand("x\n{{include-lines foo.rs:2:3}}\ny"), "x\n```rust\nb\nc\n```\ny\n");
+    // Directives in code blocks are shown as they are.
+    let fenced = "```text\n{{include-lines foo.rs:1:1}}\n```\n";
+    assert_eq!(expand(fenced), fenced);
+    let fenced = "~~~~\n```\n{{include-lines foo.rs:1:1}}\n~~~~\n";
+    assert_eq!(expand(fenced), fenced);
+    // Files outside of the source root can't be included.
+    let outside = "{{include-lines ../foo.rs:1:1}}\n";
+    assert_eq!(expand(outside), outside);
+    let absolute = format!("{{{{include-lines {}:1:1}}}}\n", dir.path().join("foo.rs").display());
+    assert_eq!(expand(&absolute), absolute);
+}
+
 #[cfg(test)]
 #[test]
 fn test_name_key() {
@@ -4186,6 +7507,22 @@ fn test_name_key() {
     assert_eq!(name_key("Fruit01"), ("Fruit", 1, 1));
     assert_eq!(name_key("Fruit10"), ("Fruit", 10, 0));
     assert_eq!(name_key("Fruit123"), ("Fruit", 123, 0));
+    assert_eq!(name_key("café"), ("café", 0, 0));
+    assert_eq!(name_key("café2"), ("café", 2, 0));
+    assert_eq!(name_key("café007"), ("café", 7, 2));
+    assert_eq!(name_key("日本10"), ("日本", 10, 0));
+    assert_eq!(name_key("１２"), ("１２", 0, 0));
+}
+
+#[cfg(test)]
+#[test]
+fn test_version_sorting() {
+    let versions = ["0.9.0", "1.0.0", "1.2.0", "1.9.1", "1.10.0-alpha", "1.10.0-beta",
+                    "1.10.0-beta.2", "1.10.0-beta.11", "1.10.0", "2.0.0"];
+    let mut sorted = versions.to_owned();
+    sorted.reverse();
+    sorted.sort_by_key(|&v| version_key(v));
+    assert_eq!(versions, sorted);
 }
 
 #[cfg(test)]
@@ -4193,6 +7530,7 @@ fn test_name_key() {
 fn test_name_sorting() {
     let names = ["Apple",
                  "Banana",
+                 "Café", "Café2", "Café10", "Cafés",
                  "Fruit", "Fruit0", "Fruit00",
                  "Fruit1", "Fruit01",
                  "Fruit2", "Fruit02",
@@ -4203,3 +7541,204 @@ fn test_name_sorting() {
     sorted.sort_by_key(|&s| name_key(s));
     assert_eq!(names, sorted);
 }
+
+#[cfg(test)]
+#[test]
+fn test_memory_sink() {
+    let sink = MemorySink::default();
+    let handle = sink.clone();
+    sink.write(Path::new("foo/index.html"), b"index").unwrap();
+    sink.write(Path::new("foo/index.html"), b"new index").unwrap();
+    sink.write_new(Path::new("foo/bar.html"), b"bar").unwrap();
+    sink.write_new(Path::new("foo/bar.html"), b"other bar").unwrap();
+
+    let files = handle.files.borrow();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[Path::new("foo/index.html")], b"new index");
+    assert_eq!(files[Path::new("foo/bar.html")], b"bar");
+}
+
+#[cfg(test)]
+#[test]
+fn test_truncation() {
+    let long = "abcdefghij".repeat(8);
+
+    let middle = Truncation::default();
+    assert_eq!(middle.truncate("short", SUMMARY_LEN), "short");
+    assert_eq!(middle.truncate(&long, SUMMARY_LEN),
+               format!("{} ... {}", &long[..50], &long[60..]));
+
+    let end = Truncation::new(TruncateAt::End, None);
+    assert_eq!(end.truncate("short", SUMMARY_LEN), "short");
+    assert_eq!(end.truncate(&long, SUMMARY_LEN), format!("{}...", &long[..70]));
+
+    let custom = Truncation::new(TruncateAt::End, Some("\u{2026}".to_string()));
+    assert_eq!(custom.truncate(&long, 10), "abcdefghij\u{2026}");
+
+    // Cuts never happen in the middle of a character.
+    let wide = "\u{e9}".repeat(40);
+    assert_eq!(middle.truncate(&wide, SUMMARY_LEN),
+               format!("{} ... {}", &wide[..50], &wide[60..]));
+    assert_eq!(end.truncate(&wide, 9), format!("{}...", &wide[..8]));
+    assert_eq!(Truncation::new(TruncateAt::Middle, Some("~".to_string())).truncate(&wide, 9),
+               format!("{}~{}", &wide[..6], &wide[78..]));
+}
+
+#[cfg(test)]
+#[test]
+fn test_split_highlighted_lines() {
+    assert_eq!(split_highlighted_lines("fn main() {}\n"), vec!["fn main() {}"]);
+    assert_eq!(split_highlighted_lines("a\n\nb"), vec!["a", "", "b"]);
+    assert_eq!(split_highlighted_lines("<span class=\"comment\">/* a\nb */</span> c\nd"),
+               vec!["<span class=\"comment\">/* a</span>",
+                    "<span class=\"comment\">b */</span> c",
+                    "d"]);
+}
+
+#[cfg(all(test, unix))]
+#[test]
+fn test_clean_srcpath_non_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut p = PathBuf::from("/src/foo");
+    p.push(OsStr::from_bytes(b"b\xffr"));
+    p.push("lib.rs");
+
+    let mut components = Vec::new();
+    clean_srcpath(Path::new("/src"), &p, true, |c| components.push(c.to_string()));
+    assert_eq!(components, vec!["foo", "b\u{fffd}r", "lib.rs"]);
+
+    components.clear();
+    clean_srcpath(Path::new("/src"), &p, false, |c| components.push(c.to_string()));
+    assert_eq!(components, vec!["foo", "b\u{fffd}r"]);
+}
+
+#[cfg(test)]
+#[test]
+fn test_crate_entry() {
+    assert_eq!(crate_entry("searchIndex", "foo"), r#"searchIndex["foo"]"#);
+    assert_eq!(crate_entry("implementors", r#"a"b\c"#), r#"implementors["a\"b\\c"]"#);
+
+    // The lines written for a crate are the ones replaced when it is documented again.
+    let sink = MemorySink::default();
+    let path = Path::new("doc/search-index.js");
+    let krate = r#"we"ird"#;
+    let lines = format!("var searchIndex = {{}};\n{} = {{}};\n{} = {{}};\n",
+                        crate_entry("searchIndex", krate), crate_entry("searchIndex", "std"));
+    sink.write(path, lines.as_bytes()).unwrap();
+    assert_eq!(collect(&sink, path, krate, "searchIndex").unwrap(),
+               [r#"searchIndex["std"] = {};"#]);
+
+    // The entries of other objects sharing the prefix of `key` are left out.
+    let lines = format!("{} = {};\n", crate_entry("searchIndexFiles", "std"),
+                        as_json(&"search-index-std.js".to_string()));
+    sink.write(path, lines.as_bytes()).unwrap();
+    assert!(collect(&sink, path, krate, "searchIndex").unwrap().is_empty());
+    assert_eq!(collect(&sink, path, krate, "searchIndexFiles").unwrap(),
+               [r#"searchIndexFiles["std"] = "search-index-std.js";"#]);
+}
+
+#[cfg(test)]
+#[test]
+fn test_index_words() {
+    assert_eq!(index_words("HashMap"), Some("hash map".to_string()));
+    assert_eq!(index_words("into_iter"), Some("into iter".to_string()));
+    assert_eq!(index_words("HTMLParser"), Some("html parser".to_string()));
+    assert_eq!(index_words("Utf8Error"), Some("utf8 error".to_string()));
+    assert_eq!(index_words("__private_field"), Some("private field".to_string()));
+    assert_eq!(index_words("CONST_VALUE"), Some("const value".to_string()));
+    assert_eq!(index_words("u8"), None);
+    assert_eq!(index_words("String"), None);
+    assert_eq!(index_words("FOO"), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_run_with_transform() {
+    use rustc::hir::def_id::{DefIndex, LOCAL_CRATE};
+
+    fn module(src: &Path, name: &str, index: u32, items: Vec<clean::Item>) -> clean::Item {
+        clean::Item {
+            source: Span {
+                filename: FileName::Real(src.to_path_buf()),
+                loline: 1,
+                locol: 0,
+                hiline: 1,
+                hicol: 14,
+            },
+            name: Some(name.to_string()),
+            attrs: Default::default(),
+            inner: clean::ModuleItem(clean::Module { items, is_crate: index == 0 }),
+            visibility: Some(clean::Public),
+            def_id: DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(index) },
+            stability: None,
+            deprecation: None,
+        }
+    }
+
+    let dir = ::tempdir::TempDir::new("rustdoc-test").unwrap();
+    let src = dir.path().join("lib.rs");
+    File::create(&src).unwrap().write_all(b"pub mod bar {}\n").unwrap();
+    let bar = module(&src, "bar", 1, Vec::new());
+    let krate = clean::Crate {
+        name: "foo".to_string(),
+        version: None,
+        src: FileName::Real(src.clone()),
+        module: Some(module(&src, "foo", 0, vec![bar])),
+        externs: Vec::new(),
+        primitives: Vec::new(),
+        access_levels: Arc::new(AccessLevels::default()),
+        external_traits: FxHashMap(),
+        masked_crates: FxHashSet(),
+    };
+
+    let transform: HtmlTransform = Arc::new(|html: &str| {
+        html.replace("</body>", "<!-- transformed --></body>")
+    });
+    let options = RenderOptions { html_transform: Some(transform), ..RenderOptions::default() };
+    let sink = MemorySink::default();
+    let dst = dir.path().join("doc");
+    let external_html = ExternalHtml {
+        in_header: String::new(),
+        before_content: String::new(),
+        after_content: String::new(),
+    };
+    run(krate, &external_html, None, dst.clone(), FxHashSet(), None,
+        RenderInfo::default(), RenderType::Pulldown, options, Box::new(sink.clone())).unwrap();
+
+    let files = sink.files.borrow();
+    for page in &["foo/index.html", "foo/bar/index.html", "src/foo/lib.rs.html"] {
+        let html = str::from_utf8(&files[&dst.join(page)]).unwrap();
+        assert!(html.contains("<!-- transformed --></body>"), "{} wasn't transformed", page);
+    }
+    // Nothing is written to disk.
+    assert!(!dst.exists());
+}
+
+#[cfg(test)]
+#[test]
+fn test_fnv_hash() {
+    assert_eq!(fnv_hash(b""), 0xcbf29ce484222325);
+    assert_eq!(fnv_hash(b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(fnv_hash(b"foobar"), 0x85944171f73967e8);
+}
+
+#[cfg(test)]
+#[test]
+fn test_collect_redirects() {
+    let dir = ::tempdir::TempDir::new("rustdoc-test").unwrap();
+    let sink = FsSink::default();
+    let path = dir.path().join("redirects.json");
+    assert!(collect_redirects(&sink, &path, "foo").unwrap().is_empty());
+
+    // The redirections of the crate being documented again are replaced.
+    sink.write(&path, br#"{
+        "foo/Foo.t.html": "foo/struct.Foo.html",
+        "src/foo/a.rs.html": "src/foo/b.rs.html",
+        "foobar/Bar.t.html": "foobar/struct.Bar.html"
+    }"#).unwrap();
+    let redirects = collect_redirects(&sink, &path, "foo").unwrap();
+    assert_eq!(redirects.into_iter().collect::<Vec<_>>(),
+               [("foobar/Bar.t.html".to_string(), "foobar/struct.Bar.html".to_string())]);
+}
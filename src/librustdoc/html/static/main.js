@@ -275,6 +275,18 @@
         }
     }
 
+    // Line numbers may be nested in the collapsible `src-item` spans of the
+    // source view, so look for the enclosing `line-numbers` block.
+    function isLineNumber(elem) {
+        for (var e = elem.parentNode; e; e = e.parentNode) {
+            if (hasClass(e, 'src-item') || hasClass(e, 'src-item-body')) {
+                continue;
+            }
+            return hasClass(e, 'line-numbers');
+        }
+        return false;
+    }
+
     document.onkeypress = handleShortcut;
     document.onkeydown = handleShortcut;
     document.onclick = function(ev) {
@@ -282,7 +294,7 @@
             collapseDocs(ev.target);
         } else if (hasClass(ev.target.parentNode, 'collapse-toggle')) {
             collapseDocs(ev.target.parentNode);
-        } else if (ev.target.tagName === 'SPAN' && hasClass(ev.target.parentNode, 'line-numbers')) {
+        } else if (ev.target.tagName === 'SPAN' && isLineNumber(ev.target)) {
             var prev_id = 0;
 
             var set_fragment = function (name) {
@@ -375,7 +387,7 @@
     })();
 
     function initSearch(rawSearchIndex) {
-        var currentResults, index, searchIndex;
+        var currentResults, index, searchIndex, searchAliases;
         var MAX_LEV_DISTANCE = 3;
         var MAX_RESULTS = 200;
         var params = getQueryStringParams();
@@ -439,6 +451,11 @@
                 for (var i = 0; i < nresults; ++i) {
                     results[i].word = searchWords[results[i].id];
                     results[i].item = searchIndex[results[i].id] || {};
+                    // whether the query is one of the words of a multi-word name, as `map`
+                    // is for `HashMap`
+                    var words = results[i].item.words;
+                    results[i].wordMatch = typeof words === "string" &&
+                        words.split(" ").indexOf(valLower) !== -1;
                 }
                 // if there are no results then return to default and fail
                 if (results.length === 0) {
@@ -464,6 +481,11 @@
                     b = (bbb.word !== valLower);
                     if (a !== b) { return a - b; }
 
+                    // sort by word match (names without a word matching the query go later)
+                    a = !aaa.wordMatch;
+                    b = !bbb.wordMatch;
+                    if (a !== b) { return a - b; }
+
                     // sort by item name length (longer goes later)
                     a = aaa.word.length;
                     b = bbb.word.length;
@@ -969,6 +991,22 @@
                         results[fullId].lev = Math.min(results[fullId].lev, lev);
                     }
                 }
+
+                // items with a `doc(alias)` matching the query exactly
+                if (searchAliases.hasOwnProperty(val)) {
+                    var aliased = searchAliases[val];
+                    for (j = 0; j < aliased.length; ++j) {
+                        var ty = searchIndex[aliased[j]];
+                        if (!ty || typePassesFilter(typeFilter, ty.ty) === false) {
+                            continue;
+                        }
+                        results[generateId(ty)] = {
+                            id: aliased[j],
+                            index: -1,
+                            lev: 0,
+                        };
+                    }
+                }
             }
 
             return {
@@ -1190,7 +1228,11 @@
                     output += '<tr class="' + type + ' result"><td>' +
                               '<a href="' + href + '">' +
                               displayPath + '<span class="' + type + '">' +
-                              name + '</span></a></td><td>' +
+                              name + '</span>' +
+                              (item.since ? '<span class="since" title="Stable since Rust ' +
+                                            'version ' + escape(item.since) + '">' +
+                                            escape(item.since) + '</span>' : '') +
+                              '</a></td><td>' +
                               '<a href="' + href + '">' +
                               '<span class="desc">' + escape(item.desc) +
                               '&nbsp;</span></a></td></tr>';
@@ -1295,6 +1337,7 @@
 
         function buildIndex(rawSearchIndex) {
             searchIndex = [];
+            searchAliases = {};
             var searchWords = [];
             for (var crate in rawSearchIndex) {
                 if (!rawSearchIndex.hasOwnProperty(crate)) { continue; }
@@ -1315,6 +1358,8 @@
                 //              (String) description,
                 //              (Number | null) the parent path index to `paths`]
                 //              (Object | null) the type of the function (if any)
+                //              (String | null) the version it was stabilized in (if any)
+                //              (String) the lowercase words of its name (if several)
                 var items = rawSearchIndex[crate].items;
                 // an array of [(Number) item type,
                 //              (String) name]
@@ -1335,11 +1380,13 @@
                 // faster analysis operations
                 var len = items.length;
                 var lastPath = "";
+                var start = searchIndex.length;
                 for (var i = 0; i < len; ++i) {
                     var rawRow = items[i];
                     var row = {crate: crate, ty: rawRow[0], name: rawRow[1],
                                path: rawRow[2] || lastPath, desc: rawRow[3],
-                               parent: paths[rawRow[4]], type: rawRow[5]};
+                               parent: paths[rawRow[4]], type: rawRow[5],
+                               since: rawRow[6], words: rawRow[7]};
                     searchIndex.push(row);
                     if (typeof row.name === "string") {
                         var word = row.name.toLowerCase();
@@ -1349,6 +1396,19 @@
                     }
                     lastPath = row.path;
                 }
+
+                // an object mapping each (lowercase) `doc(alias)` to the indices
+                // of the aliased items in `items`
+                var aliases = rawSearchIndex[crate].aliases || {};
+                for (var alias in aliases) {
+                    if (!aliases.hasOwnProperty(alias)) { continue; }
+                    if (!searchAliases.hasOwnProperty(alias)) {
+                        searchAliases[alias] = [];
+                    }
+                    for (var k = 0; k < aliases[alias].length; ++k) {
+                        searchAliases[alias].push(start + aliases[alias][k]);
+                    }
+                }
             }
             return searchWords;
         }
@@ -1482,6 +1542,47 @@
 
     window.initSearch = initSearch;
 
+    // When the search index is split per crate, `search-index.js` only maps
+    // each crate to the file holding its index. Those files are fetched the
+    // first time the search is used, and the search is set up once they have
+    // all been loaded.
+    function loadSearchIndexes(files) {
+        var search_input = document.getElementsByClassName("search-input")[0];
+        var loading = false;
+        var load = function() {
+            if (loading) {
+                return;
+            }
+            loading = true;
+            var pending = 0;
+            var done = function() {
+                pending -= 1;
+                if (pending === 0) {
+                    initSearch(window.searchIndex);
+                }
+            };
+            for (var crate in files) {
+                if (!files.hasOwnProperty(crate)) {
+                    continue;
+                }
+                pending += 1;
+                var script = document.createElement('script');
+                script.src = window.rootPath + files[crate];
+                // A missing index must not keep the search from working with the others.
+                script.onload = script.onerror = done;
+                document.body.appendChild(script);
+            }
+        };
+
+        if (getQueryStringParams().search || !search_input) {
+            load();
+        } else {
+            search_input.addEventListener('focus', load);
+        }
+    }
+
+    window.loadSearchIndexes = loadSearchIndexes;
+
     // delayed sidebar rendering.
     function initSidebarItems(items) {
         var sidebar = document.getElementsByClassName('sidebar-elems')[0];
@@ -1526,18 +1627,28 @@
             sidebar.appendChild(div);
         }
 
-        block("primitive", "Primitive Types");
-        block("mod", "Modules");
-        block("macro", "Macros");
-        block("struct", "Structs");
-        block("enum", "Enums");
-        block("union", "Unions");
-        block("constant", "Constants");
-        block("static", "Statics");
-        block("trait", "Traits");
-        block("fn", "Functions");
-        block("type", "Type Definitions");
-        block("foreigntype", "Foreign Types");
+        var longTypes = {
+            "primitive": "Primitive Types",
+            "mod": "Modules",
+            "macro": "Macros",
+            "struct": "Structs",
+            "enum": "Enums",
+            "union": "Unions",
+            "constant": "Constants",
+            "static": "Statics",
+            "trait": "Traits",
+            "fn": "Functions",
+            "type": "Type Definitions",
+            "foreigntype": "Foreign Types",
+        };
+
+        // The categories are already in the order of the module listing.
+        var shorttys = Object.keys(items);
+        for (var i = 0; i < shorttys.length; ++i) {
+            if (longTypes.hasOwnProperty(shorttys[i])) {
+                block(shorttys[i], longTypes[shorttys[i]]);
+            }
+        }
     }
 
     window.initSidebarItems = initSidebarItems;
@@ -1804,6 +1915,120 @@
                 e.previousElementSibling.childNodes[0].style.color = '';
             });
         }
+        if (e.getElementsByClassName('hidden-line').length > 0) {
+            var toggle = document.createElement('a');
+            toggle.href = 'javascript:void(0)';
+            toggle.className = 'hidden-lines-toggle';
+            toggle.innerHTML = 'Show hidden lines';
+            toggle.onclick = function() {
+                if (hasClass(e, 'show-hidden')) {
+                    removeClass(e, 'show-hidden');
+                    toggle.innerHTML = 'Show hidden lines';
+                } else {
+                    addClass(e, 'show-hidden');
+                    toggle.innerHTML = 'Hide hidden lines';
+                }
+            };
+            e.appendChild(toggle);
+        }
+    });
+
+    onEach(document.getElementsByClassName('foreign-impls-crate'), function(e) {
+        var group = e.nextElementSibling;
+        var toggle = document.createElement('a');
+        toggle.href = 'javascript:void(0)';
+        toggle.className = 'collapse-toggle';
+        toggle.innerHTML = "[<span class='inner'>" + labelForToggleButton(false) + "</span>]";
+        toggle.onclick = function() {
+            var collapse = !hasClass(group, 'collapsed');
+            if (collapse) {
+                addClass(group, 'collapsed');
+            } else {
+                removeClass(group, 'collapsed');
+            }
+            toggle.innerHTML = "[<span class='inner'>" + labelForToggleButton(collapse) +
+                               "</span>]";
+        };
+        e.insertBefore(toggle, e.firstChild);
+    });
+
+    onEach(['synthetic-implementations', 'blanket-implementations'], function(id) {
+        var header = document.getElementById(id);
+        if (!header) {
+            return;
+        }
+        var group = header.nextElementSibling;
+        var toggle = document.createElement('a');
+        toggle.href = 'javascript:void(0)';
+        toggle.className = 'collapse-toggle';
+        toggle.innerHTML = "[<span class='inner'>" + labelForToggleButton(true) + "</span>]";
+        toggle.onclick = function() {
+            var collapse = !hasClass(group, 'collapsed');
+            if (collapse) {
+                addClass(group, 'collapsed');
+            } else {
+                removeClass(group, 'collapsed');
+            }
+            toggle.innerHTML = "[<span class='inner'>" + labelForToggleButton(collapse) +
+                               "</span>]";
+        };
+        header.insertBefore(toggle, header.firstChild);
+        // Expand the section if the page was opened on one of its impls.
+        var target = document.getElementById(window.location.hash.substring(1));
+        if (target && group.contains(target)) {
+            toggle.onclick();
+        }
+    });
+
+    onEach(document.querySelectorAll('pre.line-numbers .src-item'), function(e) {
+        var start = e.getAttribute('data-item-start');
+        var toggle = document.createElement('a');
+        toggle.href = 'javascript:void(0)';
+        toggle.className = 'src-item-toggle';
+        toggle.innerHTML = '[<span class="inner">-</span>]';
+        toggle.onclick = function() {
+            var collapse = !hasClass(e, 'collapsed');
+            onEach(document.querySelectorAll('.src-item[data-item-start="' + start + '"]'),
+                   function(item) {
+                if (collapse) {
+                    addClass(item, 'collapsed');
+                } else {
+                    removeClass(item, 'collapsed');
+                }
+            });
+            toggle.innerHTML = '[<span class="inner">' + (collapse ? '+' : '-') + '</span>]';
+        };
+        e.insertBefore(toggle, e.firstChild);
+    });
+
+    function copyToClipboard(text) {
+        var area = document.createElement('textarea');
+        area.value = text;
+        area.className = 'permalink-copy-area';
+        document.body.appendChild(area);
+        area.select();
+        try {
+            document.execCommand('copy');
+        } catch (e) {}
+        document.body.removeChild(area);
+    }
+
+    onEach(document.querySelectorAll('a.anchor[data-permalink]'), function(e) {
+        var copy = document.createElement('a');
+        copy.href = 'javascript:void(0)';
+        copy.className = 'copy-permalink';
+        copy.title = 'Copy permalink';
+        copy.onclick = function() {
+            // Resolve the relative permalink against the current page.
+            var link = document.createElement('a');
+            link.href = e.getAttribute('data-permalink');
+            copyToClipboard(link.href);
+            addClass(copy, 'copied');
+            setTimeout(function() {
+                removeClass(copy, 'copied');
+            }, 1000);
+        };
+        e.parentNode.insertBefore(copy, e.nextSibling);
     });
 
     function showModal(content) {
@@ -1848,7 +2073,7 @@
     }
 
     var params = getQueryStringParams();
-    if (params && params.search) {
+    if (search_input && params && params.search) {
         addClass(document.getElementById("main"), "hidden");
         var search = document.getElementById("search");
         removeClass(search, "hidden");
@@ -1870,9 +2095,38 @@
     window.onresize = function() {
         hideSidebar();
     };
+
+    function switchTheme(name) {
+        onEach(document.querySelectorAll('link[data-theme]'), function(e) {
+            // Toggling back and forth makes browsers apply alternate stylesheets.
+            e.disabled = true;
+            e.disabled = e.getAttribute('data-theme') !== name;
+        });
+        try {
+            window.localStorage.setItem('rustdoc-theme', name);
+        } catch (e) {}
+    }
+
+    var themePicker = document.getElementById('theme-picker');
+    if (themePicker) {
+        var savedTheme = null;
+        try {
+            savedTheme = window.localStorage.getItem('rustdoc-theme');
+        } catch (e) {}
+        if (savedTheme && window.themes.indexOf(savedTheme) !== -1) {
+            themePicker.value = savedTheme;
+            switchTheme(savedTheme);
+        }
+        themePicker.onchange = function() {
+            switchTheme(themePicker.value);
+        };
+    }
 }());
 
 // Sets the focus on the search bar at the top of the page
 function focusSearchBar() {
-    document.getElementsByClassName('search-input')[0].focus();
+    var search_input = document.getElementsByClassName('search-input')[0];
+    if (search_input) {
+        search_input.focus();
+    }
 }
@@ -52,7 +52,7 @@ extern crate tempdir;
 
 extern crate serialize as rustc_serialize; // used by deriving
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::default::Default;
 use std::env;
 use std::fmt::Display;
@@ -62,7 +62,7 @@ use std::path::{Path, PathBuf};
 use std::process;
 use std::sync::mpsc::channel;
 
-use externalfiles::ExternalHtml;
+use externalfiles::{ExternalHtml, LoadStringError, load_string};
 use rustc::session::search_paths::SearchPaths;
 use rustc::session::config::{ErrorOutputType, RustcOptGroup, nightly_options,
                              Externs};
@@ -94,6 +94,7 @@ pub mod test;
 use clean::AttributesExt;
 
 use html::markdown::RenderType;
+use html::render::{TruncateAt, Truncation};
 
 struct Output {
     krate: clean::Crate,
@@ -140,7 +141,7 @@ pub fn opts() -> Vec<RustcOptGroup> {
                      "[rust]")
         }),
         stable("w", |o| {
-            o.optopt("w", "output-format", "the output type to write", "[html]")
+            o.optopt("w", "output-format", "the output type to write", "[html|markdown]")
         }),
         stable("o", |o| o.optopt("o", "output", "where to place the output", "PATH")),
         stable("crate-name", |o| {
@@ -257,6 +258,235 @@ pub fn opts() -> Vec<RustcOptGroup> {
             o.optflag("", "sort-modules-by-appearance", "sort modules by where they appear in the \
                                                          program, rather than alphabetically")
         }),
+        unstable("show-impl-source-files", |o| {
+            o.optflag("", "show-impl-source-files", "show the source file and line where each \
+                                                     inherent impl block is defined")
+        }),
+        unstable("show-panics-badge", |o| {
+            o.optflag("", "show-panics-badge", "mark functions and methods with a `# Panics` \
+                                                section in their documentation as possibly \
+                                                panicking")
+        }),
+        unstable("show-hidden-lines", |o| {
+            o.optflag("", "show-hidden-lines", "keep hidden lines of code examples in the \
+                                                documentation, revealed on demand")
+        }),
+        unstable("show-dyn-signatures", |o| {
+            o.optflag("", "show-dyn-signatures", "show how object-safe trait methods look when \
+                                                  called through a trait object")
+        }),
+        unstable("emit-anchors", |o| {
+            o.optflag("", "emit-anchors", "write an `anchors.json` file listing the anchor ids \
+                                           of every generated page")
+        }),
+        unstable("assoc-consts-section", |o| {
+            o.optflag("", "assoc-consts-section", "gather the inherent associated constants of \
+                                                   a type in their own section")
+        }),
+        unstable("type-aliases-page", |o| {
+            o.optflag("", "type-aliases-page", "generate a page listing every type alias of the \
+                                                crate along with its target")
+        }),
+        unstable("show-chainable-methods", |o| {
+            o.optflag("", "show-chainable-methods", "mark methods returning the type they are \
+                                                     defined on as chainable")
+        }),
+        unstable("enable-include-lines", |o| {
+            o.optflag("", "enable-include-lines", "expand `{{include-lines path:start:end}}` \
+                                                   directives in documentation into the given \
+                                                   lines of a source file")
+        }),
+        unstable("show-coherence-notes", |o| {
+            o.optflag("", "show-coherence-notes", "explain which side of implementations on \
+                                                   foreign types makes them coherent")
+        }),
+        unstable("api-snapshot", |o| {
+            o.optflag("", "api-snapshot", "write the normalized signature of every public item \
+                                           to `api-snapshot.txt`")
+        }),
+        unstable("stabilized-page", |o| {
+            o.optflag("", "stabilized-page", "generate a page listing the stable items of the \
+                                              crate by the version they were stabilized in")
+        }),
+        unstable("group-where-bounds", |o| {
+            o.optflag("", "group-where-bounds", "merge the where clause bounds constraining the \
+                                                 same type")
+        }),
+        unstable("show-type-usage", |o| {
+            o.optflag("", "show-type-usage", "show how many functions of the crate use a type \
+                                              in their signature")
+        }),
+        unstable("rtl", |o| {
+            o.optflag("", "rtl", "lay out the generated pages right-to-left")
+        }),
+        unstable("module-since-column", |o| {
+            o.optflag("", "module-since-column", "show the version each item was stabilized in \
+                                                  in module listings")
+        }),
+        unstable("related-crate", |o| {
+            o.optmulti("", "related-crate", "name of a crate to link to from the crate root",
+                       "NAME")
+        }),
+        unstable("show-import-path", |o| {
+            o.optflag("", "show-import-path", "show the `use` statement importing each item")
+        }),
+        unstable("summary-truncation", |o| {
+            o.optopt("", "summary-truncation",
+                     "shorten long summaries by cutting them in the middle or at the end",
+                     "[middle|end]")
+        }),
+        unstable("summary-ellipsis", |o| {
+            o.optopt("", "summary-ellipsis", "the string replacing the cut part of summaries",
+                     "STRING")
+        }),
+        unstable("at-a-glance", |o| {
+            o.optflag("", "at-a-glance", "summarize the key facts about each item below its \
+                                          heading")
+        }),
+        unstable("sort-impls", |o| {
+            o.optflag("", "sort-impls", "list trait implementations alphabetically")
+        }),
+        unstable("collapsible-source", |o| {
+            o.optflag("", "collapsible-source", "allow collapsing each item in the source view")
+        }),
+        unstable("extern-html-root-url", |o| {
+            o.optmulti("", "extern-html-root-url",
+                       "base URL to use for links to the documentation of an external crate",
+                       "NAME=URL")
+        }),
+        unstable("split-search-index", |o| {
+            o.optflag("", "split-search-index",
+                      "write each crate's search index to its own file, loaded on demand")
+        }),
+        unstable("stable-impl-item-ids", |o| {
+            o.optflag("", "stable-impl-item-ids",
+                      "derive the ids of trait impl items from a hash of the impl")
+        }),
+        unstable("commonmark-only", |o| {
+            o.optflag("", "commonmark-only",
+                      "render docs with commonmark only, without comparing them to hoedown")
+        }),
+        unstable("index-page", |o| {
+            o.optopt("", "index-page",
+                     "markdown file to render as the landing page listing the documented crates",
+                     "PATH")
+        }),
+        unstable("index-page-crate", |o| {
+            o.optmulti("", "index-page-crate",
+                       "name of another crate documented in the output directory, to list on \
+                        the landing page",
+                       "NAME")
+        }),
+        unstable("theme", |o| {
+            o.optmulti("", "theme",
+                       "additional CSS theme, selectable from the theme picker of the generated \
+                        pages",
+                       "PATH")
+        }),
+        unstable("generate-link-to-definition", |o| {
+            o.optflag("", "generate-link-to-definition",
+                      "make the paths of the rendered source code link to the documentation of \
+                       the items they refer to")
+        }),
+        unstable("emit-structured-data", |o| {
+            o.optflag("", "emit-structured-data",
+                      "embed a JSON-LD description of the item in each item page")
+        }),
+        unstable("assoc-const-value-len", |o| {
+            o.optopt("", "assoc-const-value-len",
+                     "the length past which the values of associated constants are shortened",
+                     "BYTES")
+        }),
+        unstable("sitemap-base-url", |o| {
+            o.optopt("", "sitemap-base-url",
+                     "write a sitemap.xml of the item pages, hosted under the given URL",
+                     "URL")
+        }),
+        unstable("signatures-only", |o| {
+            o.optflag("", "signatures-only",
+                      "leave the documentation out of the item pages, keeping only signatures")
+        }),
+        unstable("deny-file-collisions", |o| {
+            o.optflag("", "deny-file-collisions",
+                      "fail when the pages of two items would be the same file on \
+                       case-insensitive file systems")
+        }),
+        unstable("absolute-links", |o| {
+            o.optflag("", "absolute-links",
+                      "link the pages to each other and to the sources through the \
+                       `html_root_url` of the crate, if any")
+        }),
+        unstable("emit-cache-json", |o| {
+            o.optflag("", "emit-cache-json",
+                      "write a `cache.json` file describing the items, trait implementors and \
+                       external crates known to rustdoc")
+        }),
+        unstable("group-by-cfg", |o| {
+            o.optflag("", "group-by-cfg",
+                      "group the items listed on module pages by the platforms they are \
+                       available on")
+        }),
+        unstable("show-on-unimplemented", |o| {
+            o.optflag("", "show-on-unimplemented",
+                      "show the message of `#[rustc_on_unimplemented]` on the page of traits")
+        }),
+        unstable("hide-stable-since", |o| {
+            o.optflag("", "hide-stable-since",
+                      "don't show the version items were stabilized in next to their declarations")
+        }),
+        unstable("no-search", |o| {
+            o.optflag("", "no-search",
+                      "don't build a search index, and leave the search box out of the pages")
+        }),
+        unstable("favicon-url", |o| {
+            o.optopt("", "favicon-url",
+                     "use the given favicon, in place of the `html_favicon_url` of the crate",
+                     "URL")
+        }),
+        unstable("logo-url", |o| {
+            o.optopt("", "logo-url",
+                     "use the given logo, in place of the `html_logo_url` of the crate",
+                     "URL")
+        }),
+        unstable("check-links", |o| {
+            o.optflag("", "check-links",
+                      "warn about relative links of the documentation to pages or sections which \
+                       aren't generated")
+        }),
+        unstable("html-footer", |o| {
+            o.optopt("", "html-footer",
+                     "file containing HTML to insert as is in the footer of every page",
+                     "PATH")
+        }),
+        unstable("hashed-assets", |o| {
+            o.optflag("", "hashed-assets",
+                      "name the shared stylesheets and scripts after a hash of their contents, \
+                       so that browsers don't keep stale copies of them")
+        }),
+        unstable("collapse-impls", |o| {
+            o.optflag("", "collapse-impls", "collapse the impl blocks of every page by default")
+        }),
+        unstable("read-more-text", |o| {
+            o.optopt("", "read-more-text",
+                     "text of the link following truncated summaries, `Read more` by default",
+                     "TEXT")
+        }),
+        unstable("extern-src-root", |o| {
+            o.optmulti("", "extern-src-root",
+                       "base URL of the sources of an external crate, such as `file:///path/`, \
+                        used for source links when its documentation couldn't be found",
+                       "NAME=URL")
+        }),
+        unstable("emit-redirect-map", |o| {
+            o.optflag("", "emit-redirect-map",
+                      "also list the redirection pages in a `redirects.json` file, mapping their \
+                       paths to the paths of their targets")
+        }),
+        unstable("deny-markdown-warnings", |o| {
+            o.optflag("", "deny-markdown-warnings",
+                      "fail when the Hoedown and Pulldown renderers render the documentation \
+                       differently")
+        }),
     ]
 }
 
@@ -342,7 +572,8 @@ pub fn main_args(args: &[String]) -> isize {
     let css_file_extension = matches.opt_str("e").map(|s| PathBuf::from(&s));
     let cfgs = matches.opt_strs("cfg");
 
-    let render_type = if matches.opt_present("enable-commonmark") {
+    let commonmark_only = matches.opt_present("commonmark-only");
+    let render_type = if matches.opt_present("enable-commonmark") || commonmark_only {
         RenderType::Pulldown
     } else {
         RenderType::Hoedown
@@ -358,6 +589,19 @@ pub fn main_args(args: &[String]) -> isize {
         }
     }
 
+    let themes = matches.opt_strs("theme").iter().map(PathBuf::from).collect::<Vec<_>>();
+    for theme in &themes {
+        // The name of the theme ends up in URLs and HTML attributes, keep it simple.
+        let valid_name = theme.file_stem().and_then(|s| s.to_str()).map_or(false, |name| {
+            name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        });
+        if !theme.is_file() || !valid_name {
+            print_error(format!("option --theme argument must be a CSS file named with \
+                                 letters, digits, `-` and `_` only: {}", theme.display()));
+            return 1;
+        }
+    }
+
     let external_html = match ExternalHtml::load(
             &matches.opt_strs("html-in-header"),
             &matches.opt_strs("html-before-content"),
@@ -374,6 +618,105 @@ pub fn main_args(args: &[String]) -> isize {
     let display_warnings = matches.opt_present("display-warnings");
     let linker = matches.opt_str("linker").map(PathBuf::from);
     let sort_modules_alphabetically = !matches.opt_present("sort-modules-by-appearance");
+    let show_impl_source_files = matches.opt_present("show-impl-source-files");
+    let show_panics_badge = matches.opt_present("show-panics-badge");
+    let show_hidden_lines = matches.opt_present("show-hidden-lines");
+    let show_dyn_signatures = matches.opt_present("show-dyn-signatures");
+    let emit_anchors = matches.opt_present("emit-anchors");
+    let assoc_consts_section = matches.opt_present("assoc-consts-section");
+    let type_aliases_page = matches.opt_present("type-aliases-page");
+    let show_chainable_methods = matches.opt_present("show-chainable-methods");
+    let include_lines = matches.opt_present("enable-include-lines");
+    let show_coherence_notes = matches.opt_present("show-coherence-notes");
+    let api_snapshot = matches.opt_present("api-snapshot");
+    let stabilized_page = matches.opt_present("stabilized-page");
+    let group_where_bounds = matches.opt_present("group-where-bounds");
+    let show_type_usage = matches.opt_present("show-type-usage");
+    let rtl = matches.opt_present("rtl");
+    let module_since_column = matches.opt_present("module-since-column");
+    let related_crates = matches.opt_strs("related-crate");
+    let show_import_path = matches.opt_present("show-import-path");
+    let summary_truncation = match (matches.opt_str("summary-truncation"),
+                                    matches.opt_str("summary-ellipsis")) {
+        (None, None) => None,
+        (at, ellipsis) => {
+            let at = match at.as_ref().map(|s| &**s) {
+                Some("middle") | None => TruncateAt::Middle,
+                Some("end") => TruncateAt::End,
+                Some(s) => {
+                    print_error(format!("unknown summary truncation: {}", s));
+                    return 1;
+                }
+            };
+            Some(Truncation::new(at, ellipsis))
+        }
+    };
+    let at_a_glance = matches.opt_present("at-a-glance");
+    let sort_impls = matches.opt_present("sort-impls");
+    let collapsible_source = matches.opt_present("collapsible-source");
+    let split_search_index = matches.opt_present("split-search-index");
+    let stable_impl_item_ids = matches.opt_present("stable-impl-item-ids");
+    let index_page = match matches.opt_str("index-page") {
+        Some(path) => match load_string(&path) {
+            Ok(s) => Some(s),
+            Err(LoadStringError::ReadFail) => return 1,
+            Err(LoadStringError::BadUtf8) => return 2,
+        },
+        None => None,
+    };
+    let index_page_crates = matches.opt_strs("index-page-crate");
+    let link_to_definition = matches.opt_present("generate-link-to-definition");
+    let verbose = matches.opt_present("verbose");
+    let structured_data = matches.opt_present("emit-structured-data");
+    let deny_markdown_warnings = matches.opt_present("deny-markdown-warnings");
+    let sitemap_base_url = matches.opt_str("sitemap-base-url");
+    let signatures_only = matches.opt_present("signatures-only");
+    let deny_file_collisions = matches.opt_present("deny-file-collisions");
+    let absolute_links = matches.opt_present("absolute-links");
+    let emit_cache_json = matches.opt_present("emit-cache-json");
+    let group_by_cfg = matches.opt_present("group-by-cfg");
+    let show_on_unimplemented = matches.opt_present("show-on-unimplemented");
+    let hide_stable_since = matches.opt_present("hide-stable-since");
+    let no_search = matches.opt_present("no-search");
+    let favicon_url = matches.opt_str("favicon-url");
+    let logo_url = matches.opt_str("logo-url");
+    let check_links = matches.opt_present("check-links");
+    let footer = match matches.opt_str("html-footer") {
+        Some(path) => match load_string(&path) {
+            Ok(s) => Some(s),
+            Err(LoadStringError::ReadFail) => return 1,
+            Err(LoadStringError::BadUtf8) => return 2,
+        },
+        None => None,
+    };
+    let hashed_assets = matches.opt_present("hashed-assets");
+    let collapse_impls = matches.opt_present("collapse-impls");
+    let read_more_text = matches.opt_str("read-more-text");
+    let emit_redirect_map = matches.opt_present("emit-redirect-map");
+    let assoc_const_value_len = match matches.opt_str("assoc-const-value-len") {
+        Some(len) => match len.parse::<usize>() {
+            Ok(len) => Some(len),
+            Err(_) => {
+                print_error(format!("invalid associated constant value length: {}", len));
+                return 1;
+            }
+        },
+        None => None,
+    };
+    let extern_html_root_urls = match parse_extern_urls(&matches, "extern-html-root-url") {
+        Ok(urls) => urls,
+        Err(err) => {
+            print_error(err);
+            return 1;
+        }
+    };
+    let extern_src_roots = match parse_extern_urls(&matches, "extern-src-root") {
+        Ok(urls) => urls,
+        Err(err) => {
+            print_error(err);
+            return 1;
+        }
+    };
 
     match (should_test, markdown_input) {
         (true, true) => {
@@ -392,27 +735,94 @@ pub fn main_args(args: &[String]) -> isize {
         (false, false) => {}
     }
 
-    let output_format = matches.opt_str("w");
+    let output_format = match matches.opt_str("w").as_ref().map(|s| &**s) {
+        Some("html") | None => html::render::OutputFormat::Html,
+        Some("markdown") => {
+            if !nightly_options::is_unstable_enabled(&matches) {
+                print_error("the markdown output format is unstable, \
+                             pass `-Z unstable-options` to use it");
+                return 1;
+            }
+            html::render::OutputFormat::Markdown
+        }
+        Some(s) => {
+            print_error(format!("unknown output format: {}", s));
+            return 1;
+        }
+    };
+    let options = html::render::RenderOptions {
+        sort_modules_alphabetically,
+        show_impl_source_files,
+        show_panics_badge,
+        show_hidden_lines,
+        show_dyn_signatures,
+        emit_anchors,
+        assoc_consts_section,
+        type_aliases_page,
+        show_chainable_methods,
+        include_lines,
+        show_coherence_notes,
+        api_snapshot,
+        stabilized_page,
+        group_where_bounds,
+        show_type_usage,
+        rtl,
+        module_since_column,
+        related_crates,
+        show_import_path,
+        summary_truncation,
+        at_a_glance,
+        sort_impls,
+        collapsible_source,
+        extern_html_root_urls,
+        split_search_index,
+        stable_impl_item_ids,
+        pulldown_only: commonmark_only,
+        index_page,
+        index_page_crates,
+        themes,
+        link_to_definition,
+        verbose,
+        structured_data,
+        deny_markdown_warnings,
+        assoc_const_value_len,
+        sitemap_base_url,
+        signatures_only,
+        deny_file_collisions,
+        output_format,
+        absolute_links,
+        emit_cache_json,
+        group_by_cfg,
+        show_on_unimplemented,
+        hide_stable_since,
+        no_search,
+        html_transform: None,
+        favicon_url,
+        logo_url,
+        check_links,
+        footer,
+        hashed_assets,
+        collapse_impls,
+        read_more_text,
+        extern_src_roots,
+        emit_redirect_map,
+    };
     let res = acquire_input(PathBuf::from(input), externs, &matches, move |out| {
         let Output { krate, passes, renderinfo } = out;
         info!("going to format");
-        match output_format.as_ref().map(|s| &**s) {
-            Some("html") | None => {
-                html::render::run(krate, &external_html, playground_url,
-                                  output.unwrap_or(PathBuf::from("doc")),
-                                  passes.into_iter().collect(),
-                                  css_file_extension,
-                                  renderinfo,
-                                  render_type,
-                                  sort_modules_alphabetically)
-                    .expect("failed to generate documentation");
-                0
-            }
-            Some(s) => {
-                print_error(format!("unknown output format: {}", s));
+        html::render::run(krate, &external_html, playground_url,
+                          output.unwrap_or(PathBuf::from("doc")),
+                          passes.into_iter().collect(),
+                          css_file_extension,
+                          renderinfo,
+                          render_type,
+                          options,
+                          Box::new(html::render::FsSink::default()))
+            .map(|()| 0)
+            .unwrap_or_else(|e| {
+                print_error(format!("failed to generate documentation: {}", e));
                 1
-            }
-        }
+            })
     });
     res.unwrap_or_else(|s| {
         print_error(format!("input error: {}", s));
@@ -461,6 +871,23 @@ fn parse_externs(matches: &getopts::Matches) -> Result<Externs, String> {
     Ok(Externs::new(externs))
 }
 
+/// Extracts the `NAME=URL` arguments given to the `option` flag, such as
+/// `--extern-html-root-url`, from `matches` and returns a map of crate names to URLs.
+fn parse_extern_urls(matches: &getopts::Matches,
+                     option: &str) -> Result<HashMap<String, String>, String> {
+    let mut externs = HashMap::new();
+    for arg in &matches.opt_strs(option) {
+        let mut parts = arg.splitn(2, '=');
+        let name = parts.next()
+                        .ok_or_else(|| format!("--{} value must not be empty", option))?;
+        let url = parts.next()
+                       .ok_or_else(|| format!("--{} value must be of the format `foo=bar`",
+                                              option))?;
+        externs.insert(name.to_string(), url.to_string());
+    }
+    Ok(externs)
+}
+
 /// Interprets the input file as a rust source file, passing it through the
 /// compiler all the way through the analysis passes. The rustdoc output is then
 /// generated from the cleaned AST of the crate.
@@ -0,0 +1,21 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+#![allow(non_camel_case_types)]
+
+/// A struct.
+pub struct Foo;
+
+/// Another struct, whose page only differs from the one of `Foo` in case.
+pub struct foo;
+
+/// A function, whose page doesn't collide with the ones of the structs.
+pub fn bar() {}
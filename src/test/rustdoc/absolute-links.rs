@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --absolute-links
+
+#![crate_name = "foo"]
+#![doc(html_root_url = "https://example.com/docs")]
+
+pub mod bar {
+    // @has foo/bar/struct.Baz.html
+    // @has - '//link[@rel="stylesheet"]/@href' 'https://example.com/docs/main.css'
+    // @has - '//p[@class="location"]/a/@href' 'https://example.com/docs/foo/index.html'
+    // @has - '//a[@class="srclink"]/@href' 'https://example.com/docs/src/foo/'
+    pub struct Baz;
+}
@@ -0,0 +1,38 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+// @has foo/all.html '//h2[@id="structs"]' 'Structs'
+// @has - '//ul[@class="all-items"]/li/a[@href="struct.Foo.html"]' 'Foo'
+// @has - '//ul[@class="all-items"]/li/a[@href="bar/fn.baz.html"]' 'bar::baz'
+// @has - '//ul[@class="all-items"]/li/a[@href="bar/index.html"]' 'bar'
+// @has - '//ul[@class="all-items"]/li/a[@href="enum.Qux.html"]' 'Qux'
+// @count - '//a[@href="enum.Qux.html"]' 1
+// @!has - '//a' 'Private'
+// @!has - '//a' 'hidden'
+pub struct Foo;
+
+pub enum Qux {
+    A,
+    B,
+}
+
+pub mod bar {
+    pub fn baz() {}
+}
+
+mod private {
+    pub struct Private;
+}
+
+#[doc(hidden)]
+pub fn hidden() {}
@@ -0,0 +1,44 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --api-snapshot
+
+#![crate_name = "foo"]
+
+// @has api-snapshot.txt 'foo::add fn(a: u32, b: u32) -> u32'
+pub fn add(a: u32, b: u32) -> u32 { a + b }
+
+// @has api-snapshot.txt 'foo::Point struct'
+// @has api-snapshot.txt 'foo::Point::x field: i32'
+// @!has api-snapshot.txt 'foo::Point::secret'
+pub struct Point {
+    pub x: i32,
+    secret: i32,
+}
+
+// @has api-snapshot.txt 'foo::Point::origin fn() -> Point'
+impl Point {
+    pub fn origin() -> Point { Point { x: 0, secret: 0 } }
+    fn hidden(&self) {}
+}
+
+// @has api-snapshot.txt 'foo::inner mod'
+// @has api-snapshot.txt 'foo::inner::LIMIT const: usize'
+pub mod inner {
+    pub const LIMIT: usize = 3;
+}
+
+// @!has api-snapshot.txt 'private'
+mod private {
+    pub fn unreachable() {}
+}
+
+// @matches api-snapshot.txt '(?s)foo::Point .*foo::Point::origin.*foo::add.*foo::inner'
@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+// @has foo/index.html
+// @has - '//body/a[@class="skip-to-content"][@href="#main"]' 'Skip to main content'
+// @has - '//section[@id="main"][@role="main"]' ''
+// @has - '//form[@class="search-form js-only"][@role="search"]' ''
+// @has - '//div[@class="sidebar-elems"][@role="navigation"]' ''
+
+// @has foo/struct.Foo.html
+// @has - '//body/a[@class="skip-to-content"][@href="#main"]' 'Skip to main content'
+// @has - '//section[@id="main"][@role="main"]' ''
+// @has - '//div[@class="sidebar-elems"][@role="navigation"]' ''
+pub struct Foo;
+
+// @has foo/fn.bar.html
+// @has - '//section[@id="main"][@role="main"]' ''
+// @has - '//div[@class="sidebar-elems"][@role="navigation"]' ''
+pub fn bar() {}
@@ -0,0 +1,27 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --assoc-const-value-len 10
+
+#![crate_name = "foo"]
+
+pub struct Foo;
+
+// @has foo/struct.Foo.html
+impl Foo {
+    // @has - '//pre[@class="rust const-value"]' 'str = "012345678..."'
+    // @has - '//details[@class="const-value"]//pre' '"0123456789abcdef"'
+    pub const LONG: &'static str = "0123456789abcdef";
+    // @has - '//pre[@class="rust const-value"]' 'ARRAY: [u8; 6] = [1, 2, 3, ...]'
+    pub const ARRAY: [u8; 6] = [1, 2, 3, 4, 5, 6];
+    // @count - '//pre[@class="rust const-value"]' 2
+    // @count - '//details[@class="const-value"]' 2
+    pub const SHORT: u8 = 1;
+}
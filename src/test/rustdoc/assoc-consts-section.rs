@@ -0,0 +1,28 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --assoc-consts-section
+
+#![crate_name = "foo"]
+
+pub struct Bar;
+
+// @has foo/struct.Bar.html
+// @has - '//h2[@id="associated-consts"]' 'Associated Constants'
+// @has - '//*[@id="associatedconstant.BAZ"]' 'const BAZ: usize'
+// @count - '//*[@class="impl-items"]//*[@class="associatedconstant"]' 0
+// @has - '//*[@class="impl-items"]//*[@id="method.qux"]' 'fn qux()'
+impl Bar {
+    /// The number of bazzes.
+    pub const BAZ: usize = 1;
+
+    pub fn qux() {}
+}
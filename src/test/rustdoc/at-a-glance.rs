@@ -0,0 +1,34 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --at-a-glance
+
+#![crate_name = "foo"]
+#![feature(staged_api)]
+#![stable(feature = "foo", since = "1.0.0")]
+
+// @has foo/fn.raw.html '//div[@class="at-a-glance"]/span[@class="glance-badge"]' \
+//      'stable since 1.2.0'
+// @has - '//div[@class="at-a-glance"]/span[@class="glance-badge"]' 'unsafe'
+// @has - '//div[@class="at-a-glance"]/span[@class="glance-badge"]' 'extern "C"'
+#[stable(feature = "raw", since = "1.2.0")]
+pub unsafe extern "C" fn raw() {}
+
+// @has foo/trait.Risky.html '//div[@class="at-a-glance"]/span[@class="glance-badge"]' \
+//      'unstable'
+// @has - '//div[@class="at-a-glance"]/span[@class="glance-badge"]' 'unsafe'
+#[unstable(feature = "risky", issue = "0")]
+pub unsafe trait Risky {}
+
+// @has foo/struct.Old.html '//div[@class="at-a-glance"]/span[@class="glance-badge"]' \
+//      'deprecated'
+#[stable(feature = "old", since = "1.0.0")]
+#[rustc_deprecated(since = "1.1.0", reason = "use something else")]
+pub struct Old;
@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+pub trait Convert<T> {}
+pub trait Named {}
+
+// @has foo/struct.Foo.html
+// @has - '//h2[@id="implementations"]' 'Trait Implementations'
+// @has - '//*[@class="impl"]//code' 'impl Named for Foo'
+// @has - '//h2[@id="blanket-implementations"]' 'Blanket Implementations'
+// @has - '//div[@class="blanket-implementations collapsed"]//code' 'impl<T> Convert<Foo> for T'
+// @!has - '//div[@class="blanket-implementations collapsed"]//code' 'impl Named for Foo'
+// @has - '//div[@class="blanket-note"]' '(via blanket implementation impl<T> Convert<Foo> for T)'
+// @count - '//div[@class="blanket-note"]' 1
+// @has - '//*[@class="sidebar-title"][@href="#blanket-implementations"]' 'Blanket Implementations'
+pub struct Foo;
+
+impl Named for Foo {}
+
+impl<T> Convert<Foo> for T {}
@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --emit-cache-json
+
+#![crate_name = "foo"]
+
+// @has cache.json '"format_version":1'
+// @has - '"crate":"foo"'
+// @has - '{"kind":"trait","local":true,"path":["foo","Shape"]}'
+// @has - '{"kind":"struct","local":true,"path":["foo","Circle"]}'
+// @has - '{"kind":"trait","local":false,"path":["core","clone","Clone"]}'
+// @has - '"extern_locations":{'
+// @has - '"implementors":{'
+// @has - '["Circle"]'
+pub trait Shape {}
+
+#[derive(Clone)]
+pub struct Circle;
+
+impl Shape for Circle {}
@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Foo.html '//link[@rel="canonical"]/@href' '../foo/struct.Foo.html'
+// @has foo/Foo.t.html '//link[@rel="canonical"]/@href' 'struct.Foo.html'
+pub struct Foo;
+
+pub mod inner {
+    // @has foo/inner/struct.Bar.html '//link[@rel="canonical"]/@href' \
+    //      '../../foo/inner/struct.Bar.html'
+    pub struct Bar;
+}
+
+// The copy of `Bar` inlined here defers to its original page.
+// @has foo/outer/struct.Bar.html '//link[@rel="canonical"]/@href' \
+//      '../../foo/inner/struct.Bar.html'
+pub mod outer {
+    #[doc(inline)]
+    pub use inner::Bar;
+}
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --collapse-impls
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Foo.html
+// @has - '//h3[@id="impl"][@class="impl collapsed"]' 'impl Foo'
+// @has - '//div[@class="impl-items collapsed"]//h4[@id="method.bar"]' 'fn bar()'
+// @has - '//h3[@id="impl-Clone"][@class="impl collapsed"]' 'impl Clone for Foo'
+#[derive(Clone)]
+pub struct Foo;
+
+impl Foo {
+    pub fn bar() {}
+}
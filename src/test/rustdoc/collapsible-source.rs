@@ -0,0 +1,38 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --collapsible-source
+
+#![crate_name = "foo"]
+
+// @has src/foo/collapsible-source.rs.html
+// @count - '//pre[@class="line-numbers"]//span[@class="src-item"]' 2
+// @count - '//pre[@class="rust "]//span[@class="src-item"]' 2
+// @has - '//pre[@class="line-numbers"]/span[@data-item-start="27"]/@data-item-end' '30'
+// @has - '//pre[@class="line-numbers"]/span[@data-item-start="27"]/span[@id="27"]' '27'
+// @has - '//pre[@class="line-numbers"]/span[@data-item-start="27"]/span[@class="src-item-body"]/span[@id="28"]' '28'
+// @has - '//pre[@class="rust "]/span[@data-item-start="27"]/@data-item-end' '30'
+// @has - '//pre[@class="rust "]/span[@data-item-start="32"]/@data-item-end' '36'
+// @!has - '//span[@data-item-start="33"]'
+// @!has - '//span[@data-item-start="38"]'
+
+pub struct Foo {
+    pub a: u32,
+    pub b: u32,
+}
+
+impl Foo {
+    pub fn new() -> Foo {
+        Foo { a: 0, b: 0 }
+    }
+}
+
+pub fn one_line() {}
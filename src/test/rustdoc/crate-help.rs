@@ -0,0 +1,20 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+#![doc(html_help = "Press `g` twice to *go home*.")]
+
+// @has foo/index.html '//aside[@id="help"]/div[@class="with-crate-help"]' ''
+// @has - '//aside[@id="help"]//div[@class="crate-help docblock"]/p/code' 'g'
+// @has - '//aside[@id="help"]//div[@class="crate-help docblock"]/p/em' 'go home'
+// @has foo/struct.Foo.html '//aside[@id="help"]//div[@class="crate-help docblock"]' \
+//      'Press g twice to go home.'
+
+pub struct Foo;
@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+// @has src/foo/auxiliary/dedup-sources-a.rs.html
+// @has src/foo/auxiliary/dedup-sources-b.rs.html
+// @matches - 'URL=../../../src/foo/auxiliary/dedup-sources-a.rs.html'
+// @has src/source-hashes.txt 'foo/auxiliary/dedup-sources-a.rs.html'
+// @!has src/source-hashes.txt 'foo/auxiliary/dedup-sources-b.rs.html'
+
+// @has foo/a/struct.S.html '//a[@class="srclink"]/@href' 'auxiliary/dedup-sources-a.rs.html'
+#[path = "auxiliary/dedup-sources-a.rs"]
+pub mod a;
+
+// @has foo/b/struct.S.html '//a[@class="srclink"]/@href' 'auxiliary/dedup-sources-a.rs.html'
+#[path = "auxiliary/dedup-sources-b.rs"]
+pub mod b;
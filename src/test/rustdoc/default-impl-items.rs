@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+pub trait Tr {
+    fn required(&self);
+    fn provided(&self) {}
+    fn overridden(&self) {}
+}
+
+// @has foo/struct.Foo.html
+// @has - '//h4[@id="method.provided"]/@class' 'method from-default'
+// @has - '//h4[@id="method.provided"]//span[@class="from-default"]' 'provided'
+// @!has - '//h4[@id="method.required"]//span[@class="from-default"]' ''
+// @!has - '//h4[@id="method.overridden"]//span[@class="from-default"]' ''
+pub struct Foo;
+
+impl Tr for Foo {
+    fn required(&self) {}
+    fn overridden(&self) {}
+}
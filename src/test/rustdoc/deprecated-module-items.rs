@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @has foo/old/index.html '//div[@class="stab deprecated module-deprecation"]' \
+//      'This module is deprecated, and so are all of its items.'
+// @count - '//tr[@data-deprecated]' 2
+// @has - '//tr[@data-deprecated]//a[@href="struct.Plain.html"]' 'Plain'
+// @count - '//tr[@data-deprecated]//div[@class="stab deprecated"]' 2
+#[deprecated(note = "gone")]
+pub mod old {
+    pub struct Plain;
+
+    #[deprecated(note = "use New")]
+    pub struct Stricter;
+}
+
+// @!has foo/current/index.html '//div[@class="stab deprecated module-deprecation"]'
+// @!has - '//tr[@data-deprecated]'
+pub mod current {
+    pub struct Fine;
+}
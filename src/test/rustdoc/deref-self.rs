@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+use std::ops::Deref;
+
+// @has foo/struct.Foo.html
+// @has - '//h2[@id="methods"]' 'Methods'
+// @!has - '//h2[@id="deref-methods"]' ''
+// @!has - '//*[@class="sidebar-title"][@href="#deref-methods"]' ''
+// @has - '//h4[@id="method.own"]' 'fn own'
+// @!has - '//h4[@id="method.own-1"]' ''
+pub struct Foo;
+
+impl Foo {
+    pub fn own(&self) {}
+}
+
+impl Deref for Foo {
+    type Target = Foo;
+    fn deref(&self) -> &Foo {
+        self
+    }
+}
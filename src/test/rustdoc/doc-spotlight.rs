@@ -44,3 +44,20 @@ impl SomeStruct {
 pub fn bare_fn() -> SomeStruct {
     SomeStruct
 }
+
+#[doc(spotlight)]
+pub trait OtherTrait {}
+
+pub struct BothStruct;
+impl SomeTrait for BothStruct {}
+impl OtherTrait for BothStruct {}
+
+// @has doc_spotlight/fn.both_fn.html
+// @count - '//h3[@class="important"]' 1
+// @count - '//code[@class="content"]/span[@class="important-impl"]' 2
+// @has - '//span[@class="important-impl"]' 'impl SomeTrait for BothStruct'
+// @has - '//span[@class="important-impl"]' 'impl OtherTrait for BothStruct'
+// @has - '//span[@class="tooltiptext"]' 'Important traits for BothStruct: SomeTrait, OtherTrait'
+pub fn both_fn() -> BothStruct {
+    BothStruct
+}
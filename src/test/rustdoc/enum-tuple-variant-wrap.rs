@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+use std::collections::HashMap;
+
+// @has foo/enum.Message.html
+// @has - '//pre[@class="rust enum"]' 'Short(u8, u16),'
+// @matches foo/enum.Message.html 'Long\(\n        <a [^>]*>HashMap</a>'
+// @matches foo/enum.Message.html '\n        <a [^>]*>Result</a>.*,\n    \),'
+pub enum Message {
+    Short(u8, u16),
+    Long(HashMap<String, Vec<u64>>, Option<HashMap<u32, String>>, Result<u8, String>),
+}
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @has foo/index.html
+// @has - '//sup[@class="abi-flag"][starts-with(@title, "extern")]' 'C'
+// @has - '//sup[@class="abi-flag"][contains(@title, "system")]' 'system'
+// @count - '//sup[@class="abi-flag"]' 2
+
+// @has foo/fn.c_fn.html '//pre[@class="rust fn"]' 'pub extern "C" fn c_fn()'
+pub extern "C" fn c_fn() {}
+
+// @has foo/fn.system_fn.html '//pre[@class="rust fn"]' 'pub extern "system" fn system_fn()'
+pub extern "system" fn system_fn() {}
+
+pub fn rust_fn() {}
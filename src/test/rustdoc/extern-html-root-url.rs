@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// aux-build:extern-html-root-url.rs
+// ignore-cross-compile
+// compile-flags: -Z unstable-options
+// compile-flags: --extern-html-root-url extern_html_root_url=https://docs.example.com/private
+
+#![crate_name = "foo"]
+
+extern crate extern_html_root_url;
+
+// @has foo/fn.bar.html \
+//      '//a[@href="https://docs.example.com/private/extern_html_root_url/struct.Bar.html"]' \
+//      'Bar'
+pub fn bar() -> extern_html_root_url::Bar {
+    extern_html_root_url::Bar
+}
@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:extern-src-root.rs
+// compile-flags: -Z unstable-options --extern-src-root extern_src_root=file:///src/dep
+// ignore-cross-compile
+
+#![crate_name = "foo"]
+
+extern crate extern_src_root;
+
+// @has foo/struct.Foo.html
+// @has - '//a[@class="srclink"]/@href' 'file:///src/dep/extern-src-root.rs#L11'
+pub use extern_src_root::Foo;
+
+// @has foo/struct.Bar.html
+// @has - '//a[@class="srclink"]/@href' 'file:///src/dep/extern-src-root.rs#L13-L15'
+pub use extern_src_root::Bar;
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --logo-url https://example.com/logo.svg
+// compile-flags: --favicon-url https://example.com/favicon.ico
+
+#![crate_name = "foo"]
+#![doc(html_logo_url = "https://crate.example.com/logo.png")]
+
+// The logo given on the command line wins over the attribute, and the favicon
+// fills in for the missing attribute.
+// @has foo/index.html
+// @has - '//img[@alt="logo"]/@src' 'https://example.com/logo.svg'
+// @!has - '//img[@alt="logo"]/@src' 'https://crate.example.com/logo.png'
+// @has - '//link[@rel="shortcut icon"]/@href' 'https://example.com/favicon.ico'
+pub struct Foo;
@@ -0,0 +1,27 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+use std::cell::Cell;
+
+// @has foo/trait.Describe.html
+// @count - '//h3[@class="foreign-impls-crate"]' 2
+// @has - '//h3[@id="foreign-impls.alloc"]' 'alloc'
+// @has - '//h3[@id="foreign-impls.core"]' 'core'
+// @count - '//div[@class="foreign-impls-group"]' 2
+// @count - '//div[@class="foreign-impls-group"]/h3[@class="impl"]' 3
+// @matches - '(?s)foreign-impls\.alloc.*>String<.*>Vec<.*foreign-impls\.core.*>Cell<'
+pub trait Describe {}
+
+impl Describe for Vec<u8> {}
+impl Describe for Cell<u8> {}
+impl Describe for String {}
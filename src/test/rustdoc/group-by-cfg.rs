@@ -0,0 +1,34 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --group-by-cfg
+
+#![crate_name = "foo"]
+#![feature(doc_cfg)]
+
+// @has foo/index.html
+// @count - '//h3[@class="cfg-group"]' 2
+// @has - '//table[1]//a' 'Anywhere'
+// @has - '//h3[@class="cfg-group"][1]' 'Unix'
+// @has - '//h3[@class="cfg-group"][1]/following-sibling::table[1]//a' 'OnUnix'
+// @has - '//h3[@class="cfg-group"][2]' 'Windows'
+// @has - '//h3[@class="cfg-group"][2]/following-sibling::table[1]//a' 'OnWindows'
+// @!has - '//h3[@class="cfg-group"][2]/following-sibling::table[1]//a' 'OnUnix'
+pub struct Anywhere;
+
+#[doc(cfg(windows))]
+pub struct OnWindows;
+
+#[doc(cfg(unix))]
+pub struct OnUnix;
+
+#[doc(cfg(unix))]
+pub struct AlsoOnUnix;
@@ -0,0 +1,28 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --group-where-bounds
+
+#![crate_name = "foo"]
+
+use std::fmt::{Debug, Display};
+
+// @has foo/fn.show.html '//pre[@class="rust fn"]' 'where T: Debug + Display + Clone, U: Copy'
+pub fn show<T, U>(_: T, _: U) where T: Debug, T: Display, U: Copy, T: Clone {}
+
+// @has foo/struct.Pair.html '//pre[@class="rust struct"]' 'where T: Debug + Clone'
+pub struct Pair<T> where T: Debug, T: Clone {
+    pub first: T,
+    pub second: T,
+}
+
+// @has foo/trait.Shown.html '//pre[@class="rust trait"]' 'where T: Debug + Display'
+pub trait Shown<T> where T: Debug, T: Display {}
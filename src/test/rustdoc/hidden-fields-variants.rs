@@ -0,0 +1,44 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --no-defaults --passes collapse-docs --passes unindent-comments
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Foo.html
+// @has - '//pre[@class="rust struct"]' 'pub a: u32'
+// @!has - '//pre[@class="rust struct"]' 'pub b: u32'
+// @has - '//pre[@class="rust struct"]' '// some fields omitted'
+// @has - '//span[@id="structfield.a"]' 'a: u32'
+// @!has - '//span[@id="structfield.b"]' ''
+// @has - '//div[@class="sidebar-links"]/a[@href="#structfield.a"]' 'a'
+// @!has - '//div[@class="sidebar-links"]/a[@href="#structfield.b"]' 'b'
+pub struct Foo {
+    pub a: u32,
+    #[doc(hidden)]
+    pub b: u32,
+}
+
+// @has foo/struct.Tuple.html '//pre[@class="rust struct"]' 'pub struct Tuple(pub u32, _);'
+pub struct Tuple(pub u32, #[doc(hidden)] pub u32);
+
+// @has foo/enum.Bar.html
+// @has - '//pre[@class="rust enum"]' 'Visible,'
+// @!has - '//pre[@class="rust enum"]' 'Hidden'
+// @has - '//pre[@class="rust enum"]' '// some variants omitted'
+// @has - '//span[@id="variant.Visible"]' 'Visible'
+// @!has - '//span[@id="variant.Hidden"]' ''
+// @has - '//div[@class="sidebar-links"]/a[@href="#variant.Visible"]' 'Visible'
+// @!has - '//div[@class="sidebar-links"]/a[@href="#variant.Hidden"]' 'Hidden'
+pub enum Bar {
+    Visible,
+    #[doc(hidden)]
+    Hidden,
+}
@@ -0,0 +1,28 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:hidden-implementors.rs
+// ignore-cross-compile
+
+#![crate_name = "foo"]
+
+extern crate hidden_implementors;
+
+use hidden_implementors::Marker;
+
+pub struct Shown;
+
+#[doc(hidden)]
+pub struct Hidden;
+
+// @has implementors/hidden_implementors/trait.Marker.js Shown
+// @!has - Hidden
+impl Marker for Shown {}
+impl Marker for Box<Hidden> {}
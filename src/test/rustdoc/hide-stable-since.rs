@@ -0,0 +1,46 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --hide-stable-since --module-since-column
+
+#![crate_name = "foo"]
+#![feature(staged_api)]
+#![stable(feature = "foo", since = "1.0.0")]
+
+// @has foo/index.html
+// @!has - '//span[@class="since"]' ''
+// @!has - '//td[@class="since-column"]' ''
+
+// @has foo/struct.Foo.html
+// @!has - '//div[@class="since"]' ''
+// @!has - '//span[@class="since"]' ''
+// @has - '//code' 'fn newer()'
+#[stable(feature = "old", since = "1.2.0")]
+pub struct Foo;
+
+impl Foo {
+    #[stable(feature = "newer", since = "1.10.0")]
+    pub fn newer() {}
+}
+
+#[stable(feature = "old", since = "1.2.0")]
+impl Clone for Foo {
+    #[stable(feature = "old", since = "1.2.0")]
+    fn clone(&self) -> Foo { Foo }
+}
+
+// @has foo/enum.Bar.html
+// @!has - '//div[@class="since"]' ''
+#[stable(feature = "old", since = "1.2.0")]
+pub enum Bar {
+    #[stable(feature = "newer", since = "1.10.0")]
+    Baz,
+}
@@ -0,0 +1,22 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --html-footer {{src-base}}/auxiliary/html-footer.html
+
+#![crate_name = "foo"]
+
+// @has foo/index.html
+// @has - '//section[@class="footer"]/p[@class="copyright"]' 'Copyright © The Foo Developers.'
+// @has - '//section[@class="footer"]/p/a/@href' 'https://example.com'
+
+// @has foo/struct.Foo.html
+// @has - '//section[@class="footer"]/p[@class="copyright"]' 'The Foo Developers.'
+pub struct Foo;
@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @matches 'search-index.js' '"aliases":\{"length":\[\d+\],"size":\[\d+\]\}'
+
+pub struct Bag;
+
+impl Bag {
+    #[doc(alias = "length")]
+    #[doc(alias = "size")]
+    pub fn len(&self) -> usize { 0 }
+
+    pub fn is_empty(&self) -> bool { true }
+}
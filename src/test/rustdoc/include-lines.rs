@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --enable-include-lines
+
+#![crate_name = "foo"]
+
+// @has foo/fn.answer.html
+// @has - '//pre[@class="rust rust-example-rendered"]' 'let answer = 6 * 7;'
+// @has - '//pre[@class="rust rust-example-rendered"]' 'assert_eq!(answer, 42);'
+// @!has - '//pre[@class="rust rust-example-rendered"]' 'fn main'
+/// Computes the answer:
+///
+/// {{include-lines auxiliary/include-lines-example.rs:13:14}}
+pub fn answer() -> u32 { 42 }
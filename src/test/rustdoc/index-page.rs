@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --index-page {{src-base}}/auxiliary/index-page.md
+// compile-flags: --index-page-crate other
+
+#![crate_name = "foo"]
+
+// @has index.html '//title' 'Crates - Rust'
+// @has - '//div[@class="docblock"]/h1' 'Welcome'
+// @has - '//div[@class="docblock"]/p/em' 'all'
+// @count - '//ul[@class="crate-list"]/li' 2
+// @has - '//ul[@class="crate-list"]/li/a[@href="foo/index.html"]' 'foo'
+// @has - '//ul[@class="crate-list"]/li/a[@href="other/index.html"]' 'other'
+pub struct Foo;
@@ -81,3 +81,8 @@ pub fn test_44731_4() -> Box<Iterator<Item=impl Clone>> {
 // @has issue_43869/fn.test_44731_0.html
 // @has issue_43869/fn.test_44731_1.html
 // @has issue_43869/fn.test_44731_4.html
+
+// @has issue_43869/fn.j.html
+// @has - '//span[@class="impl-trait"]/@title' '- Iterator<Item = u8>'
+// @has - '//span[@class="impl-trait"]/@title' '- Clone'
+// @has - '//span[@class="impl-trait"]' 'impl Iterator<Item = u8> + Clone'
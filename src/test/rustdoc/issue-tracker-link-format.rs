@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+#![feature(staged_api)]
+#![doc(issue_tracker_base_url = "http://issue_url/")]
+#![doc(issue_tracker_link_format = "Tracking issue: {}")]
+
+#![unstable(feature="test", issue="27759")]
+
+// @has foo/fn.issue.html
+// @has - '<code>test_function </code><a href="http://issue_url/1234">Tracking issue: 1234</a>'
+// @!has - '#1234'
+#[unstable(feature="test_function", issue="1234")]
+pub fn issue() {}
@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --generate-link-to-definition
+
+#![crate_name = "foo"]
+
+// @has src/foo/link-to-definition.rs.html
+// @has - '//pre[@class="rust "]//a[@href="../../foo/struct.Bar.html"]' 'Bar'
+// @has - '//pre[@class="rust "]//a[@href="../../foo/bar/trait.Tr.html"]' 'Tr'
+// The source links of impls are kept.
+// @has foo/struct.Bar.html
+// @has - '//h3[starts-with(@class, "impl")]//a[@class="srclink"]/@href' \
+//      '../src/foo/link-to-definition.rs.html'
+pub struct Bar;
+
+pub mod bar {
+    pub trait Tr {}
+}
+
+pub use bar::Tr;
+
+pub fn baz(_: Bar) -> Option<Bar> { None }
+
+impl bar::Tr for Bar {}
@@ -12,6 +12,10 @@
 // @has - //pre '() => { ... };'
 // @has - //pre '($a:tt) => { ... };'
 // @has - //pre '($e:expr) => { ... };'
+// @has - '//h2[@id="arms"]' 'Arms'
+// @has - '//div[@id="arm.0"][@class="macro-arm"]/code' '() => { ... }'
+// @has - '//div[@id="arm.1"]/a[@href="#arm.1"]' ''
+// @has - '//div[@id="arm.2"]/code' '($e:expr) => { ... }'
 // @has macros/macro.my_macro!.html
 // @has - //a 'macro.my_macro.html'
 #[macro_export]
@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options -w markdown --emit-anchors --type-aliases-page
+// compile-flags: --stabilized-page --sitemap-base-url https://example.com/
+
+#![crate_name = "foo"]
+
+// The pages and files only describing the HTML pages are left out.
+// @has foo/index.md
+// @!has anchors.json
+// @!has type-aliases.html
+// @!has stabilized.html
+// @!has sitemap.xml
+
+pub type Alias = u32;
@@ -0,0 +1,51 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options -w markdown
+
+#![crate_name = "foo"]
+
+//! The foo crate.
+
+// @has foo/index.md
+// @matches - '^# Crate `foo`'
+// @matches - '- \[`Foo`\]\(struct\.Foo\.md\): A foo\.'
+// @matches - '- \[`add`\]\(fn\.add\.md\): Adds two numbers\.'
+// @!has foo/index.html
+// @!has main.js
+
+// @has foo/fn.add.md
+// @matches - '(?m)^pub fn add\(a: u32, b: u32\) -> u32$'
+// @matches - '(?m)^Adds two numbers\.$'
+/// Adds two numbers.
+pub fn add(a: u32, b: u32) -> u32 { a + b }
+
+// @has foo/struct.Foo.md
+// @matches - '(?m)^# Struct `foo::Foo`$'
+// @matches - '(?s)pub struct Foo \{\n    pub a: u8,\n    // some fields omitted\n\}'
+// @matches - '(?m)^pub fn new\(\) -> Foo$'
+// @matches - '(?m)^Makes a foo\.$'
+/// A foo.
+pub struct Foo {
+    pub a: u8,
+    b: u8,
+}
+
+impl Foo {
+    /// Makes a foo.
+    pub fn new() -> Foo { Foo { a: 0, b: 0 } }
+}
+
+// @has foo/trait.Bar.md
+// @matches - '(?s)pub trait Bar \{\n    fn bar\(&self\);\n\}'
+pub trait Bar {
+    /// Does the bar.
+    fn bar(&self);
+}
@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+pub mod outer {
+    pub mod inner {
+        pub trait Double {
+            fn foo();
+        }
+    }
+}
+
+// @has foo/index.html
+// @count - '//a[@class="trait"][@title="trait foo::Double"]' 1
+pub use outer::inner::*;
+pub use outer::*;
+
+// @has - '//code' 'pub use std::string::String;'
+// @has - '//code' 'pub use std::string::ToString;'
+// @has - '//code' 'pub use std::vec::Vec as Vector;'
+#[doc(no_inline)]
+pub use std::string::{String, ToString};
+#[doc(no_inline)]
+pub use std::vec::Vec as Vector;
@@ -0,0 +1,28 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --module-since-column
+
+#![crate_name = "foo"]
+#![feature(staged_api)]
+#![stable(feature = "foo", since = "1.0.0")]
+
+// @has foo/index.html '//tr[@class="module-item"]/td[@class="since-column"]/span[@class="since"]' \
+//      '1.2.0'
+// @has - '//td[@class="since-column"]/span[@title="Stable since Rust version 1.10.0"]' '1.10.0'
+// @count - '//td[@class="since-column"]' 3
+#[stable(feature = "old", since = "1.2.0")]
+pub struct Old;
+
+#[stable(feature = "newer", since = "1.10.0")]
+pub fn newer() {}
+
+#[unstable(feature = "experimental", issue = "0")]
+pub struct Experimental;
@@ -19,3 +19,14 @@ pub struct Struct {
 pub enum Enum {
     Variant(i32),
 }
+
+// @has must_use/fn.function.html //pre '#[must_use = "the <result> matters"]'
+// @has - '//div[@class="stab must-use"]' 'must be used: the <result> matters'
+// @matches - 'must be used: the &lt;result&gt; matters'
+#[must_use = "the <result> matters"]
+pub fn function() -> i32 { 0 }
+
+// @has must_use/fn.bare.html //pre '#[must_use]'
+// @!has - '//div[@class="stab must-use"]' ''
+#[must_use]
+pub fn bare() -> i32 { 0 }
@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --no-search
+
+#![crate_name = "foo"]
+
+// @!has search-index.js
+// @!has search-index.json
+// @has foo/index.html
+// @!has - '//input[@class="search-input"]' ''
+// @!has - '//script[@src="../search-index.js"]' ''
+// @has - '//script[@src="../main.js"]' ''
+
+// @has foo/struct.Foo.html
+// @!has - '//form[@class="search-form js-only"]' ''
+pub struct Foo;
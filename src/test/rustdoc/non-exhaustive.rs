@@ -0,0 +1,50 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(non_exhaustive)]
+#![crate_name = "foo"]
+
+// @has foo/struct.Config.html '//pre[@class="rust struct"]' '// more fields may be added'
+// @has - '//div[@class="stab non-exhaustive"]' 'This struct is marked as non-exhaustive'
+#[non_exhaustive]
+pub struct Config {
+    pub verbose: bool,
+}
+
+// @has foo/struct.Opaque.html '//pre[@class="rust struct"]' \
+//      'pub struct Opaque { /* some fields omitted, more may be added */ }'
+#[non_exhaustive]
+pub struct Opaque {
+    inner: u8,
+}
+
+// @has foo/enum.Error.html '//pre[@class="rust enum"]' '// more variants may be added'
+// @has - '//div[@class="stab non-exhaustive"]' 'This enum is marked as non-exhaustive'
+#[non_exhaustive]
+pub enum Error {
+    Io,
+    Parse,
+}
+
+// @has foo/enum.Kind.html '//pre[@class="rust enum"]' '// some variants omitted, more may be added'
+#[non_exhaustive]
+pub enum Kind {
+    Public,
+    #[doc(hidden)]
+    Hidden,
+}
+
+// @has foo/enum.Closed.html
+// @!has - '//pre[@class="rust enum"]' 'may be added'
+// @!has - '//div[@class="stab non-exhaustive"]'
+pub enum Closed {
+    A,
+}
@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --show-on-unimplemented
+
+#![crate_name = "foo"]
+#![feature(on_unimplemented)]
+
+// @has foo/trait.Short.html
+// @has - '//div[@class="stab on-unimplemented"]' 'the compiler reports: {Self} is not short'
+#[rustc_on_unimplemented = "{Self} is not short"]
+pub trait Short {}
+
+// @has foo/trait.Listed.html
+// @has - '//div[@class="stab on-unimplemented"]' 'the compiler reports: {Self} is not <listed>'
+#[rustc_on_unimplemented(message = "{Self} is not <listed>", label = "unlisted")]
+pub trait Listed {}
+
+// @has foo/trait.Plain.html
+// @!has - '//div[@class="stab on-unimplemented"]' ''
+pub trait Plain {}
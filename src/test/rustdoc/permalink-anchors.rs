@@ -0,0 +1,34 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Foo.html '//a[@href="#fields"]/@data-permalink' 'struct.Foo.html#fields'
+// @has - '//a[@href="#methods"]/@data-permalink' '../foo/struct.Foo.html#methods'
+// @has - '//a[@href="#implementations"]/@data-permalink' 'Foo.html#implementations'
+pub struct Foo {
+    pub x: u32,
+}
+
+impl Foo {
+    pub fn new() -> Foo { Foo { x: 0 } }
+}
+
+impl Clone for Foo {
+    fn clone(&self) -> Foo { Foo { x: self.x } }
+}
+
+pub mod bar {
+    // @has foo/bar/trait.Bar.html
+    // @has - '//a[@href="#required-methods"]/@data-permalink' 'bar/trait.Bar.html#required-methods'
+    pub trait Bar {
+        fn bar(&self);
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @has foo/fn.double.html
+// @has - '//pre[@class="rust fn"]/a[@class="primitive"]/@href' 'primitive.u32.html'
+pub fn double(x: u32) -> u32 { x * 2 }
+
+pub mod inner {
+    // @has foo/inner/fn.halve.html
+    // @has - '//pre[@class="rust fn"]/a[@class="primitive"]/@href' '../primitive.u32.html'
+    // @has search-index.js '"generics":["u32"]'
+    pub fn halve(x: Option<u32>) -> u32 { x.unwrap_or(0) / 2 }
+}
+
+/// The 32-bit unsigned integer type.
+#[doc(primitive = "u32")]
+mod prim_u32 {}
@@ -0,0 +1,27 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --read-more-text Continued
+
+#![crate_name = "foo"]
+
+pub trait T {
+    /// Summary of the method.
+    ///
+    /// More about it.
+    fn method(&self) {}
+}
+
+// @has foo/struct.S.html
+// @has - '//*[@class="docblock"]//a[@href="../foo/trait.T.html#method.method"]' 'Continued'
+// @!has - '//*[@class="docblock"]' 'Read more'
+pub struct S;
+
+impl T for S {}
@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --emit-redirect-map
+
+#![crate_name = "foo"]
+
+// @has redirects.json '"foo/Foo.t.html":"foo/struct.Foo.html"'
+pub struct Foo;
+
+// @has redirects.json '"foo/bar.v.html":"foo/fn.bar.html"'
+pub fn bar() {}
+
+// @has redirects.json '"foo/macro.baz!.html":"foo/macro.baz.html"'
+#[macro_export]
+macro_rules! baz {
+    () => {}
+}
+
+mod hidden {
+    pub struct Qux;
+}
+
+// @has redirects.json '"foo/hidden/struct.Qux.html":"foo/struct.Qux.html"'
+pub use hidden::Qux;
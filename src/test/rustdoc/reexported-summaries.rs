@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+mod private {
+    /// The *shared* summary.
+    ///
+    /// More details.
+    pub struct Shared;
+}
+
+// @has foo/a/index.html '//td[@class="docblock-short"]' 'The shared summary.'
+// @has - '//td[@class="docblock-short"]//em' 'shared'
+// @has foo/a/sidebar-items.js '"The shared summary."'
+pub mod a {
+    pub use private::Shared;
+}
+
+// @has foo/b/index.html '//td[@class="docblock-short"]' 'The shared summary.'
+// @has - '//td[@class="docblock-short"]//em' 'shared'
+// @has foo/b/sidebar-items.js '"The shared summary."'
+pub mod b {
+    pub use private::Shared;
+}
@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --related-crate bar --related-crate baz
+// compile-flags: --related-crate a<b
+
+#![crate_name = "foo"]
+
+// @has foo/index.html '//h2[@id="related-crates"]' 'Related crates'
+// @has - '//a[@href="../bar/index.html"]' 'bar'
+// @has - '//a[@href="../baz/index.html"]' 'baz'
+// @has - '//a[@href="../a<b/index.html"]' 'a<b'
+// @matches - 'a&lt;b</a>'
+// @!has foo/inner/index.html '//h2[@id="related-crates"]' 'Related crates'
+
+pub mod inner {}
@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --rtl
+
+#![crate_name = "foo"]
+
+// @has foo/index.html '//html[@dir="rtl"]' ''
+// @has foo/bar/index.html '//html[@dir="rtl"]' ''
+
+pub mod bar {
+    // @has foo/bar/struct.Baz.html '//html[@dir="rtl"]' ''
+    // @has - '//h1[@class="fqn"]/span[@class="in-band"]/bdi[@dir="ltr"]' 'foo::bar::Baz'
+    // @has - '//p[@class="location"]/bdi[@dir="ltr"]/a' 'bar'
+    pub struct Baz;
+}
@@ -0,0 +1,21 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+// @has search-index.js '{"arg":"needle","name":"str"}'
+// @has - '{"arg":"haystack","generics":["string"],"name":"vec"}'
+pub fn find(needle: &str, haystack: Vec<String>) -> Option<usize> {
+    None
+}
+
+// @!has search-index.js '"arg":"_"'
+pub fn ignore(_: u8, (a, b): (u8, u8)) {}
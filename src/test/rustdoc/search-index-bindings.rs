@@ -0,0 +1,19 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @has search-index.js '{"arg":"bytes","generics":["u8"],"name":"iterator"}'
+pub fn checksum(bytes: &mut Iterator<Item = u8>) -> u32 {
+    0
+}
+
+// @has search-index.js '{"arg":"map","generics":["string","u32"],"name":"hashmap"}'
+pub fn lookup(map: &::std::collections::HashMap<String, u32>) {}
@@ -0,0 +1,21 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(staged_api)]
+#![stable(feature = "rust1", since = "1.0.0")]
+#![crate_name = "foo"]
+
+// @matches search-index.js '"new_fn",[^\]]*\],"output":null\},"1\.20\.0","new fn"\]'
+#[stable(feature = "new_fn", since = "1.20.0")]
+pub fn new_fn() {}
+
+// @matches search-index.js '"unstable_fn",[^\]]*\],"output":null\},null,"unstable fn"\]'
+#[unstable(feature = "unstable_fn", issue = "0")]
+pub fn unstable_fn() {}
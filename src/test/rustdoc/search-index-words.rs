@@ -0,0 +1,20 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @matches search-index.js '"HashMapLike","[^"]*","",null,null,null,"hash map like"\]'
+pub struct HashMapLike;
+
+// @matches search-index.js '"into_iter","[^"]*","",null,\{[^}]*\},null,"into iter"\]'
+pub fn into_iter() {}
+
+// @matches search-index.js '"Single","[^"]*","",null,null\]'
+pub struct Single;
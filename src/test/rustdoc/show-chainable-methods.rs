@@ -0,0 +1,36 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --show-chainable-methods
+
+#![crate_name = "foo"]
+
+pub struct Builder;
+
+pub struct Error;
+
+// @has foo/struct.Builder.html
+impl Builder {
+    // @!has - '//*[@id="method.new"]//span[@class="chainable"]' 'chainable'
+    pub fn new() -> Builder { Builder }
+
+    // @has - '//*[@id="method.name"]//span[@class="chainable"]' 'chainable'
+    pub fn name(self, _: &str) -> Self { self }
+
+    // @has - '//*[@id="method.size"]//span[@class="chainable"]' 'chainable'
+    pub fn size(&mut self, _: usize) -> &mut Builder { self }
+
+    // @has - '//*[@id="method.check"]//span[@class="chainable"]' 'chainable?'
+    pub fn check(self) -> Result<Self, Error> { Ok(self) }
+
+    // @!has - '//*[@id="method.build"]//span[@class="chainable"]' 'chainable'
+    pub fn build(self) -> u32 { 0 }
+}
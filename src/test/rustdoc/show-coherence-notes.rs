@@ -0,0 +1,37 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --show-coherence-notes
+
+#![crate_name = "foo"]
+
+// @has foo/trait.Describe.html
+// @has - '//h2[@id="foreign-impls"]' 'Implementations on Foreign Types'
+// @count - '//div[@class="coherence-note"]' 2
+// @has - '//div[@class="coherence-note"]' \
+//      'Permitted because the trait is defined in the implementing crate.'
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for Vec<u8> {
+    fn describe(&self) -> String { String::new() }
+}
+
+impl Describe for String {
+    fn describe(&self) -> String { String::new() }
+}
+
+pub struct Local;
+
+impl Describe for Local {
+    fn describe(&self) -> String { String::new() }
+}
@@ -0,0 +1,43 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --show-dyn-signatures
+
+#![crate_name = "foo"]
+
+// @has foo/trait.Shape.html
+pub trait Shape {
+    // @has - '//*[@id="tymethod.boxed"]' 'fn boxed(&self) -> Box<Self>'
+    // @has - '//div[@class="dyn-signature"]' 'fn boxed(&self) -> Box<Shape>'
+    fn boxed(&self) -> Box<Self>;
+
+    // @has - '//div[@class="dyn-signature"]' 'fn same(&self, other: &Shape) -> bool'
+    fn same(&self, other: &Self) -> bool;
+
+    // @count - '//div[@class="dyn-signature"]' 2
+    fn new() -> Self where Self: Sized;
+
+    fn by_value(self) -> Self where Self: Sized;
+
+    // Methods which can't be called through a trait object only get their
+    // normal signature.
+    // @has - '//*[@id="tymethod.generic"]' 'fn generic<T>(&self, t: T)'
+    // @!has - '//div[@class="dyn-signature"]' 'fn generic'
+    fn generic<T>(&self, t: T);
+
+    // @has - '//*[@id="tymethod.duplicate"]' 'fn duplicate(&self) -> Self'
+    // @!has - '//div[@class="dyn-signature"]' 'fn duplicate'
+    fn duplicate(&self) -> Self;
+
+    // @has - '//*[@id="tymethod.sized_only"]' 'fn sized_only(&self)'
+    // @!has - '//div[@class="dyn-signature"]' 'fn sized_only'
+    fn sized_only(&self) where Self: Sized;
+}
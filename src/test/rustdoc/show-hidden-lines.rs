@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --show-hidden-lines
+
+#![crate_name = "foo"]
+
+// @has foo/fn.bar.html
+// @has - '//pre[@class="rust rust-example-rendered"]/span[@class="hidden-line"]' 'fn main'
+// @has - '//pre[@class="rust rust-example-rendered"]' 'let x = foo::bar();'
+/// ```
+/// # fn main() {
+/// let x = foo::bar();
+/// # }
+/// ```
+pub fn bar() -> u32 { 0 }
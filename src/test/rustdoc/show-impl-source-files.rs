@@ -0,0 +1,31 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --show-impl-source-files
+
+#![crate_name = "foo"]
+
+pub struct Foo;
+
+// @has foo/struct.Foo.html
+// @has - '//div[@class="impl-location"]' 'Defined in show-impl-source-files.rs:19'
+impl Foo {
+    pub fn foo() {}
+}
+
+// @has - '//div[@class="impl-location"]' 'Defined in show-impl-source-files.rs:24'
+impl Foo {
+    pub fn bar() {}
+}
+
+// @count - '//div[@class="impl-location"]' 2
+impl Clone for Foo {
+    fn clone(&self) -> Foo { Foo }
+}
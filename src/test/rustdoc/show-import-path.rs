@@ -0,0 +1,38 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --show-import-path
+
+#![crate_name = "foo"]
+
+// @!has foo/index.html '//div[@class="import-path"]'
+
+pub mod outer {
+    // @has foo/outer/inner/index.html '//div[@class="import-path"]/code' \
+    //      'use foo::outer::inner;'
+    pub mod inner {
+        // @has foo/outer/inner/struct.Deep.html '//div[@class="import-path"]/code' \
+        //      'use foo::outer::inner::Deep;'
+        pub struct Deep;
+    }
+}
+
+mod hidden {
+    pub struct Reexported;
+}
+
+// @has foo/struct.Reexported.html '//div[@class="import-path"]/code' 'use foo::Reexported;'
+pub use hidden::Reexported;
+
+// @!has foo/macro.mac.html '//div[@class="import-path"]'
+#[macro_export]
+macro_rules! mac {
+    () => {}
+}
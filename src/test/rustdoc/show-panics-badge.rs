@@ -0,0 +1,82 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --show-panics-badge
+
+#![crate_name = "foo"]
+
+// @has foo/fn.may_panic.html
+// @has - '//div[@class="panics-badge"]/a[@href="#panics"]' 'May panic'
+/// Divides two numbers.
+///
+/// # Panics
+///
+/// Panics if `b` is zero.
+pub fn may_panic(a: u32, b: u32) -> u32 {
+    a / b
+}
+
+// @has foo/fn.never_panics.html
+// @!has - '//div[@class="panics-badge"]'
+/// Adds two numbers, wrapping on overflow.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(foo::never_panics(1, 2), 3);
+/// ```
+pub fn never_panics(a: u32, b: u32) -> u32 {
+    a.wrapping_add(b)
+}
+
+// @has foo/struct.Stack.html
+// @has - '//div[@class="panics-badge"]/a[@href="#panics"]' 'May panic'
+// @has - '//h1[@id="panics"]' 'Panics'
+// @has - '//div[@class="panics-badge"]/a[@href="#panics-1"]' 'May panic'
+// @has - '//h1[@id="panics-1"]' 'Panics'
+// @count - '//div[@class="panics-badge"]' 2
+pub struct Stack(Vec<u32>);
+
+impl Stack {
+    /// Removes the top of the stack.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stack is empty.
+    pub fn pop(&mut self) -> u32 {
+        self.0.pop().unwrap()
+    }
+
+    /// Returns the top of the stack.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stack is empty.
+    pub fn top(&self) -> u32 {
+        *self.0.last().unwrap()
+    }
+
+    /// Returns the size of the stack.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+// @has foo/trait.Parse.html
+// @has - '//div[@class="panics-badge"]/a[@href="#panics"]' 'May panic'
+pub trait Parse {
+    /// Parses `s`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is malformed.
+    fn parse(s: &str) -> Self;
+}
@@ -0,0 +1,41 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --show-type-usage
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Foo.html '//div[@class="type-usage"]/a[@href="?search=Foo"]' \
+//      'Used in 2 functions'
+pub struct Foo;
+
+// @has foo/enum.Bar.html '//div[@class="type-usage"]' 'Used in 1 function'
+pub enum Bar {
+    A,
+}
+
+// @!has foo/struct.Unused.html '//div[@class="type-usage"]'
+pub struct Unused;
+
+pub fn make() -> Foo {
+    Foo
+}
+
+pub fn consume(_: Foo, _: Bar) {}
+
+pub mod other {
+    // A type named like another one only counts the functions using it.
+    // @has foo/other/struct.Foo.html '//div[@class="type-usage"]' 'Used in 1 function'
+    pub struct Foo;
+
+    pub fn maybe_make() -> Option<Foo> {
+        None
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @matches foo/sidebar-items.js '(?s)"mod":.*"struct":.*"enum":.*"constant":.*"fn":.*"union":'
+pub union Union { a: u8 }
+pub fn function() {}
+pub const CONSTANT: u8 = 0;
+pub enum Enum {}
+pub struct Struct;
+pub mod module {}
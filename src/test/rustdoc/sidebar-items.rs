@@ -11,13 +11,13 @@
 #![crate_name = "foo"]
 
 // @has foo/trait.Foo.html
-// @has - '//*[@class="sidebar-title"][@href="#required-methods"]' 'Required Methods'
+// @has - '//*[@class="sidebar-title"][@href="#required-methods"]' 'Required Methods (1)'
 // @has - '//*[@class="sidebar-links"]/a' 'bar'
-// @has - '//*[@class="sidebar-title"][@href="#provided-methods"]' 'Provided Methods'
+// @has - '//*[@class="sidebar-title"][@href="#provided-methods"]' 'Provided Methods (1)'
 // @has - '//*[@class="sidebar-links"]/a' 'foo'
-// @has - '//*[@class="sidebar-title"][@href="#associated-const"]' 'Associated Constants'
+// @has - '//*[@class="sidebar-title"][@href="#associated-const"]' 'Associated Constants (1)'
 // @has - '//*[@class="sidebar-links"]/a' 'BAR'
-// @has - '//*[@class="sidebar-title"][@href="#associated-types"]' 'Associated Types'
+// @has - '//*[@class="sidebar-title"][@href="#associated-types"]' 'Associated Types (1)'
 // @has - '//*[@class="sidebar-links"]/a' 'Output'
 pub trait Foo {
     const BAR: u32 = 0;
@@ -27,6 +27,14 @@ pub trait Foo {
     fn bar() -> Self::Output;
 }
 
+// @has foo/trait.Baz.html
+// @has - '//*[@class="sidebar-title"][@href="#required-methods"]' 'Required Methods (2)'
+// @count - '//*[@class="sidebar-title"][@href="#provided-methods"]' 0
+pub trait Baz {
+    fn baz();
+    fn quux();
+}
+
 // @has foo/struct.Bar.html
 // @has - '//*[@class="sidebar-title"][@href="#fields"]' 'Fields'
 // @has - '//*[@class="sidebar-links"]/a[@href="#structfield.f"]' 'f'
@@ -0,0 +1,28 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --signatures-only
+
+#![crate_name = "foo"]
+
+// @has foo/fn.add.html '//pre[@class="rust fn"]' 'pub fn add(a: u32, b: u32) -> u32'
+// @!has - '//div[@class="docblock"]' 'Adds two numbers.'
+/// Adds two numbers.
+pub fn add(a: u32, b: u32) -> u32 { a + b }
+
+// @has foo/struct.Foo.html '//*[@id="new.v"]//code' 'pub fn new() -> Foo'
+// @!has - '//div[@class="docblock"]' 'Makes a Foo.'
+// @has - '//*[@class="sidebar"]//a[@href="#method.new"]' 'new'
+pub struct Foo;
+
+impl Foo {
+    /// Makes a Foo.
+    pub fn new() -> Foo { Foo }
+}
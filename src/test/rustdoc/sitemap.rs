@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --sitemap-base-url https://docs.example.com/
+
+#![crate_name = "foo"]
+
+// @has sitemap.xml
+// @matches - '<loc>https://docs\.example\.com/foo/index\.html</loc>'
+// @matches - '<loc>https://docs\.example\.com/foo/struct\.Foo\.html</loc>'
+// @matches - '<loc>https://docs\.example\.com/foo/bar/index\.html</loc>'
+// @matches - '<loc>https://docs\.example\.com/foo/bar/fn\.baz\.html</loc>'
+// @!matches - 'Hidden|variant|A\.html'
+pub struct Foo;
+
+pub mod bar {
+    pub fn baz() {}
+}
+
+pub enum E {
+    A,
+}
+
+mod hidden {
+    pub struct Hidden;
+}
@@ -0,0 +1,27 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --sort-impls
+
+#![crate_name = "foo"]
+
+pub trait Zebra {}
+pub trait Apple {}
+pub trait Mango {}
+
+// @matches foo/struct.Foo.html '(?s)impl Apple for Foo.*impl Mango for Foo.*impl Zebra for Foo'
+pub struct Foo;
+
+impl Zebra for Foo {}
+impl Foo {
+    pub fn new() -> Foo { Foo }
+}
+impl Mango for Foo {}
+impl Apple for Foo {}
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --split-search-index
+
+#![crate_name = "foo"]
+
+//! The foo crate.
+
+// @has search-index.js 'searchIndexFiles["foo"] = "search-index-foo.js";'
+// @has - 'loadSearchIndexes(searchIndexFiles);'
+// @!has - '"Bar"'
+// @has search-index-foo.js 'searchIndex["foo"] = {"doc":"The foo crate."'
+// @has - '"Bar"'
+
+pub struct Bar;
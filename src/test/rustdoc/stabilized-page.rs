@@ -0,0 +1,41 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --stabilized-page
+
+#![crate_name = "foo"]
+#![feature(staged_api)]
+#![stable(feature = "foo", since = "1.0.0")]
+
+// @has stabilized.html
+// @has - '//details/summary' '1.2.0 (2)'
+// @has - '//a[@href="foo/struct.Old.html"]' 'foo::Old'
+// @has - '//a[@href="foo/fn.newer.html"]' 'foo::newer'
+// @!has - 'foo::Experimental'
+// @matches - '(?s)1\.2\.0.*1\.10\.0'
+#[stable(feature = "old", since = "1.2.0")]
+pub struct Old;
+
+#[stable(feature = "old", since = "1.2.0")]
+pub fn old() {}
+
+#[stable(feature = "newer", since = "1.10.0")]
+pub fn newer() {}
+
+// Associated items link to their parent's page.
+// @has - '//a[@href="foo/struct.Old.html#method.new"]' 'foo::Old::new'
+impl Old {
+    #[stable(feature = "newer", since = "1.10.0")]
+    pub fn new() -> Old { Old }
+}
+
+#[unstable(feature = "experimental", issue = "0")]
+pub struct Experimental;
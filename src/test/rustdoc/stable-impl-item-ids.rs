@@ -0,0 +1,38 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --stable-impl-item-ids
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Foo.html '//h4[@id="method.new"]' 'fn new'
+// @!has - '//h4[@id="method.from"]'
+// @!has - '//h4[@id="method.from-1"]'
+// @matches - "id='method\.from-[0-9a-f]{8}'"
+pub struct Foo;
+
+impl Foo {
+    pub fn new() -> Foo {
+        Foo
+    }
+}
+
+impl From<u8> for Foo {
+    fn from(_: u8) -> Foo {
+        Foo
+    }
+}
+
+impl From<u16> for Foo {
+    fn from(_: u16) -> Foo {
+        Foo
+    }
+}
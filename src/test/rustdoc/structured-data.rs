@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --emit-structured-data
+
+#![crate_name = "foo"]
+
+// @matches foo/struct.Foo.html '<script type="application/ld\+json">\{"@context":'
+// @matches foo/struct.Foo.html '"description":"A \\u003cb>Foo\\u003c/b>\."'
+// @matches foo/struct.Foo.html '"genre":"struct","name":"foo::Foo","url":"../foo/struct.Foo.html"'
+/// A `<b>Foo</b>`.
+pub struct Foo;
+
+// @!matches foo/fn.bar.html '"description"'
+// @matches foo/fn.bar.html '"genre":"fn","name":"foo::bar"'
+pub fn bar() {}
@@ -0,0 +1,27 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z unstable-options --summary-truncation end --summary-ellipsis ~
+
+#![crate_name = "foo"]
+
+// @has foo/index.html '//td[@class="docblock-short"]' \
+//      'This summary is long enough that it does not fit in the module listing~'
+// @!has - '//td[@class="docblock-short"]' 'listing table'
+
+/// This summary is long enough that it does not fit in the module listing table.
+pub struct Long;
+
+// The markdown is stripped before the summary is cut, rather than cut open.
+// @has foo/index.html '//td[@class="docblock-short"]' 'Links to `Long` from a summary'
+// @!has - '//td[@class="docblock-short"]' 'struct.Long.html'
+// @!has - '//td[@class="docblock-short"]' 'cut short.'
+/// Links to [`Long`](struct.Long.html) from a summary which is also long enough to be cut short.
+pub struct Linked;
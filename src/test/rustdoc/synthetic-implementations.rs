@@ -0,0 +1,40 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+#![feature(optin_builtin_traits)]
+
+use std::marker::PhantomData;
+
+// @has foo/struct.Foo.html
+// @has - '//h2[@id="implementations"]' 'Trait Implementations'
+// @has - '//*[@class="impl"]//code' 'impl Clone for Foo'
+// @has - '//h2[@id="synthetic-implementations"]' 'Auto Trait Implementations'
+// @has - '//div[@class="synthetic-implementations collapsed"]//code' 'impl Send for Foo'
+// @has - '//div[@class="synthetic-implementations collapsed"]//code' 'impl !Sync for Foo'
+// @!has - '//div[@class="synthetic-implementations collapsed"]//code' 'impl Clone for Foo'
+// @has - '//a[@href="#synthetic-implementations"]' 'Auto Trait Implementations'
+pub struct Foo(PhantomData<*const u8>);
+
+impl Clone for Foo {
+    fn clone(&self) -> Foo { Foo(PhantomData) }
+}
+
+unsafe impl Send for Foo {}
+impl !Sync for Foo {}
+
+// @has foo/struct.Bar.html
+// @!has - '//h2[@id="synthetic-implementations"]' ''
+pub struct Bar;
+
+impl Clone for Bar {
+    fn clone(&self) -> Bar { Bar }
+}
@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --theme {{src-base}}/auxiliary/dark.css
+
+#![crate_name = "foo"]
+
+// @has theme-dark.css 'background-color: #353535;'
+// @has foo/struct.Foo.html
+// @has - '//link[@data-theme="dark"]/@href' '../theme-dark.css'
+// @has - '//link[@data-theme="dark"]/@rel' 'alternate stylesheet'
+// @has - '//select[@id="theme-picker"]/option[@value="dark"]' 'dark'
+// @has - '//select[@id="theme-picker"]/option[@value=""]' 'default'
+// @has - 'window.themes = ["dark"];'
+pub struct Foo;
@@ -0,0 +1,27 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+pub trait AVeryLongTraitNameNumberOne {}
+pub trait AVeryLongTraitNameNumberTwo {}
+pub trait AVeryLongTraitNameNumberThree {}
+
+// @has foo/trait.Short.html '//pre[@class="rust trait"]' 'pub trait Short: Clone + Copy {'
+// @count - '//pre[@class="rust trait"]/br' 0
+pub trait Short: Clone + Copy {}
+
+// @has foo/trait.Long.html '//pre[@class="rust trait"]' \
+//      'pub trait Long: AVeryLongTraitNameNumberOne + AVeryLongTraitNameNumberTwo'
+// @has - '//pre[@class="rust trait"]' '+ AVeryLongTraitNameNumberThree {'
+// @count - '//pre[@class="rust trait"]/br' 3
+pub trait Long: AVeryLongTraitNameNumberOne + AVeryLongTraitNameNumberTwo
+    + AVeryLongTraitNameNumberThree {}
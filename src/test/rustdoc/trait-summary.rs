@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @has foo/trait.Shape.html '//div[@class="trait-summary"]' \
+//      '2 required methods, 1 provided method, 1 associated type'
+pub trait Shape {
+    type Output;
+
+    fn area(&self) -> Self::Output;
+    fn perimeter(&self) -> Self::Output;
+    fn describe(&self) -> String { String::new() }
+}
+
+// @has foo/trait.Consts.html '//div[@class="trait-summary"]' '1 associated constant'
+pub trait Consts {
+    const ID: u32;
+}
+
+// @!has foo/trait.Marker.html '//div[@class="trait-summary"]'
+pub trait Marker {}
@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Pair.html
+// @has - '//h2[@id="fields"]' 'Tuple Fields'
+// @has - '//span[@id="structfield.0"]/a[@href="#structfield.0"]' ''
+// @has - '//span[@id="structfield.0"]//code' '0: u32'
+// @has - '//span[@id="structfield.2"]//code' '2: String'
+// @!has - '//span[@id="structfield.1"]' ''
+// @has - '//div[@class="docblock"]' 'The width.'
+pub struct Pair(
+    /// The width.
+    pub u32,
+    u8,
+    pub String,
+);
+
+// @has foo/struct.Undocumented.html
+// @!has - '//h2[@id="fields"]' ''
+pub struct Undocumented(pub u32, pub u32);
@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -Z unstable-options --type-aliases-page
+
+#![crate_name = "foo"]
+
+pub struct Error;
+
+// @has type-aliases.html
+// @has - '//a[@href="foo/type.Result.html"]' 'foo::Result'
+// @has - '//td/code' 'Result<T, Error>'
+// @has - '//td[@class="docblock-short"]' 'Results using our error type.'
+/// Results using our error type.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+pub mod inner {
+    // @has type-aliases.html '//a[@href="foo/inner/type.Bytes.html"]' 'foo::inner::Bytes'
+    pub type Bytes = Vec<u8>;
+}
+
+// @!has type-aliases.html 'foo::Error'
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+pub unsafe trait Zeroable {}
+pub trait Named {}
+
+// @has foo/struct.Foo.html
+// @has - '//h3[@class="impl impl-unsafe"]/span/code' 'impl Zeroable for Foo'
+// @has - '//h3[@id="impl-Zeroable"]//span[@class="unsafe-impl-badge"]' 'unsafe impl'
+// @has - '//h3[@id="impl-Named"][@class="impl"]' 'impl Named for Foo'
+// @count - '//h3[@id="impl-Named"]//span[@class="unsafe-impl-badge"]' 0
+pub struct Foo;
+
+unsafe impl Zeroable for Foo {}
+impl Named for Foo {}